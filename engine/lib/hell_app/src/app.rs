@@ -1,6 +1,6 @@
 use hell_common::window::{HellWindow, HellWindowExtent};
 use hell_core::error::HellResult;
-use hell_input::InputManager;
+use hell_input::{InputManager, InputReplayer};
 use hell_renderer::render_types::RenderPackage;
 use hell_renderer::{HellRenderer, HellRendererInfo, config};
 
@@ -15,7 +15,8 @@ pub trait HellGame {
     fn render_package(&self) -> &RenderPackage;
 
     fn init_game(&mut self, renderer: &mut HellRenderer) -> HellResult<()>;
-    fn update_game(&mut self, delta_time: f32, input: &InputManager) -> HellResult<()>;
+    // the window can be used to change the title, cursor, fullscreen mode or to request closing the app
+    fn update_game(&mut self, delta_time: f32, input: &InputManager, window: &mut dyn HellWindow) -> HellResult<()>;
 }
 
 
@@ -24,10 +25,12 @@ pub trait HellGame {
 // hell-app
 // ----------------------------------------------------------------------------
 
+// without a renderer the game is still updated every frame, but nothing is drawn - e.g. for tests or servers
 pub struct HellApp {
-    renderer: HellRenderer,
+    renderer: Option<HellRenderer>,
     game: &'static mut dyn HellGame,
     pub input: InputManager,
+    replayer: Option<InputReplayer>,
 }
 
 
@@ -44,52 +47,278 @@ impl HellApp {
         };
 
         let renderer = HellRenderer::new(info)?;
-        let input = InputManager::new();
+        Ok(Self::with_renderer(Some(renderer), game))
+    }
 
-        Ok(Self {
+    pub fn with_renderer(renderer: Option<HellRenderer>, game: &'static mut dyn HellGame) -> Self {
+        Self {
             renderer,
             game,
-            input,
-        })
+            input: InputManager::new(),
+            replayer: None,
+        }
+    }
+
+    pub fn has_renderer(&self) -> bool {
+        self.renderer.is_some()
     }
 }
 
 impl HellApp {
+    // 'HellGame::init_game' only creates render resources, so it is skipped without a renderer
     pub fn init_game(&mut self) -> HellResult<()> {
-        self.game.init_game(&mut self.renderer)?;
-        self.renderer.prepare_renderer()?;
+        if let Some(renderer) = &mut self.renderer {
+            self.game.init_game(renderer)?;
+            renderer.prepare_renderer()?;
+        }
 
         Ok(())
     }
 
 
-    fn update_game(&mut self, delta_time: f32) -> HellResult<()> {
-        self.game.update_game(delta_time, &self.input)
+    fn update_game(&mut self, delta_time: f32, window: &mut dyn HellWindow) -> HellResult<()> {
+        self.game.update_game(delta_time, &self.input, window)
     }
 }
 
 impl HellApp {
     pub fn handle_window_changed(&mut self, window_extent: HellWindowExtent) -> HellResult<()> {
         self.wait_idle()?;
-        self.renderer.handle_window_changed(window_extent)
+        match &mut self.renderer {
+            Some(renderer) => renderer.handle_window_changed(window_extent),
+            None => Ok(()),
+        }
     }
 
     pub fn wait_idle(&self) -> HellResult<()> {
-        self.renderer.wait_idle()
+        match &self.renderer {
+            Some(renderer) => renderer.wait_idle(),
+            None => Ok(()),
+        }
     }
 
+    // call once after every frame
     pub fn advance_frame(&mut self) -> HellResult<()> {
-        self.input.reset_released_keys();
+        self.input.advance_frame();
 
         Ok(())
     }
 
-    pub fn draw_frame(&mut self, delta_time: f32) -> HellResult<bool> {
+    // returns true if the swapchain has to be recreated, which never happens without a renderer
+    pub fn draw_frame(&mut self, delta_time: f32, window: &mut dyn HellWindow) -> HellResult<bool> {
         // std::thread::sleep(std::time::Duration::from_millis(250));
         // let delta_time = 0.1;
 
-        self.update_game(delta_time)?;
-        let render_pkg = self.game.render_package();
-        self.renderer.draw_frame(delta_time, render_pkg)
+        let delta_time = self.replay_frame(delta_time)?;
+        self.input.record_frame(delta_time);
+
+        self.input.poll_gamepads();
+        self.update_game(delta_time, window)?;
+
+        match &mut self.renderer {
+            Some(renderer) => renderer.draw_frame(delta_time, self.game.render_package()),
+            None => Ok(false),
+        }
+    }
+}
+
+// replay
+// ------
+impl HellApp {
+    // the recorded key and modifier input replaces the live one, delta times are taken from the recording as well
+    pub fn start_replay(&mut self, replayer: InputReplayer) {
+        self.replayer = Some(replayer);
+    }
+
+    pub fn stop_replay(&mut self) -> Option<InputReplayer> {
+        self.replayer.take()
+    }
+
+    // false once all recorded frames have been replayed
+    pub fn is_replaying(&self) -> bool {
+        self.replayer.as_ref().map(|r| !r.is_finished()).unwrap_or(false)
+    }
+
+    fn replay_frame(&mut self, delta_time: f32) -> HellResult<f32> {
+        match &mut self.replayer {
+            Some(replayer) => Ok(replayer.replay_frame(&mut self.input)?.unwrap_or(delta_time)),
+            None => Ok(delta_time),
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::time::{Duration, Instant};
+
+    use hell_common::mock_window::MockWindow;
+    use hell_input::{InputPattern, InputRecording, KeyCode, KeyState, SequenceMatcher};
+
+    use super::*;
+
+    // what the game saw in a single frame
+    #[derive(Debug, Clone, PartialEq)]
+    struct FrameLog {
+        delta_time: f32,
+        jump_pressed: bool,
+        jump_state: KeyState,
+        combo: bool,
+    }
+
+    #[derive(Default)]
+    struct TestGame {
+        render_pkg: RenderPackage,
+        sequences: SequenceMatcher,
+        log: Rc<RefCell<Vec<FrameLog>>>,
+    }
+
+    impl HellGame for TestGame {
+        fn render_package(&self) -> &RenderPackage {
+            &self.render_pkg
+        }
+
+        fn init_game(&mut self, _renderer: &mut HellRenderer) -> HellResult<()> {
+            panic!("the game must not be initialized without a renderer");
+        }
+
+        fn update_game(&mut self, delta_time: f32, input: &InputManager, window: &mut dyn HellWindow) -> HellResult<()> {
+            self.sequences.update(input);
+            self.log.borrow_mut().push(FrameLog {
+                delta_time,
+                jump_pressed: input.just_pressed(KeyCode::Space),
+                jump_state: input.key_state(KeyCode::Space),
+                combo: self.sequences.is_recognized("combo"),
+            });
+
+            if input.just_pressed(KeyCode::Escape) {
+                window.request_close();
+            }
+
+            Ok(())
+        }
+    }
+
+    fn headless_app() -> (HellApp, Rc<RefCell<Vec<FrameLog>>>) {
+        let game = TestGame {
+            sequences: SequenceMatcher::new(vec![InputPattern::sequence("combo", &[KeyCode::A, KeyCode::B, KeyCode::C], 0.1)]),
+            ..Default::default()
+        };
+        let log = game.log.clone();
+
+        let mut app = HellApp::with_renderer(None, Box::leak(Box::new(game)));
+        app.init_game().unwrap();
+        (app, log)
+    }
+
+    // same order as the main loop of the window: input events, update + draw, end of frame
+    fn run_frame(app: &mut HellApp, window: &mut MockWindow, delta_time: f32, keys: &[(KeyCode, KeyState)]) {
+        for (key, state) in keys {
+            app.input.update_key_state(*key, *state).unwrap();
+        }
+
+        assert!(!app.draw_frame(delta_time, window).unwrap());
+        app.advance_frame().unwrap();
+    }
+
+    // like 'run_frame', but the keys are received at the given times and the frame ends at 'frame_end'
+    fn run_timed_frame(app: &mut HellApp, window: &mut MockWindow, frame_end: Instant, delta_time: f32, keys: &[(KeyCode, KeyState, Instant)]) {
+        for (key, state, time) in keys {
+            app.input.set_event_time(Some(*time));
+            app.input.update_key_state(*key, *state).unwrap();
+        }
+
+        app.input.set_event_time(Some(frame_end));
+        assert!(!app.draw_frame(delta_time, window).unwrap());
+        app.advance_frame().unwrap();
+    }
+
+    #[test]
+    fn runs_without_a_renderer() {
+        let (mut app, log) = headless_app();
+        let mut window = MockWindow::default();
+        assert!(!app.has_renderer());
+
+        run_frame(&mut app, &mut window, 0.016, &[(KeyCode::Space, KeyState::Pressed)]);
+        run_frame(&mut app, &mut window, 0.017, &[]);
+        run_frame(&mut app, &mut window, 0.018, &[(KeyCode::Space, KeyState::Released)]);
+        assert!(!window.close_requested);
+
+        run_frame(&mut app, &mut window, 0.019, &[(KeyCode::Escape, KeyState::Pressed)]);
+        assert!(window.close_requested);
+
+        let log = log.borrow();
+        assert_eq!(log[0], FrameLog { delta_time: 0.016, jump_pressed: true, jump_state: KeyState::Pressed, combo: false });
+        assert_eq!(log[1], FrameLog { delta_time: 0.017, jump_pressed: false, jump_state: KeyState::Held, combo: false });
+        assert_eq!(log[2], FrameLog { delta_time: 0.018, jump_pressed: false, jump_state: KeyState::Released, combo: false });
+        assert_eq!(log[3].jump_state, KeyState::Inactive);
+
+        app.handle_window_changed(HellWindowExtent::new(1024, 768)).unwrap();
+        app.wait_idle().unwrap();
+    }
+
+    #[test]
+    fn replays_a_recording() {
+        let (mut app, live_log) = headless_app();
+        let mut window = MockWindow::default();
+
+        app.input.start_recording();
+        run_frame(&mut app, &mut window, 0.016, &[(KeyCode::Space, KeyState::Pressed)]);
+        run_frame(&mut app, &mut window, 0.020, &[]);
+        run_frame(&mut app, &mut window, 0.033, &[(KeyCode::Space, KeyState::Released)]);
+        let recording = app.input.stop_recording().unwrap();
+
+        let recording = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+        let (mut app, replay_log) = headless_app();
+        app.start_replay(InputReplayer::new(recording));
+
+        // live input and delta times are replaced by the recording
+        while app.is_replaying() {
+            run_frame(&mut app, &mut window, 1.0, &[]);
+        }
+
+        assert_eq!(*replay_log.borrow(), *live_log.borrow());
+    }
+
+    #[test]
+    fn replays_the_timing_of_a_sequence() {
+        let (mut app, live_log) = headless_app();
+        let mut window = MockWindow::default();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        app.input.set_event_time(Some(start));
+        app.input.start_recording();
+        // a, b and c within the max. delay of 100 ms, spread over two frames
+        run_timed_frame(&mut app, &mut window, at(64), 0.064, &[
+            (KeyCode::A, KeyState::Pressed, at(2)), (KeyCode::A, KeyState::Released, at(4)), (KeyCode::B, KeyState::Pressed, at(60)),
+        ]);
+        run_timed_frame(&mut app, &mut window, at(116), 0.052, &[
+            (KeyCode::B, KeyState::Released, at(70)), (KeyCode::C, KeyState::Pressed, at(110)),
+        ]);
+        // the delay between b and c is too long, even though they are only one frame apart
+        run_timed_frame(&mut app, &mut window, at(132), 0.016, &[
+            (KeyCode::C, KeyState::Released, at(120)), (KeyCode::A, KeyState::Pressed, at(125)), (KeyCode::B, KeyState::Pressed, at(130)),
+        ]);
+        run_timed_frame(&mut app, &mut window, at(432), 0.3, &[
+            (KeyCode::C, KeyState::Pressed, at(431)),
+        ]);
+        let recording = app.input.stop_recording().unwrap();
+
+        let combos: Vec<bool> = live_log.borrow().iter().map(|f| f.combo).collect();
+        assert_eq!(combos, vec![false, true, false, false]);
+
+        let recording = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+        let (mut app, replay_log) = headless_app();
+        app.start_replay(InputReplayer::new(recording));
+
+        while app.is_replaying() {
+            run_frame(&mut app, &mut window, 1.0, &[]);
+        }
+
+        assert_eq!(*replay_log.borrow(), *live_log.borrow());
     }
 }
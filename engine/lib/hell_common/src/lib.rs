@@ -5,4 +5,6 @@
 
 
 pub mod window;
+pub mod mock_window;
 pub mod transform;
+pub mod transform_hierarchy;
@@ -0,0 +1,92 @@
+use hell_core::error::{HellResult, HellError, HellErrorKind};
+
+use crate::window::{HellWindow, HellSurfaceInfo, HellWindowExtent, HellWindowMode, HellCursorMode};
+
+
+
+// window without a display - creates a headless surface and only records the requested state
+#[derive(Debug, Clone)]
+pub struct MockWindow {
+    pub extent: HellWindowExtent,
+    pub title: String,
+    pub window_mode: HellWindowMode,
+    pub cursor_mode: HellCursorMode,
+    pub scale_factor: f64,
+    pub close_requested: bool,
+    // makes 'set_cursor_mode' fail for 'Grabbed', like on platforms without cursor grabbing
+    pub fail_cursor_grab: bool,
+}
+
+impl MockWindow {
+    pub fn new(title: &str, width: u32, height: u32) -> Self {
+        Self {
+            extent: HellWindowExtent::new(width, height),
+            title: title.to_string(),
+            window_mode: HellWindowMode::default(),
+            cursor_mode: HellCursorMode::default(),
+            scale_factor: 1.0,
+            close_requested: false,
+            fail_cursor_grab: false,
+        }
+    }
+}
+
+impl Default for MockWindow {
+    fn default() -> Self {
+        Self::new("mock-window", 800, 600)
+    }
+}
+
+impl HellWindow for MockWindow {
+    fn create_surface_info(&self) -> HellResult<HellSurfaceInfo> {
+        Ok(HellSurfaceInfo::new_headless())
+    }
+
+    fn get_window_extent(&self) -> HellWindowExtent {
+        self.extent
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
+    fn window_mode(&self) -> HellWindowMode {
+        self.window_mode
+    }
+
+    fn set_window_mode(&mut self, mode: HellWindowMode) {
+        self.window_mode = mode;
+    }
+
+    fn cursor_mode(&self) -> HellCursorMode {
+        self.cursor_mode
+    }
+
+    fn set_cursor_mode(&mut self, mode: HellCursorMode) -> HellResult<()> {
+        if self.fail_cursor_grab && mode == HellCursorMode::Grabbed {
+            return Err(HellError::from_msg(
+                HellErrorKind::WindowError,
+                "cursor grabbing is not supported by the mock window".to_string()
+            ));
+        }
+
+        self.cursor_mode = mode;
+        Ok(())
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    fn request_close(&mut self) {
+        self.close_requested = true;
+    }
+
+    fn is_close_requested(&self) -> bool {
+        self.close_requested
+    }
+}
@@ -1,6 +1,11 @@
-use glam::{Mat4, Vec3, Quat};
+use std::ops::Mul;
 
-#[derive(Debug, Clone)]
+use glam::{Mat3, Mat4, Vec3, Quat};
+use hell_core::error::{HellResult, HellError, HellErrorKind};
+
+// missing fields fall back to the identity when deserializing
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct Transform {
     pub translation: Vec3,
     pub rotation: Quat,
@@ -9,6 +14,11 @@ pub struct Transform {
 
 impl Transform {
     pub const IDENTITY: Transform = Transform::identity();
+
+    // the engine uses a left-handed coordinate system
+    pub const FORWARD: Vec3 = Vec3::Z;
+    pub const RIGHT: Vec3 = Vec3::X;
+    pub const UP: Vec3 = Vec3::Y;
 }
 
 impl Transform {
@@ -39,6 +49,16 @@ impl Transform {
             self.translation
         )
     }
+
+    // fails if the matrix contains skew or a projection, which can't be represented by a transform
+    pub fn from_matrix(mat: &Mat4) -> HellResult<Self> {
+        let mat = hell_math::Mat4::from(*mat);
+
+        let (scale, rotation, translation) = mat.to_scale_rotation_translation()
+            .map_err(|e| HellError::from_msg(HellErrorKind::GenericError, format!("failed to decompose matrix: {}", e)))?;
+
+        Ok(Self::new(translation.into(), rotation.into(), scale.into()))
+    }
 }
 
 
@@ -112,3 +132,217 @@ impl Transform {
         self.scale *= factor;
     }
 }
+
+// orientation
+// -----------
+impl Transform {
+    pub fn forward(&self) -> Vec3 {
+        self.rotation * Self::FORWARD
+    }
+
+    pub fn right(&self) -> Vec3 {
+        self.rotation * Self::RIGHT
+    }
+
+    pub fn up(&self) -> Vec3 {
+        self.rotation * Self::UP
+    }
+
+    // rotates the transform so that 'forward' points along 'direction'
+    // does nothing if the direction is zero or parallel to 'up'
+    pub fn look_to(&mut self, direction: Vec3, up: Vec3) {
+        let forward = direction.normalize_or_zero();
+        let right = up.cross(forward).normalize_or_zero();
+        if forward == Vec3::ZERO || right == Vec3::ZERO {
+            return;
+        }
+
+        let up = forward.cross(right);
+        self.rotation = Quat::from_mat3(&Mat3::from_cols(right, up, forward));
+    }
+
+    pub fn look_at(&mut self, target: Vec3, up: Vec3) {
+        self.look_to(target - self.translation, up);
+    }
+}
+
+// space conversions
+// -----------------
+impl Transform {
+    // scale, rotate and translate
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.translation + self.rotation * (self.scale * point)
+    }
+
+    // scale and rotate
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        self.rotation * (self.scale * vector)
+    }
+
+    // only rotate, the length of the direction is preserved
+    pub fn transform_direction(&self, direction: Vec3) -> Vec3 {
+        self.rotation * direction
+    }
+
+    pub fn inverse_transform_point(&self, point: Vec3) -> Vec3 {
+        (self.rotation.inverse() * (point - self.translation)) / self.scale
+    }
+
+    // only exact for a uniform scale: the inverse of a rotated, non-uniform scale contains skew, which a
+    // transform can't represent - use 'create_model_mat().inverse()' or 'inverse_transform_point' in that case
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.inverse();
+        let scale = self.scale.recip();
+        let translation = scale * (rotation * -self.translation);
+
+        Self::new(translation, rotation, scale)
+    }
+
+    // applies 'child' first and 'self' second, like 'self.create_model_mat() * child.create_model_mat()'
+    // exact as long as the scale of 'self' is uniform
+    pub fn mul_transform(&self, child: &Transform) -> Self {
+        Self::new(
+            self.transform_point(child.translation),
+            self.rotation * child.rotation,
+            self.scale * child.scale,
+        )
+    }
+}
+
+impl Mul<&Transform> for &Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: &Transform) -> Self::Output {
+        self.mul_transform(rhs)
+    }
+}
+
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Self::Output {
+        self.mul_transform(&rhs)
+    }
+}
+
+// interpolation
+// -------------
+impl Transform {
+    // linear translation and scale, normalized linear rotation - cheap, but the angular velocity is not constant
+    pub fn lerp(&self, end: &Transform, t: f32) -> Self {
+        let end_rotation = if self.rotation.dot(end.rotation) < 0.0 { -end.rotation } else { end.rotation };
+
+        Self::new(
+            self.translation.lerp(end.translation, t),
+            self.rotation.lerp(end_rotation, t).normalize(),
+            self.scale.lerp(end.scale, t),
+        )
+    }
+
+    // linear translation and scale, spherical rotation
+    pub fn slerp(&self, end: &Transform, t: f32) -> Self {
+        Self::new(
+            self.translation.lerp(end.translation, t),
+            self.rotation.slerp(end.rotation, t),
+            self.scale.lerp(end.scale, t),
+        )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn assert_mat_eq(lhs: Mat4, rhs: Mat4) {
+        assert!(lhs.abs_diff_eq(rhs, EPSILON), "{:?} != {:?}", lhs, rhs);
+    }
+
+    fn assert_vec_eq(lhs: Vec3, rhs: Vec3) {
+        assert!(lhs.abs_diff_eq(rhs, EPSILON), "{:?} != {:?}", lhs, rhs);
+    }
+
+    fn uniform() -> Transform {
+        Transform::new(Vec3::new(1.0, -2.0, 3.0), Quat::from_euler(glam::EulerRot::YXZ, 0.4, -1.1, 2.0), Vec3::splat(2.5))
+    }
+
+    fn non_uniform() -> Transform {
+        Transform::new(Vec3::new(-4.0, 0.5, 2.0), Quat::from_axis_angle(Vec3::new(1.0, 1.0, 0.0).normalize(), 0.7), Vec3::new(1.0, 2.0, 0.5))
+    }
+
+    #[test]
+    fn transform_point_matches_matrix() {
+        let point = Vec3::new(0.3, 4.0, -1.5);
+
+        for t in [uniform(), non_uniform()] {
+            let mat = t.create_model_mat();
+            assert_vec_eq(t.transform_point(point), mat.transform_point3(point));
+            assert_vec_eq(t.transform_vector(point), mat.transform_vector3(point));
+            assert_vec_eq(t.inverse_transform_point(t.transform_point(point)), point);
+        }
+    }
+
+    #[test]
+    fn inverse_matches_matrix() {
+        let t = uniform();
+        assert_mat_eq(t.inverse().create_model_mat(), t.create_model_mat().inverse());
+        assert_mat_eq((t.inverse() * t).create_model_mat(), Mat4::IDENTITY);
+
+        // a rotated, non-uniform scale can't be inverted exactly
+        let t = non_uniform();
+        assert!(!t.inverse().create_model_mat().abs_diff_eq(t.create_model_mat().inverse(), EPSILON));
+    }
+
+    #[test]
+    fn mul_transform_matches_matrix() {
+        let parent = uniform();
+        let child = non_uniform();
+        assert_mat_eq((&parent * &child).create_model_mat(), parent.create_model_mat() * child.create_model_mat());
+        assert_mat_eq((parent * Transform::IDENTITY).create_model_mat(), uniform().create_model_mat());
+    }
+
+    #[test]
+    fn from_matrix_round_trip() {
+        let t = non_uniform();
+        let decomposed = Transform::from_matrix(&t.create_model_mat()).unwrap();
+        assert_mat_eq(decomposed.create_model_mat(), t.create_model_mat());
+    }
+
+    #[test]
+    fn interpolation() {
+        let start = uniform();
+        let end = non_uniform();
+
+        for t in [0.0, 0.5, 1.0] {
+            let lerped = start.lerp(&end, t);
+            let slerped = start.slerp(&end, t);
+
+            assert_vec_eq(lerped.translation, start.translation.lerp(end.translation, t));
+            assert_vec_eq(slerped.scale, start.scale.lerp(end.scale, t));
+            assert!((lerped.rotation.length() - 1.0).abs() < EPSILON);
+            assert!(slerped.rotation.abs_diff_eq(start.rotation.slerp(end.rotation, t), EPSILON));
+        }
+
+        assert_mat_eq(start.lerp(&end, 1.0).create_model_mat(), end.create_model_mat());
+        assert_mat_eq(start.slerp(&end, 0.0).create_model_mat(), start.create_model_mat());
+
+        // the halfway rotation is the same for both, only the speed in between differs
+        let lerped = start.lerp(&end, 0.5).rotation;
+        let slerped = start.slerp(&end, 0.5).rotation;
+        assert!(lerped.abs_diff_eq(slerped, EPSILON) || lerped.abs_diff_eq(-slerped, EPSILON));
+    }
+
+    #[test]
+    fn orientation() {
+        let mut t = Transform::IDENTITY;
+        assert_eq!(t.forward(), Transform::FORWARD);
+
+        t.translation = Vec3::new(1.0, 0.0, 0.0);
+        t.look_at(Vec3::new(1.0, 0.0, -5.0), Transform::UP);
+        assert_vec_eq(t.forward(), Vec3::new(0.0, 0.0, -1.0));
+        assert_vec_eq(t.up(), Transform::UP);
+    }
+}
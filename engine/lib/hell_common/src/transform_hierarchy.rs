@@ -0,0 +1,422 @@
+use glam::Mat4;
+use hell_core::error::{HellResult, HellError, HellErrorKind};
+
+use crate::transform::Transform;
+
+
+
+// the generation is bumped whenever a slot is freed, so handles of removed nodes never alias a later insertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransformHandle {
+    pub idx: usize,
+    pub generation: u32,
+}
+
+impl TransformHandle {
+    pub const fn new(idx: usize, generation: u32) -> Self {
+        Self { idx, generation }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TransformNode {
+    local: Transform,
+    // only valid after 'update_world_matrices' if the node is dirty
+    world: Mat4,
+    parent: Option<TransformHandle>,
+    children: Vec<TransformHandle>,
+    dirty: bool,
+    alive: bool,
+    generation: u32,
+}
+
+impl TransformNode {
+    fn new(local: Transform, parent: Option<TransformHandle>) -> Self {
+        Self {
+            local,
+            world: Mat4::IDENTITY,
+            parent,
+            children: Vec::new(),
+            dirty: true,
+            alive: true,
+            generation: 0,
+        }
+    }
+
+    fn is_valid(&self, handle: TransformHandle) -> bool {
+        self.alive && self.generation == handle.generation
+    }
+}
+
+
+
+// world = parent_world * local
+// changing a local transform marks the node dirty, the world matrices of the node and all of its
+// descendants are recomputed by the next call to 'update_world_matrices'
+// slots of removed nodes are reused by later insertions, accessing a removed node through its old handle panics
+#[derive(Debug, Clone, Default)]
+pub struct TransformHierarchy {
+    nodes: Vec<TransformNode>,
+    free: Vec<usize>,
+}
+
+impl TransformHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, handle: TransformHandle) -> bool {
+        self.nodes.get(handle.idx).map(|n| n.is_valid(handle)).unwrap_or(false)
+    }
+
+    fn node(&self, handle: TransformHandle) -> &TransformNode {
+        let node = &self.nodes[handle.idx];
+        assert!(node.is_valid(handle), "transform handle '{:?}' has been removed", handle);
+        node
+    }
+
+    fn node_mut(&mut self, handle: TransformHandle) -> &mut TransformNode {
+        let node = &mut self.nodes[handle.idx];
+        assert!(node.is_valid(handle), "transform handle '{:?}' has been removed", handle);
+        node
+    }
+
+    fn check_handle(&self, handle: TransformHandle) -> HellResult<()> {
+        if self.contains(handle) {
+            Ok(())
+        } else {
+            Err(HellError::from_msg(HellErrorKind::GenericError, format!("invalid transform handle '{:?}'", handle)))
+        }
+    }
+}
+
+// insertion / removal
+// -------------------
+impl TransformHierarchy {
+    pub fn add(&mut self, local: Transform) -> TransformHandle {
+        self.insert_node(TransformNode::new(local, None))
+    }
+
+    pub fn add_child(&mut self, parent: TransformHandle, local: Transform) -> HellResult<TransformHandle> {
+        self.check_handle(parent)?;
+
+        let handle = self.insert_node(TransformNode::new(local, Some(parent)));
+        self.node_mut(parent).children.push(handle);
+        Ok(handle)
+    }
+
+    fn insert_node(&mut self, mut node: TransformNode) -> TransformHandle {
+        match self.free.pop() {
+            Some(idx) => {
+                node.generation = self.nodes[idx].generation;
+                self.nodes[idx] = node;
+                TransformHandle::new(idx, self.nodes[idx].generation)
+            }
+            None => {
+                self.nodes.push(node);
+                TransformHandle::new(self.nodes.len() - 1, 0)
+            }
+        }
+    }
+
+    // invalidates all handles to the slot
+    fn free_node(&mut self, idx: usize) {
+        let node = &mut self.nodes[idx];
+        node.alive = false;
+        node.generation = node.generation.wrapping_add(1);
+        node.children.clear();
+        self.free.push(idx);
+    }
+
+    // removes the node together with all of its descendants
+    pub fn remove(&mut self, handle: TransformHandle) -> HellResult<()> {
+        self.check_handle(handle)?;
+        self.detach(handle);
+
+        let mut stack = vec![handle];
+        while let Some(curr) = stack.pop() {
+            stack.append(&mut self.node_mut(curr).children);
+            self.free_node(curr.idx);
+        }
+
+        Ok(())
+    }
+
+    // keeps the slots, so handles from before the call stay invalid
+    pub fn clear(&mut self) {
+        for idx in 0..self.nodes.len() {
+            if self.nodes[idx].alive { self.free_node(idx); }
+        }
+    }
+}
+
+// access
+// ------
+impl TransformHierarchy {
+    pub fn local(&self, handle: TransformHandle) -> &Transform {
+        &self.node(handle).local
+    }
+
+    // marks the node as dirty
+    pub fn local_mut(&mut self, handle: TransformHandle) -> &mut Transform {
+        let node = self.node_mut(handle);
+        node.dirty = true;
+        &mut node.local
+    }
+
+    pub fn set_local(&mut self, handle: TransformHandle, local: Transform) {
+        *self.local_mut(handle) = local;
+    }
+
+    // cached value from the last call to 'update_world_matrices'
+    pub fn world_matrix(&self, handle: TransformHandle) -> &Mat4 {
+        &self.node(handle).world
+    }
+
+    // walks up the parent chain instead of using the cache, so the result is always up to date
+    pub fn compute_world_matrix(&self, handle: TransformHandle) -> Mat4 {
+        let node = self.node(handle);
+        let local = node.local.create_model_mat();
+
+        match node.parent {
+            Some(parent) => self.compute_world_matrix(parent) * local,
+            None => local,
+        }
+    }
+
+    // fails if the world matrix contains skew, e.g. from a non-uniformly scaled, rotated parent
+    pub fn world_transform(&self, handle: TransformHandle) -> HellResult<Transform> {
+        Transform::from_matrix(&self.compute_world_matrix(handle))
+    }
+
+    pub fn is_dirty(&self, handle: TransformHandle) -> bool {
+        self.node(handle).dirty
+    }
+
+    pub fn parent(&self, handle: TransformHandle) -> Option<TransformHandle> {
+        self.node(handle).parent
+    }
+
+    pub fn children(&self, handle: TransformHandle) -> &[TransformHandle] {
+        &self.node(handle).children
+    }
+
+    pub fn roots(&self) -> impl Iterator<Item = TransformHandle> + '_ {
+        self.nodes.iter().enumerate()
+            .filter(|(_, n)| n.alive && n.parent.is_none())
+            .map(|(idx, n)| TransformHandle::new(idx, n.generation))
+    }
+
+    pub fn is_ancestor_of(&self, ancestor: TransformHandle, handle: TransformHandle) -> bool {
+        let mut curr = self.node(handle).parent;
+        while let Some(parent) = curr {
+            if parent == ancestor { return true; }
+            curr = self.node(parent).parent;
+        }
+
+        false
+    }
+}
+
+// reparenting
+// -----------
+impl TransformHierarchy {
+    // keeps the local transform, so the node moves along with its new parent
+    pub fn set_parent(&mut self, handle: TransformHandle, parent: Option<TransformHandle>) -> HellResult<()> {
+        self.check_handle(handle)?;
+
+        if let Some(parent) = parent {
+            self.check_handle(parent)?;
+
+            if parent == handle || self.is_ancestor_of(handle, parent) {
+                return Err(HellError::from_msg(
+                    HellErrorKind::GenericError,
+                    format!("can't parent transform '{:?}' to '{:?}': this would create a cycle", handle, parent)
+                ));
+            }
+        }
+
+        self.detach(handle);
+
+        if let Some(parent) = parent {
+            self.node_mut(parent).children.push(handle);
+        }
+
+        let node = self.node_mut(handle);
+        node.parent = parent;
+        node.dirty = true;
+
+        Ok(())
+    }
+
+    // recomputes the local transform, so the node stays where it is in world space
+    pub fn set_parent_keep_world(&mut self, handle: TransformHandle, parent: Option<TransformHandle>) -> HellResult<()> {
+        self.check_handle(handle)?;
+        if let Some(parent) = parent { self.check_handle(parent)?; }
+
+        let world = self.compute_world_matrix(handle);
+        let local = match parent {
+            Some(parent) => self.compute_world_matrix(parent).inverse() * world,
+            None => world,
+        };
+        let local = Transform::from_matrix(&local)?;
+
+        self.set_parent(handle, parent)?;
+        self.set_local(handle, local);
+
+        Ok(())
+    }
+
+    // removes the node from the child list of its parent
+    fn detach(&mut self, handle: TransformHandle) {
+        if let Some(parent) = self.node(handle).parent {
+            self.node_mut(parent).children.retain(|c| *c != handle);
+            self.node_mut(handle).parent = None;
+        }
+    }
+}
+
+// update
+// ------
+impl TransformHierarchy {
+    // recomputes the world matrices of all dirty nodes and their descendants
+    pub fn update_world_matrices(&mut self) {
+        let mut stack: Vec<(TransformHandle, bool)> = self.roots().map(|r| (r, false)).collect();
+
+        while let Some((handle, parent_changed)) = stack.pop() {
+            let node = self.node(handle);
+            let changed = parent_changed || node.dirty;
+
+            if changed {
+                let local = node.local.create_model_mat();
+                let world = match node.parent {
+                    Some(parent) => self.node(parent).world * local,
+                    None => local,
+                };
+
+                let node = self.node_mut(handle);
+                node.world = world;
+                node.dirty = false;
+            }
+
+            stack.extend(self.node(handle).children.iter().map(|c| (*c, changed)));
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use glam::{Quat, Vec3};
+
+    use super::*;
+
+    fn at(x: f32, y: f32, z: f32) -> Transform {
+        Transform::new(Vec3::new(x, y, z), Quat::IDENTITY, Vec3::ONE)
+    }
+
+    #[test]
+    fn stale_handles_are_rejected() {
+        let mut hierarchy = TransformHierarchy::new();
+        let a = hierarchy.add(at(1.0, 0.0, 0.0));
+        hierarchy.remove(a).unwrap();
+
+        // reuses the slot of 'a'
+        let b = hierarchy.add(at(2.0, 0.0, 0.0));
+        assert_eq!(a.idx, b.idx);
+        assert_ne!(a, b);
+
+        assert!(!hierarchy.contains(a));
+        assert!(hierarchy.contains(b));
+        assert!(hierarchy.remove(a).is_err());
+        assert!(hierarchy.add_child(a, Transform::IDENTITY).is_err());
+        assert!(hierarchy.set_parent(b, Some(a)).is_err());
+        assert_eq!(hierarchy.local(b).translation, Vec3::new(2.0, 0.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "has been removed")]
+    fn accessing_a_stale_handle_panics() {
+        let mut hierarchy = TransformHierarchy::new();
+        let a = hierarchy.add(Transform::IDENTITY);
+        hierarchy.remove(a).unwrap();
+        hierarchy.add(Transform::IDENTITY);
+
+        hierarchy.local_mut(a);
+    }
+
+    #[test]
+    fn clear_invalidates_handles() {
+        let mut hierarchy = TransformHierarchy::new();
+        let a = hierarchy.add(Transform::IDENTITY);
+        hierarchy.clear();
+        assert!(hierarchy.is_empty());
+
+        let b = hierarchy.add(Transform::IDENTITY);
+        assert!(!hierarchy.contains(a));
+        assert!(hierarchy.contains(b));
+        assert_eq!(hierarchy.len(), 1);
+    }
+
+    #[test]
+    fn remove_takes_descendants_along() {
+        let mut hierarchy = TransformHierarchy::new();
+        let root = hierarchy.add(Transform::IDENTITY);
+        let child = hierarchy.add_child(root, Transform::IDENTITY).unwrap();
+        let grand_child = hierarchy.add_child(child, Transform::IDENTITY).unwrap();
+        let other = hierarchy.add(Transform::IDENTITY);
+
+        hierarchy.remove(child).unwrap();
+        assert!(!hierarchy.contains(child));
+        assert!(!hierarchy.contains(grand_child));
+        assert!(hierarchy.children(root).is_empty());
+        assert_eq!(hierarchy.len(), 2);
+        assert_eq!(hierarchy.roots().collect::<Vec<_>>(), vec![root, other]);
+    }
+
+    #[test]
+    fn world_matrices() {
+        let mut hierarchy = TransformHierarchy::new();
+        let root = hierarchy.add(at(1.0, 0.0, 0.0));
+        let child = hierarchy.add_child(root, at(0.0, 2.0, 0.0)).unwrap();
+
+        hierarchy.update_world_matrices();
+        assert_eq!(hierarchy.world_matrix(child).transform_point3(Vec3::ZERO), Vec3::new(1.0, 2.0, 0.0));
+        assert!(!hierarchy.is_dirty(child));
+
+        hierarchy.local_mut(root).translation.z = 3.0;
+        assert!(hierarchy.is_dirty(root));
+        assert_eq!(hierarchy.compute_world_matrix(child).transform_point3(Vec3::ZERO), Vec3::new(1.0, 2.0, 3.0));
+
+        hierarchy.update_world_matrices();
+        assert_eq!(hierarchy.world_matrix(child).transform_point3(Vec3::ZERO), Vec3::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn reparenting() {
+        let mut hierarchy = TransformHierarchy::new();
+        let a = hierarchy.add(at(1.0, 0.0, 0.0));
+        let b = hierarchy.add_child(a, at(0.0, 1.0, 0.0)).unwrap();
+        let c = hierarchy.add(at(0.0, 0.0, 5.0));
+
+        assert!(hierarchy.set_parent(a, Some(b)).is_err());
+        assert!(hierarchy.set_parent(a, Some(a)).is_err());
+
+        hierarchy.set_parent_keep_world(b, Some(c)).unwrap();
+        assert_eq!(hierarchy.parent(b), Some(c));
+        assert!(hierarchy.children(a).is_empty());
+        assert!((hierarchy.world_transform(b).unwrap().translation - Vec3::new(1.0, 1.0, 0.0)).length() < 1e-5);
+
+        hierarchy.set_parent(b, None).unwrap();
+        assert_eq!(hierarchy.world_transform(b).unwrap().translation, Vec3::new(1.0, 1.0, -5.0));
+    }
+}
@@ -1,41 +1,143 @@
 use std::os::raw;
 
 use hell_core::error::HellResult;
+use hell_math::{UVec2, IVec2, geometry::{Rect, IRect}};
 
 
-#[derive(Clone, Copy)]
-pub struct HellSurfaceInfo {
-    display: *mut raw::c_void,
-    window: raw::c_ulong,
+// native handles the renderer needs to create a surface
+// 'Headless' renders without a window, e.g. for offscreen ci runs
+#[derive(Debug, Clone, Copy)]
+pub enum HellSurfaceInfo {
+    Xlib {
+        display: *mut raw::c_void,
+        window: raw::c_ulong,
+    },
+    Xcb {
+        connection: *mut raw::c_void,
+        window: u32,
+    },
+    Wayland {
+        display: *mut raw::c_void,
+        surface: *mut raw::c_void,
+    },
+    Headless,
 }
 
 impl HellSurfaceInfo {
-    pub fn new(display: *mut raw::c_void, window: raw::c_ulong) -> Self {
-        Self { display, window }
+    pub const fn new_xlib(display: *mut raw::c_void, window: raw::c_ulong) -> Self {
+        Self::Xlib { display, window }
+    }
+
+    pub const fn new_xcb(connection: *mut raw::c_void, window: u32) -> Self {
+        Self::Xcb { connection, window }
+    }
+
+    pub const fn new_wayland(display: *mut raw::c_void, surface: *mut raw::c_void) -> Self {
+        Self::Wayland { display, surface }
     }
 
-    pub const fn get_display(&self) -> *mut raw::c_void {
-        self.display
+    pub const fn new_headless() -> Self {
+        Self::Headless
     }
 
-    pub const fn get_window(&self) -> raw::c_ulong {
-        self.window
+    pub const fn is_headless(&self) -> bool {
+        matches!(self, Self::Headless)
     }
 }
 
 
 
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct HellWindowExtent {
     pub width: u32,
     pub height: u32,
 }
 
+impl HellWindowExtent {
+    pub const fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    pub fn aspect_ratio(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    // rect that covers the whole window, starting at the origin
+    pub fn to_rect(&self) -> Rect {
+        IRect::from(*self).into()
+    }
+}
+
+impl From<HellWindowExtent> for UVec2 {
+    fn from(val: HellWindowExtent) -> Self {
+        UVec2::new(val.width, val.height)
+    }
+}
+
+impl From<UVec2> for HellWindowExtent {
+    fn from(val: UVec2) -> Self {
+        Self::new(val.x, val.y)
+    }
+}
+
+impl From<HellWindowExtent> for IRect {
+    fn from(val: HellWindowExtent) -> Self {
+        IRect::new(IVec2::zero(), UVec2::from(val).as_ivec2())
+    }
+}
+
+// negative sizes are clamped to 0
+impl From<IRect> for HellWindowExtent {
+    fn from(val: IRect) -> Self {
+        let size = val.size().max(IVec2::zero());
+        Self::new(size.x as u32, size.y as u32)
+    }
+}
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum HellWindowMode {
+    #[default]
+    Windowed,
+    // window without decorations
+    Borderless,
+    // borderless window that covers the current monitor
+    Fullscreen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum HellCursorMode {
+    #[default]
+    Visible,
+    Hidden,
+    // hidden and kept inside the window, e.g. for mouse-look
+    Grabbed,
+}
+
 
 
 
 pub trait HellWindow {
     fn create_surface_info(&self) -> HellResult<HellSurfaceInfo>;
     fn get_window_extent(&self) -> HellWindowExtent;
+
+    fn title(&self) -> &str;
+    fn set_title(&mut self, title: &str);
+
+    fn window_mode(&self) -> HellWindowMode;
+    fn set_window_mode(&mut self, mode: HellWindowMode);
+
+    fn cursor_mode(&self) -> HellCursorMode;
+    // fails if the platform can't grab the cursor, the previous mode is kept in that case
+    fn set_cursor_mode(&mut self, mode: HellCursorMode) -> HellResult<()>;
+
+    // physical pixels per logical pixel
+    fn scale_factor(&self) -> f64;
+
+    // the window is closed at the end of the current frame
+    fn request_close(&mut self);
+    fn is_close_requested(&self) -> bool;
 }
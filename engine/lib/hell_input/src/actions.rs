@@ -0,0 +1,493 @@
+use std::collections::BTreeMap;
+use std::{fs, path::Path};
+
+use hell_core::error::HellResult;
+
+use crate::input::{InputManager, KeyState, ModifiersState};
+use crate::keycodes::KeyCode;
+use crate::mouse::MouseButton;
+use crate::gamepad::{GamepadButton, GamepadAxis};
+
+
+
+// ----------------------------------------------------------------------------
+// bindings
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ActionBinding {
+    Key {
+        key: KeyCode,
+        // binds the key by its position on a us keyboard instead of the character of the active layout
+        #[serde(default, skip_serializing_if = "is_false")]
+        physical: bool,
+        // all of them have to be active while the key is down, generic modifiers like 'shift' accept either side
+        #[serde(default, skip_serializing_if = "ModifiersState::is_empty")]
+        modifiers: ModifiersState,
+    },
+    MouseButton {
+        button: MouseButton,
+    },
+    GamepadButton {
+        button: GamepadButton,
+    },
+    // the axis acts as a button once it passes the threshold, negative thresholds trigger in the negative direction
+    GamepadAxis {
+        axis: GamepadAxis,
+        threshold: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseAxis {
+    MotionX,
+    MotionY,
+    ScrollX,
+    ScrollY,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AxisBinding {
+    Keys {
+        negative: KeyCode,
+        positive: KeyCode,
+        #[serde(default, skip_serializing_if = "is_false")]
+        physical: bool,
+    },
+    GamepadAxis {
+        axis: GamepadAxis,
+        #[serde(default)]
+        invert: bool,
+    },
+    GamepadButtons {
+        negative: GamepadButton,
+        positive: GamepadButton,
+    },
+    // mouse values are not limited to [-1, 1]
+    Mouse {
+        axis: MouseAxis,
+        #[serde(default = "AxisBinding::default_scale")]
+        scale: f32,
+    },
+}
+
+impl AxisBinding {
+    fn default_scale() -> f32 {
+        1.0
+    }
+}
+
+impl ActionBinding {
+    pub fn state(&self, input: &InputManager) -> KeyState {
+        match self {
+            ActionBinding::Key { key, physical, modifiers } => {
+                let state = key_binding_state(input, *key, *physical);
+                if state.is_down() && !input.modifiers_state().satisfies(*modifiers) {
+                    if lost_modifiers(input, state, *modifiers) { KeyState::Released } else { KeyState::Inactive }
+                } else {
+                    state
+                }
+            }
+            ActionBinding::MouseButton { button } => input.mouse_button_state(*button),
+            ActionBinding::GamepadButton { button } => {
+                input.gamepads().iter()
+                    .map(|g| g.button_state(*button))
+                    .fold(KeyState::NeverUsed, combine_states)
+            }
+            ActionBinding::GamepadAxis { axis, threshold } => {
+                let dead_zones = input.gamepad_dead_zones();
+                let passes = |value: f32| {
+                    if *threshold < 0.0 { value <= *threshold } else { value >= *threshold }
+                };
+
+                input.gamepads().iter()
+                    .map(|g| match (passes(g.prev_axis(*axis, dead_zones)), passes(g.axis(*axis, dead_zones))) {
+                        (false, true)  => KeyState::Pressed,
+                        (true,  true)  => KeyState::Held,
+                        (true,  false) => KeyState::Released,
+                        (false, false) => KeyState::Inactive,
+                    })
+                    .fold(KeyState::NeverUsed, combine_states)
+            }
+        }
+    }
+}
+
+impl ActionBinding {
+    // uses the events of the current frame, so a press and release within the same frame reports both
+    // key bindings only count as pressed if their modifiers are active at the end of the frame
+    pub fn pressed(&self, input: &InputManager) -> bool {
+        match self {
+            ActionBinding::Key { key, physical, modifiers } => {
+                input.modifiers_state().satisfies(*modifiers)
+                    && input.events().iter().any(|e| if *physical { e.is_physical_key_press(*key) } else { e.is_key_press(*key) })
+            }
+            ActionBinding::MouseButton { button } => input.events().iter().any(|e| e.is_mouse_button_press(*button)),
+            ActionBinding::GamepadButton { button } => input.events().iter().any(|e| e.is_gamepad_button_press(*button)),
+            // axes are sampled once per frame
+            ActionBinding::GamepadAxis { .. } => self.state(input) == KeyState::Pressed,
+        }
+    }
+
+    // losing the modifiers of a held key binding counts as a release
+    pub fn released(&self, input: &InputManager) -> bool {
+        match self {
+            ActionBinding::Key { key, physical, modifiers } => {
+                input.events().iter().any(|e| if *physical { e.is_physical_key_release(*key) } else { e.is_key_release(*key) })
+                    || lost_modifiers(input, key_binding_state(input, *key, *physical), *modifiers)
+            }
+            ActionBinding::MouseButton { button } => input.events().iter().any(|e| e.is_mouse_button_release(*button)),
+            ActionBinding::GamepadButton { button } => input.events().iter().any(|e| e.is_gamepad_button_release(*button)),
+            ActionBinding::GamepadAxis { .. } => self.state(input) == KeyState::Released,
+        }
+    }
+}
+
+impl AxisBinding {
+    pub fn value(&self, input: &InputManager) -> f32 {
+        match self {
+            AxisBinding::Keys { negative, positive, physical: false } => {
+                key_axis(input.key_state(*negative), input.key_state(*positive))
+            }
+            AxisBinding::Keys { negative, positive, physical: true } => {
+                key_axis(input.physical_key_state(*negative), input.physical_key_state(*positive))
+            }
+            AxisBinding::GamepadAxis { axis, invert } => {
+                let value = input.gamepads().iter()
+                    .map(|g| g.axis(*axis, input.gamepad_dead_zones()))
+                    .fold(0.0, largest_magnitude);
+                if *invert { -value } else { value }
+            }
+            AxisBinding::GamepadButtons { negative, positive } => {
+                input.gamepads().iter()
+                    .map(|g| key_axis(g.button_state(*negative), g.button_state(*positive)))
+                    .fold(0.0, largest_magnitude)
+            }
+            AxisBinding::Mouse { axis, scale } => {
+                let value = match axis {
+                    MouseAxis::MotionX => input.mouse_motion_delta().x,
+                    MouseAxis::MotionY => input.mouse_motion_delta().y,
+                    MouseAxis::ScrollX => input.mouse_scroll_delta().x,
+                    MouseAxis::ScrollY => input.mouse_scroll_delta().y,
+                };
+                value * scale
+            }
+        }
+    }
+}
+
+fn key_binding_state(input: &InputManager, key: KeyCode, physical: bool) -> KeyState {
+    if physical { input.physical_key_state(key) } else { input.key_state(key) }
+}
+
+// the key was held with all modifiers at the start of the frame, but at least one of them is gone now
+fn lost_modifiers(input: &InputManager, key_state: KeyState, modifiers: ModifiersState) -> bool {
+    key_state == KeyState::Held
+        && input.prev_modifiers_state().satisfies(modifiers)
+        && !input.modifiers_state().satisfies(modifiers)
+}
+
+fn is_false(val: &bool) -> bool {
+    !*val
+}
+
+fn key_axis(negative: KeyState, positive: KeyState) -> f32 {
+    (positive.is_down() as i32 - negative.is_down() as i32) as f32
+}
+
+fn largest_magnitude(lhs: f32, rhs: f32) -> f32 {
+    if rhs.abs() > lhs.abs() { rhs } else { lhs }
+}
+
+// merges the states of multiple bindings of the same action: an action stays down as long as one of its bindings is down
+fn combine_states(lhs: KeyState, rhs: KeyState) -> KeyState {
+    fn rank(state: KeyState) -> u8 {
+        match state {
+            KeyState::NeverUsed => 0,
+            KeyState::Inactive  => 1,
+            KeyState::Released  => 2,
+            KeyState::Pressed   => 3,
+            KeyState::Held      => 4,
+        }
+    }
+
+    if rank(rhs) > rank(lhs) { rhs } else { lhs }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// input map
+// ----------------------------------------------------------------------------
+
+// input:
+//   actions:
+//     jump:
+//       - type: key
+//         key: Space
+//       - type: gamepad_button
+//         button: South
+//     save:
+//       - type: key
+//         key: S
+//         modifiers: [ctrl]
+//   axes:
+//     move_x:
+//       - type: keys
+//         negative: A
+//         positive: D
+//         physical: true
+//       - type: gamepad_axis
+//         axis: LeftStickX
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InputMapFile {
+    pub input: InputMap,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InputMap {
+    #[serde(default)]
+    pub actions: BTreeMap<String, Vec<ActionBinding>>,
+    #[serde(default)]
+    pub axes: BTreeMap<String, Vec<AxisBinding>>,
+}
+
+impl InputMapFile {
+    pub fn from_yaml(raw: &str) -> HellResult<Self> {
+        let file: InputMapFile = serde_yaml::from_str(raw)?;
+        Ok(file)
+    }
+
+    pub fn load_file(path: impl AsRef<Path>) -> HellResult<Self> {
+        let raw = fs::read_to_string(path)?;
+        Self::from_yaml(&raw)
+    }
+
+    pub fn to_yaml(&self) -> HellResult<String> {
+        let raw = serde_yaml::to_string(self)?;
+        Ok(raw)
+    }
+
+    pub fn save_file(&self, path: impl AsRef<Path>) -> HellResult<()> {
+        fs::write(path, self.to_yaml()?)?;
+        Ok(())
+    }
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bind_action(&mut self, name: impl Into<String>, binding: ActionBinding) {
+        self.actions.entry(name.into()).or_default().push(binding);
+    }
+
+    pub fn bind_axis(&mut self, name: impl Into<String>, binding: AxisBinding) {
+        self.axes.entry(name.into()).or_default().push(binding);
+    }
+
+    pub fn clear_action(&mut self, name: &str) {
+        self.actions.remove(name);
+    }
+
+    pub fn clear_axis(&mut self, name: &str) {
+        self.axes.remove(name);
+    }
+
+    // unknown actions report 'NeverUsed'
+    pub fn action_state(&self, name: &str, input: &InputManager) -> KeyState {
+        self.actions.get(name)
+            .map(|bindings| bindings.iter().map(|b| b.state(input)).fold(KeyState::NeverUsed, combine_states))
+            .unwrap_or(KeyState::NeverUsed)
+    }
+
+    // pressing a second binding while another one is still held from a previous frame doesn't count
+    pub fn action_pressed(&self, name: &str, input: &InputManager) -> bool {
+        self.actions.get(name)
+            .map(|bindings| {
+                bindings.iter().any(|b| b.pressed(input))
+                    && !bindings.iter().any(|b| b.state(input) == KeyState::Held)
+            })
+            .unwrap_or(false)
+    }
+
+    // releasing one binding while another one is still down doesn't count
+    pub fn action_released(&self, name: &str, input: &InputManager) -> bool {
+        self.actions.get(name)
+            .map(|bindings| {
+                bindings.iter().any(|b| b.released(input))
+                    && !bindings.iter().any(|b| b.state(input).is_down())
+            })
+            .unwrap_or(false)
+    }
+
+    // the binding with the largest magnitude wins, unknown axes report 0
+    pub fn axis_value(&self, name: &str, input: &InputManager) -> f32 {
+        self.axes.get(name)
+            .map(|bindings| bindings.iter().map(|b| b.value(input)).fold(0.0, largest_magnitude))
+            .unwrap_or(0.0)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamepad::{GamepadEvent, GamepadId};
+
+    const PAD: GamepadId = GamepadId::new(0);
+
+    fn input_with_map() -> InputManager {
+        let yaml = r#"
+input:
+  actions:
+    jump:
+      - type: key
+        key: Space
+      - type: gamepad_button
+        button: South
+    save:
+      - type: key
+        key: S
+        modifiers: [ctrl]
+    fire:
+      - type: gamepad_axis
+        axis: RightTrigger
+        threshold: 0.5
+  axes:
+    move_x:
+      - type: keys
+        negative: A
+        positive: D
+"#;
+
+        let mut input = InputManager::new();
+        input.set_input_map(InputMapFile::from_yaml(yaml).unwrap().input);
+        input.handle_gamepad_event(GamepadEvent::Connected { id: PAD, name: "mock".to_string() });
+        input.advance_frame();
+        input
+    }
+
+    #[test]
+    fn yaml_round_trip() {
+        let input = input_with_map();
+        let file = InputMapFile { input: input.input_map().clone() };
+        assert_eq!(InputMapFile::from_yaml(&file.to_yaml().unwrap()).unwrap(), file);
+    }
+
+    #[test]
+    fn press_hold_release() {
+        let mut input = input_with_map();
+
+        input.update_key_state(KeyCode::Space, KeyState::Pressed).unwrap();
+        assert!(input.action_pressed("jump"));
+        assert!(input.action_held("jump"));
+        assert!(!input.action_released("jump"));
+
+        input.advance_frame();
+        assert!(!input.action_pressed("jump"));
+        assert!(input.action_held("jump"));
+
+        input.update_key_state(KeyCode::Space, KeyState::Released).unwrap();
+        assert!(input.action_released("jump"));
+        assert!(!input.action_held("jump"));
+
+        input.advance_frame();
+        assert!(!input.action_released("jump"));
+        assert_eq!(input.action_state("jump"), KeyState::Inactive);
+    }
+
+    #[test]
+    fn press_and_release_within_one_frame() {
+        let mut input = input_with_map();
+        input.update_key_state(KeyCode::Space, KeyState::Pressed).unwrap();
+        input.update_key_state(KeyCode::Space, KeyState::Released).unwrap();
+
+        assert!(input.action_pressed("jump"));
+        assert!(input.action_released("jump"));
+        assert!(!input.action_held("jump"));
+    }
+
+    #[test]
+    fn multiple_bindings() {
+        let mut input = input_with_map();
+
+        input.update_key_state(KeyCode::Space, KeyState::Pressed).unwrap();
+        input.advance_frame();
+
+        // the action is already down, the second binding neither presses it again ...
+        input.handle_gamepad_event(GamepadEvent::Button { id: PAD, button: GamepadButton::South, pressed: true });
+        assert!(!input.action_pressed("jump"));
+        input.advance_frame();
+
+        // ... nor does releasing the first one release it
+        input.update_key_state(KeyCode::Space, KeyState::Released).unwrap();
+        assert!(!input.action_released("jump"));
+        assert!(input.action_held("jump"));
+        input.advance_frame();
+
+        input.handle_gamepad_event(GamepadEvent::Button { id: PAD, button: GamepadButton::South, pressed: false });
+        assert!(input.action_released("jump"));
+    }
+
+    #[test]
+    fn modifiers() {
+        let mut input = input_with_map();
+
+        input.update_key_state(KeyCode::S, KeyState::Pressed).unwrap();
+        assert!(!input.action_pressed("save"));
+        assert!(!input.action_held("save"));
+        input.update_key_state(KeyCode::S, KeyState::Released).unwrap();
+        input.advance_frame();
+
+        input.update_modifiers_state(ModifiersState::CTRL | ModifiersState::LCTRL);
+        input.update_key_state(KeyCode::S, KeyState::Pressed).unwrap();
+        assert!(input.action_pressed("save"));
+        input.advance_frame();
+        assert!(input.action_held("save"));
+
+        // letting go of ctrl while s is still held releases the action once
+        input.update_modifiers_state(ModifiersState::empty());
+        assert!(input.action_released("save"));
+        assert!(!input.action_held("save"));
+        input.advance_frame();
+        assert!(!input.action_released("save"));
+        assert_eq!(input.key_state(KeyCode::S), KeyState::Held);
+
+        // pressing ctrl again doesn't press the action without a new key press
+        input.update_modifiers_state(ModifiersState::CTRL | ModifiersState::LCTRL);
+        assert!(!input.action_pressed("save"));
+    }
+
+    #[test]
+    fn gamepad_axis_threshold() {
+        let mut input = input_with_map();
+
+        input.handle_gamepad_event(GamepadEvent::Axis { id: PAD, axis: GamepadAxis::RightTrigger, value: 1.0 });
+        assert!(input.action_pressed("fire"));
+        input.advance_frame();
+        assert!(input.action_held("fire"));
+        assert!(!input.action_pressed("fire"));
+
+        input.handle_gamepad_event(GamepadEvent::Axis { id: PAD, axis: GamepadAxis::RightTrigger, value: 0.0 });
+        assert!(input.action_released("fire"));
+    }
+
+    #[test]
+    fn key_axis() {
+        let mut input = input_with_map();
+        assert_eq!(input.axis_value("move_x"), 0.0);
+
+        input.update_key_state(KeyCode::D, KeyState::Pressed).unwrap();
+        assert_eq!(input.axis_value("move_x"), 1.0);
+        input.update_key_state(KeyCode::A, KeyState::Pressed).unwrap();
+        assert_eq!(input.axis_value("move_x"), 0.0);
+        assert_eq!(input.axis_value("unknown"), 0.0);
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Instant;
+
+use crate::input::ModifiersState;
+use crate::keycodes::KeyCode;
+use crate::mouse::MouseButton;
+use crate::gamepad::{GamepadId, GamepadButton};
+
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InputEventKind {
+    // 'repeat' is set for presses of a key that is already down, e.g. generated by the os while holding it
+    Key { key: KeyCode, pressed: bool, repeat: bool },
+    // the key at this position on a us keyboard, reported in addition to the logical 'Key' event
+    PhysicalKey { key: KeyCode, pressed: bool, repeat: bool },
+    MouseButton { button: MouseButton, pressed: bool },
+    GamepadButton { id: GamepadId, button: GamepadButton, pressed: bool },
+    Modifiers(ModifiersState),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InputEvent {
+    // the time at which the event was passed to the 'InputManager', see 'InputManager::set_event_time'
+    pub timestamp: Instant,
+    pub kind: InputEventKind,
+}
+
+impl InputEvent {
+    pub fn new(kind: InputEventKind) -> Self {
+        Self::with_timestamp(kind, Instant::now())
+    }
+
+    pub fn with_timestamp(kind: InputEventKind, timestamp: Instant) -> Self {
+        Self { timestamp, kind }
+    }
+
+    pub fn is_key_press(&self, keycode: KeyCode) -> bool {
+        matches!(self.kind, InputEventKind::Key { key, pressed: true, repeat: false } if key == keycode)
+    }
+
+    pub fn is_key_release(&self, keycode: KeyCode) -> bool {
+        matches!(self.kind, InputEventKind::Key { key, pressed: false, .. } if key == keycode)
+    }
+
+    pub fn is_physical_key_press(&self, keycode: KeyCode) -> bool {
+        matches!(self.kind, InputEventKind::PhysicalKey { key, pressed: true, repeat: false } if key == keycode)
+    }
+
+    pub fn is_physical_key_release(&self, keycode: KeyCode) -> bool {
+        matches!(self.kind, InputEventKind::PhysicalKey { key, pressed: false, .. } if key == keycode)
+    }
+
+    pub fn is_mouse_button_press(&self, mouse_button: MouseButton) -> bool {
+        matches!(self.kind, InputEventKind::MouseButton { button, pressed: true } if button == mouse_button)
+    }
+
+    pub fn is_mouse_button_release(&self, mouse_button: MouseButton) -> bool {
+        matches!(self.kind, InputEventKind::MouseButton { button, pressed: false } if button == mouse_button)
+    }
+
+    // presses and releases of any gamepad
+    pub fn is_gamepad_button_press(&self, gamepad_button: GamepadButton) -> bool {
+        matches!(self.kind, InputEventKind::GamepadButton { button, pressed: true, .. } if button == gamepad_button)
+    }
+
+    pub fn is_gamepad_button_release(&self, gamepad_button: GamepadButton) -> bool {
+        matches!(self.kind, InputEventKind::GamepadButton { button, pressed: false, .. } if button == gamepad_button)
+    }
+}
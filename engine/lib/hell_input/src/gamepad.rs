@@ -0,0 +1,357 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use hell_math::Vec2;
+use strum::EnumCount;
+
+use crate::input::KeyState;
+
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GamepadId {
+    pub idx: usize,
+}
+
+impl GamepadId {
+    pub const fn new(idx: usize) -> Self {
+        Self { idx }
+    }
+}
+
+// face buttons are named by position, 'South' is A on xbox and cross on playstation controllers
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, strum::EnumCount, serde::Serialize, serde::Deserialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftBumper,
+    RightBumper,
+    Select,
+    Start,
+    Guide,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+// sticks are in [-1, 1] with positive y pointing up, triggers are in [0, 1]
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, strum::EnumCount, serde::Serialize, serde::Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GamepadStick {
+    Left,
+    Right,
+}
+
+impl GamepadStick {
+    pub const fn axes(&self) -> (GamepadAxis, GamepadAxis) {
+        match self {
+            GamepadStick::Left  => (GamepadAxis::LeftStickX, GamepadAxis::LeftStickY),
+            GamepadStick::Right => (GamepadAxis::RightStickX, GamepadAxis::RightStickY),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadEvent {
+    Connected { id: GamepadId, name: String },
+    Disconnected { id: GamepadId },
+    Button { id: GamepadId, button: GamepadButton, pressed: bool },
+    Axis { id: GamepadId, axis: GamepadAxis, value: f32 },
+}
+
+
+
+
+// dead zone
+// ---------
+// values below 'inner' are treated as 0, values above 'outer' as 1 - everything in between is rescaled
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeadZone {
+    pub inner: f32,
+    pub outer: f32,
+}
+
+impl DeadZone {
+    pub const NONE: DeadZone = DeadZone::new(0.0, 1.0);
+
+    pub const fn new(inner: f32, outer: f32) -> Self {
+        Self { inner, outer }
+    }
+
+    pub fn apply(&self, value: f32) -> f32 {
+        let range = (self.outer - self.inner).max(f32::EPSILON);
+        let scaled = ((value.abs() - self.inner) / range).clamp(0.0, 1.0);
+        scaled.copysign(value)
+    }
+
+    // radial dead zone: the direction is kept, only the length is rescaled
+    pub fn apply_stick(&self, value: Vec2) -> Vec2 {
+        let mag = value.mag();
+        if mag <= self.inner || mag == 0.0 {
+            return Vec2::zero();
+        }
+
+        value * (self.apply(mag) / mag)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GamepadDeadZones {
+    pub stick: DeadZone,
+    pub trigger: DeadZone,
+}
+
+impl Default for GamepadDeadZones {
+    fn default() -> Self {
+        Self {
+            stick: DeadZone::new(0.15, 0.95),
+            trigger: DeadZone::new(0.05, 1.0),
+        }
+    }
+}
+
+
+
+
+// gamepad state
+// -------------
+#[derive(Debug, Clone)]
+pub struct GamepadState {
+    id: GamepadId,
+    name: String,
+    button_states: [KeyState; GamepadButton::COUNT],
+    // raw values as reported by the backend, dead zones are applied when reading them
+    axes: [f32; GamepadAxis::COUNT],
+    // raw values at the end of the previous frame
+    prev_axes: [f32; GamepadAxis::COUNT],
+}
+
+impl GamepadState {
+    pub fn new(id: GamepadId, name: String) -> Self {
+        Self {
+            id,
+            name,
+            button_states: [KeyState::NeverUsed; GamepadButton::COUNT],
+            axes: [0.0; GamepadAxis::COUNT],
+            prev_axes: [0.0; GamepadAxis::COUNT],
+        }
+    }
+
+    pub fn id(&self) -> GamepadId {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn button_state(&self, button: GamepadButton) -> KeyState {
+        self.button_states[button as usize]
+    }
+
+    pub fn raw_axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes[axis as usize]
+    }
+
+    pub fn prev_raw_axis(&self, axis: GamepadAxis) -> f32 {
+        self.prev_axes[axis as usize]
+    }
+
+    pub fn axis(&self, axis: GamepadAxis, dead_zones: &GamepadDeadZones) -> f32 {
+        Self::axis_from(&self.axes, axis, dead_zones)
+    }
+
+    // value at the end of the previous frame, dead zones are applied
+    pub fn prev_axis(&self, axis: GamepadAxis, dead_zones: &GamepadDeadZones) -> f32 {
+        Self::axis_from(&self.prev_axes, axis, dead_zones)
+    }
+
+    pub fn stick(&self, stick: GamepadStick, dead_zones: &GamepadDeadZones) -> Vec2 {
+        Self::stick_from(&self.axes, stick, dead_zones)
+    }
+
+    fn axis_from(axes: &[f32; GamepadAxis::COUNT], axis: GamepadAxis, dead_zones: &GamepadDeadZones) -> f32 {
+        match axis {
+            GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => dead_zones.trigger.apply(axes[axis as usize]),
+            // single stick axes still use the radial dead zone of the whole stick
+            GamepadAxis::LeftStickX | GamepadAxis::RightStickX => Self::stick_from(axes, Self::stick_of(axis), dead_zones).x,
+            GamepadAxis::LeftStickY | GamepadAxis::RightStickY => Self::stick_from(axes, Self::stick_of(axis), dead_zones).y,
+        }
+    }
+
+    fn stick_from(axes: &[f32; GamepadAxis::COUNT], stick: GamepadStick, dead_zones: &GamepadDeadZones) -> Vec2 {
+        let (x, y) = stick.axes();
+        dead_zones.stick.apply_stick(Vec2::new(axes[x as usize], axes[y as usize]))
+    }
+
+    fn stick_of(axis: GamepadAxis) -> GamepadStick {
+        match axis {
+            GamepadAxis::RightStickX | GamepadAxis::RightStickY => GamepadStick::Right,
+            _ => GamepadStick::Left,
+        }
+    }
+
+    pub fn update_button_state(&mut self, button: GamepadButton, new_state: KeyState) {
+        let state = &mut self.button_states[button as usize];
+        *state = state.transition(new_state);
+    }
+
+    pub fn update_axis(&mut self, axis: GamepadAxis, value: f32) {
+        self.axes[axis as usize] = value;
+    }
+
+    pub fn advance_frame(&mut self) {
+        self.button_states.iter_mut().for_each(|s| *s = s.advance_frame());
+
+        self.prev_axes = self.axes;
+    }
+}
+
+
+
+
+// backends
+// --------
+pub trait GamepadBackend {
+    // all events since the last call, in the order they happened
+    fn poll_events(&mut self) -> Vec<GamepadEvent>;
+}
+
+// hands out scripted events, e.g. to test gameplay code with stick input
+// clones share their event queue: keep a clone around to push events after the backend was moved into the 'InputManager'
+#[derive(Debug, Clone, Default)]
+pub struct MockGamepadBackend {
+    events: Rc<RefCell<VecDeque<GamepadEvent>>>,
+}
+
+impl MockGamepadBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_event(&self, event: GamepadEvent) {
+        self.events.borrow_mut().push_back(event);
+    }
+
+    pub fn connect(&self, id: GamepadId, name: &str) {
+        self.push_event(GamepadEvent::Connected { id, name: name.to_string() });
+    }
+
+    pub fn disconnect(&self, id: GamepadId) {
+        self.push_event(GamepadEvent::Disconnected { id });
+    }
+
+    pub fn set_button(&self, id: GamepadId, button: GamepadButton, pressed: bool) {
+        self.push_event(GamepadEvent::Button { id, button, pressed });
+    }
+
+    pub fn set_axis(&self, id: GamepadId, axis: GamepadAxis, value: f32) {
+        self.push_event(GamepadEvent::Axis { id, axis, value });
+    }
+
+    pub fn set_stick(&self, id: GamepadId, stick: GamepadStick, value: Vec2) {
+        let (x, y) = stick.axes();
+        self.set_axis(id, x, value.x);
+        self.set_axis(id, y, value.y);
+    }
+}
+
+impl GamepadBackend for MockGamepadBackend {
+    fn poll_events(&mut self) -> Vec<GamepadEvent> {
+        self.events.borrow_mut().drain(..).collect()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::InputManager;
+
+    const PAD: GamepadId = GamepadId::new(0);
+
+    fn manager_with_pad() -> (InputManager, MockGamepadBackend) {
+        let backend = MockGamepadBackend::new();
+        let mut input = InputManager::new();
+        input.set_gamepad_backend(Box::new(backend.clone()));
+
+        backend.connect(PAD, "mock");
+        input.poll_gamepads();
+        (input, backend)
+    }
+
+    #[test]
+    fn connect_and_disconnect() {
+        let (mut input, backend) = manager_with_pad();
+        assert_eq!(input.gamepads().len(), 1);
+        assert_eq!(input.gamepad(PAD).unwrap().name(), "mock");
+        assert_eq!(input.gamepad_connection_events().len(), 1);
+
+        input.advance_frame();
+        backend.disconnect(PAD);
+        input.poll_gamepads();
+        assert!(input.gamepads().is_empty());
+        assert_eq!(input.gamepad_button_state(PAD, GamepadButton::South), KeyState::NeverUsed);
+    }
+
+    #[test]
+    fn button_press_hold_release_cycle() {
+        let (mut input, backend) = manager_with_pad();
+
+        backend.set_button(PAD, GamepadButton::South, true);
+        input.poll_gamepads();
+        assert_eq!(input.gamepad_button_state(PAD, GamepadButton::South), KeyState::Pressed);
+
+        input.advance_frame();
+        input.poll_gamepads();
+        assert_eq!(input.gamepad_button_state(PAD, GamepadButton::South), KeyState::Held);
+
+        input.advance_frame();
+        backend.set_button(PAD, GamepadButton::South, false);
+        input.poll_gamepads();
+        assert_eq!(input.gamepad_button_state(PAD, GamepadButton::South), KeyState::Released);
+
+        input.advance_frame();
+        input.poll_gamepads();
+        assert_eq!(input.gamepad_button_state(PAD, GamepadButton::South), KeyState::Inactive);
+    }
+
+    #[test]
+    fn axes_and_dead_zones() {
+        let (mut input, backend) = manager_with_pad();
+        input.set_gamepad_dead_zones(GamepadDeadZones { stick: DeadZone::new(0.2, 1.0), trigger: DeadZone::new(0.1, 1.0) });
+
+        backend.set_stick(PAD, GamepadStick::Left, Vec2::new(0.1, 0.1));
+        backend.set_axis(PAD, GamepadAxis::RightTrigger, 1.0);
+        input.poll_gamepads();
+        assert_eq!(input.gamepad_stick(PAD, GamepadStick::Left), Vec2::zero());
+        assert_eq!(input.gamepad_axis(PAD, GamepadAxis::RightTrigger), 1.0);
+
+        input.advance_frame();
+        backend.set_axis(PAD, GamepadAxis::LeftStickX, 1.0);
+        input.poll_gamepads();
+
+        let pad = input.gamepad(PAD).unwrap();
+        assert_eq!(pad.raw_axis(GamepadAxis::LeftStickX), 1.0);
+        assert_eq!(pad.prev_raw_axis(GamepadAxis::LeftStickX), 0.1);
+        assert!(input.gamepad_axis(PAD, GamepadAxis::LeftStickX) > 0.9);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::gamepad::{GamepadBackend, GamepadEvent, GamepadId, GamepadButton, GamepadAxis};
+
+
+
+// gilrs reports buttons and axes of all connected controllers through a single event queue
+pub struct GilrsGamepadBackend {
+    gilrs: gilrs::Gilrs,
+}
+
+impl GilrsGamepadBackend {
+    pub fn new() -> Option<Self> {
+        let gilrs = gilrs::Gilrs::new().ok()?;
+        Some(Self { gilrs })
+    }
+}
+
+impl GamepadBackend for GilrsGamepadBackend {
+    fn poll_events(&mut self) -> Vec<GamepadEvent> {
+        let mut result = Vec::new();
+
+        while let Some(gilrs::Event { id: gilrs_id, event, .. }) = self.gilrs.next_event() {
+            let id = GamepadId::new(usize::from(gilrs_id));
+
+            let event = match event {
+                gilrs::EventType::Connected => {
+                    let name = self.gilrs.gamepad(gilrs_id).name().to_string();
+                    Some(GamepadEvent::Connected { id, name })
+                }
+                gilrs::EventType::Disconnected => Some(GamepadEvent::Disconnected { id }),
+                gilrs::EventType::ButtonPressed(button, _)  => button_to_hell(button).map(|button| GamepadEvent::Button { id, button, pressed: true }),
+                gilrs::EventType::ButtonReleased(button, _) => button_to_hell(button).map(|button| GamepadEvent::Button { id, button, pressed: false }),
+                // analog triggers are reported as buttons with a value
+                gilrs::EventType::ButtonChanged(button, value, _) => trigger_to_hell(button).map(|axis| GamepadEvent::Axis { id, axis, value }),
+                gilrs::EventType::AxisChanged(axis, value, _) => axis_to_hell(axis).map(|axis| GamepadEvent::Axis { id, axis, value }),
+                _ => None,
+            };
+
+            result.extend(event);
+        }
+
+        result
+    }
+}
+
+fn button_to_hell(button: gilrs::Button) -> Option<GamepadButton> {
+    match button {
+        gilrs::Button::South        => Some(GamepadButton::South),
+        gilrs::Button::East         => Some(GamepadButton::East),
+        gilrs::Button::West         => Some(GamepadButton::West),
+        gilrs::Button::North        => Some(GamepadButton::North),
+        gilrs::Button::LeftTrigger  => Some(GamepadButton::LeftBumper),
+        gilrs::Button::RightTrigger => Some(GamepadButton::RightBumper),
+        gilrs::Button::Select       => Some(GamepadButton::Select),
+        gilrs::Button::Start        => Some(GamepadButton::Start),
+        gilrs::Button::Mode         => Some(GamepadButton::Guide),
+        gilrs::Button::LeftThumb    => Some(GamepadButton::LeftStick),
+        gilrs::Button::RightThumb   => Some(GamepadButton::RightStick),
+        gilrs::Button::DPadUp       => Some(GamepadButton::DPadUp),
+        gilrs::Button::DPadDown     => Some(GamepadButton::DPadDown),
+        gilrs::Button::DPadLeft     => Some(GamepadButton::DPadLeft),
+        gilrs::Button::DPadRight    => Some(GamepadButton::DPadRight),
+        _ => None,
+    }
+}
+
+fn trigger_to_hell(button: gilrs::Button) -> Option<GamepadAxis> {
+    match button {
+        gilrs::Button::LeftTrigger2  => Some(GamepadAxis::LeftTrigger),
+        gilrs::Button::RightTrigger2 => Some(GamepadAxis::RightTrigger),
+        _ => None,
+    }
+}
+
+fn axis_to_hell(axis: gilrs::Axis) -> Option<GamepadAxis> {
+    match axis {
+        gilrs::Axis::LeftStickX  => Some(GamepadAxis::LeftStickX),
+        gilrs::Axis::LeftStickY  => Some(GamepadAxis::LeftStickY),
+        gilrs::Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        gilrs::Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        _ => None,
+    }
+}
@@ -1,8 +1,16 @@
 use core::fmt;
+use std::time::Instant;
 
 use hell_core::error::HellResult;
+use hell_math::Vec2;
 use strum::EnumCount;
 use crate::keycodes::KeyCode;
+use crate::events::{InputEvent, InputEventKind};
+use crate::mouse::{MouseButton, MouseState};
+use crate::text::{TextInputState, TextInputEvent, EditKey};
+use crate::actions::InputMap;
+use crate::recording::{InputRecorder, InputRecording, RecordedInput};
+use crate::gamepad::{GamepadBackend, GamepadEvent, GamepadState, GamepadId, GamepadButton, GamepadAxis, GamepadStick, GamepadDeadZones};
 
 
 
@@ -28,6 +36,22 @@ impl KeyState {
         Self::DOWN_STATES.contains(self)
     }
 
+    // repeated presses of a key that is already down turn into 'Held'
+    pub fn transition(self, new_state: KeyState) -> KeyState {
+        match (self, new_state) {
+            (KeyState::Pressed | KeyState::Held, KeyState::Pressed) => KeyState::Held,
+            (_, s) => s,
+        }
+    }
+
+    // the state at the start of the next frame if no new input arrives
+    pub fn advance_frame(self) -> KeyState {
+        match self {
+            KeyState::Pressed => KeyState::Held,
+            KeyState::Released => KeyState::Inactive,
+            s => s,
+        }
+    }
 }
 
 bitflags::bitflags! {
@@ -51,12 +75,106 @@ bitflags::bitflags! {
     }
 }
 
+impl ModifiersState {
+    const NAMES: &'static [(&'static str, ModifiersState)] = &[
+        ("shift", ModifiersState::SHIFT), ("lshift", ModifiersState::LSHIFT), ("rshift", ModifiersState::RSHIFT),
+        ("ctrl",  ModifiersState::CTRL),  ("lctrl",  ModifiersState::LCTRL),  ("rctrl",  ModifiersState::RCTRL),
+        ("alt",   ModifiersState::ALT),   ("lalt",   ModifiersState::LALT),   ("ralt",   ModifiersState::RALT),
+        ("super", ModifiersState::SUPER), ("lsuper", ModifiersState::LSUPER), ("rsuper", ModifiersState::RSUPER),
+    ];
+
+    // true if either side is active
+    pub fn shift(&self) -> bool {
+        self.satisfies(ModifiersState::SHIFT)
+    }
+
+    pub fn ctrl(&self) -> bool {
+        self.satisfies(ModifiersState::CTRL)
+    }
+
+    pub fn alt(&self) -> bool {
+        self.satisfies(ModifiersState::ALT)
+    }
+
+    pub fn logo(&self) -> bool {
+        self.satisfies(ModifiersState::SUPER)
+    }
+
+    // every modifier in 'required' has to be active, a generic modifier like 'SHIFT' is also satisfied by either side
+    pub fn satisfies(&self, required: ModifiersState) -> bool {
+        let generic_mask = ModifiersState::SHIFT | ModifiersState::CTRL | ModifiersState::ALT | ModifiersState::SUPER;
+
+        // expand the sides of the current state into their generic modifier
+        let mut current = *self;
+        for generic in generic_mask.iter_flags() {
+            let sides = ModifiersState::from_bits_truncate((generic.bits() >> 1) | (generic.bits() >> 2));
+            if self.intersects(sides) { current |= generic; }
+        }
+
+        current.contains(required)
+    }
+
+    fn iter_flags(self) -> impl Iterator<Item = ModifiersState> {
+        Self::NAMES.iter()
+            .map(|(_, flag)| *flag)
+            .filter(move |flag| self.contains(*flag))
+    }
+}
+
+// serialized as a list of names, e.g. [ctrl, lshift]
+impl serde::Serialize for ModifiersState {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(None)?;
+        for (name, flag) in Self::NAMES {
+            if self.contains(*flag) { seq.serialize_element(name)?; }
+        }
+        seq.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ModifiersState {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let names: Vec<String> = Vec::deserialize(deserializer)?;
+
+        names.iter().try_fold(ModifiersState::empty(), |acc, name| {
+            Self::NAMES.iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, flag)| acc | *flag)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown modifier '{}'", name)))
+        })
+    }
+}
+
 
 
 
 pub struct InputManager {
     modifier_states: ModifiersState,
+    // the modifiers at the start of the frame
+    prev_modifier_states: ModifiersState,
     key_states: [KeyState; KeyCode::COUNT],
+    // keys by their position on a us keyboard, independent of the active layout
+    physical_key_states: [KeyState; KeyCode::COUNT],
+    // the logical key last reported for every physical key
+    key_layout: [KeyCode; KeyCode::COUNT],
+    // all events of the current frame in the order in which they were received
+    events: Vec<InputEvent>,
+    // overrides the timestamp of new events, e.g. while replaying a recording
+    event_time: Option<Instant>,
+    mouse: MouseState,
+    text: TextInputState,
+
+    gamepad_backend: Option<Box<dyn GamepadBackend>>,
+    gamepads: Vec<GamepadState>,
+    gamepad_dead_zones: GamepadDeadZones,
+    // connect / disconnect events of the current frame
+    gamepad_connection_events: Vec<GamepadEvent>,
+
+    input_map: InputMap,
+
+    recorder: Option<InputRecorder>,
 }
 
 impl InputManager {
@@ -66,21 +184,38 @@ impl InputManager {
 
         Self {
             modifier_states,
+            prev_modifier_states: modifier_states,
             key_states,
+            physical_key_states: [KeyState::NeverUsed; KeyCode::COUNT],
+            key_layout: [KeyCode::Invalid; KeyCode::COUNT],
+            events: Vec::new(),
+            event_time: None,
+            mouse: MouseState::new(),
+            text: TextInputState::new(),
+
+            gamepad_backend: None,
+            gamepads: Vec::new(),
+            gamepad_dead_zones: GamepadDeadZones::default(),
+            gamepad_connection_events: Vec::new(),
+
+            input_map: InputMap::new(),
+
+            recorder: None,
         }
     }
 
     pub fn update_key_state(&mut self, keycode: KeyCode, new_state: KeyState) -> HellResult<()> {
-        let state = self.key_states.get_mut(keycode as usize).unwrap();
-
-        let new_state = match (*state, new_state) {
-            (KeyState::Pressed | KeyState::Held, KeyState::Pressed) => KeyState::Held,
-            (_, s) => s,
-        };
+        let time = self.event_time();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(RecordedInput::Key { key: keycode, state: new_state }, time);
+        }
 
-        println!("INPUT: udpate key state: {:?} => {:?}", keycode, new_state);
+        let state = self.key_states.get_mut(keycode as usize).unwrap();
+        let pressed = new_state.is_down();
+        let repeat = pressed && state.is_down();
 
-        *state = new_state;
+        *state = state.transition(new_state);
+        self.events.push(InputEvent::with_timestamp(InputEventKind::Key { key: keycode, pressed, repeat }, time));
 
         Ok(())
     }
@@ -89,17 +224,366 @@ impl InputManager {
         self.key_states[keycode as usize]
     }
 
+    // 'keycode' is the key at this position on a us keyboard, e.g. 'W' is 'Z' on azerty
+    pub fn update_physical_key_state(&mut self, keycode: KeyCode, new_state: KeyState) -> HellResult<()> {
+        let time = self.event_time();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(RecordedInput::PhysicalKey { key: keycode, state: new_state }, time);
+        }
+
+        let state = &mut self.physical_key_states[keycode as usize];
+        let pressed = new_state.is_down();
+        let repeat = pressed && state.is_down();
+
+        *state = state.transition(new_state);
+        self.events.push(InputEvent::with_timestamp(InputEventKind::PhysicalKey { key: keycode, pressed, repeat }, time));
+
+        Ok(())
+    }
+
+    pub fn physical_key_state(&self, keycode: KeyCode) -> KeyState {
+        self.physical_key_states[keycode as usize]
+    }
+
+    // remembers which logical key the active layout produces for a physical key
+    pub fn update_key_layout(&mut self, physical: KeyCode, logical: KeyCode) {
+        self.key_layout[physical as usize] = logical;
+    }
+
+    // the logical key of the physical key in the active layout, only known once the key has been pressed
+    pub fn logical_key(&self, physical: KeyCode) -> Option<KeyCode> {
+        match self.key_layout[physical as usize] {
+            KeyCode::Invalid => None,
+            logical => Some(logical),
+        }
+    }
+
+    // falls back to the us name as long as the key hasn't been pressed
+    pub fn physical_key_name(&self, physical: KeyCode) -> &'static str {
+        self.logical_key(physical).unwrap_or(physical).display_name()
+    }
+
     pub fn update_modifiers_state(&mut self, new_state: ModifiersState) {
+        let time = self.event_time();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(RecordedInput::Modifiers(new_state), time);
+        }
+
         self.modifier_states = new_state;
+        self.events.push(InputEvent::with_timestamp(InputEventKind::Modifiers(new_state), time));
+    }
+
+    pub fn modifiers_state(&self) -> ModifiersState {
+        self.modifier_states
+    }
+
+    // the modifiers at the start of the current frame
+    pub fn prev_modifiers_state(&self) -> ModifiersState {
+        self.prev_modifier_states
     }
 
+    // pressed keys become held and released keys become inactive
     pub fn reset_released_keys(&mut self) {
         self.key_states.iter_mut()
-            .filter(|s| **s == KeyState::Released)
-            .for_each(|s| *s = KeyState::Inactive);
+            .chain(self.physical_key_states.iter_mut())
+            .for_each(|s| *s = s.advance_frame());
+    }
+
+    // has to be called once at the end of every frame
+    pub fn advance_frame(&mut self) {
+        self.reset_released_keys();
+        self.prev_modifier_states = self.modifier_states;
+        self.events.clear();
+        self.mouse.advance_frame();
+        self.text.advance_frame();
+        self.gamepads.iter_mut().for_each(|g| g.advance_frame());
+        self.gamepad_connection_events.clear();
     }
 }
 
+// events
+// ------
+impl InputManager {
+    // all events of the current frame, ordered by their timestamp
+    pub fn events(&self) -> &[InputEvent] {
+        &self.events
+    }
+
+    // true even if the key was already released again within the same frame, key repeats are ignored
+    pub fn just_pressed(&self, keycode: KeyCode) -> bool {
+        self.events.iter().any(|e| e.is_key_press(keycode))
+    }
+
+    pub fn just_released(&self, keycode: KeyCode) -> bool {
+        self.events.iter().any(|e| e.is_key_release(keycode))
+    }
+
+    // number of presses in the current frame, key repeats are ignored
+    pub fn press_count(&self, keycode: KeyCode) -> usize {
+        self.events.iter().filter(|e| e.is_key_press(keycode)).count()
+    }
+
+    // all following events are stamped with 'time' instead of the current time until it is reset with 'None'
+    pub fn set_event_time(&mut self, time: Option<Instant>) {
+        self.event_time = time;
+    }
+
+    // the timestamp of the next event
+    pub fn event_time(&self) -> Instant {
+        self.event_time.unwrap_or_else(Instant::now)
+    }
+
+    fn push_event(&mut self, kind: InputEventKind) {
+        self.events.push(InputEvent::with_timestamp(kind, self.event_time()));
+    }
+}
+
+// mouse
+// -----
+impl InputManager {
+    pub fn mouse(&self) -> &MouseState {
+        &self.mouse
+    }
+
+    pub fn update_mouse_button_state(&mut self, button: MouseButton, new_state: KeyState) {
+        self.mouse.update_button_state(button, new_state);
+        self.push_event(InputEventKind::MouseButton { button, pressed: new_state.is_down() });
+    }
+
+    // true even if the button was already released again within the same frame
+    pub fn mouse_button_just_pressed(&self, button: MouseButton) -> bool {
+        self.events.iter().any(|e| e.is_mouse_button_press(button))
+    }
+
+    pub fn mouse_button_just_released(&self, button: MouseButton) -> bool {
+        self.events.iter().any(|e| e.is_mouse_button_release(button))
+    }
+
+    pub fn mouse_button_state(&self, button: MouseButton) -> KeyState {
+        self.mouse.button_state(button)
+    }
+
+    pub fn update_cursor_position(&mut self, position: Vec2, window_size: Vec2) {
+        self.mouse.update_position(position, window_size);
+    }
+
+    pub fn update_cursor_inside_window(&mut self, is_inside: bool) {
+        self.mouse.update_inside_window(is_inside);
+    }
+
+    pub fn add_mouse_motion(&mut self, delta: Vec2) {
+        self.mouse.add_motion(delta);
+    }
+
+    pub fn add_mouse_scroll(&mut self, delta: Vec2) {
+        self.mouse.add_scroll(delta);
+    }
+
+    pub fn cursor_position(&self) -> Vec2 {
+        self.mouse.position()
+    }
+
+    pub fn cursor_position_normalized(&self) -> Vec2 {
+        self.mouse.position_normalized()
+    }
+
+    pub fn mouse_motion_delta(&self) -> Vec2 {
+        self.mouse.motion_delta()
+    }
+
+    pub fn mouse_scroll_delta(&self) -> Vec2 {
+        self.mouse.scroll_delta()
+    }
+}
+
+// text
+// ----
+impl InputManager {
+    pub fn text_input(&self) -> &TextInputState {
+        &self.text
+    }
+
+    // control characters like backspace are ignored, they are reported through 'update_edit_key'
+    pub fn push_text_char(&mut self, c: char) {
+        self.text.push_char(c);
+    }
+
+    pub fn update_edit_key(&mut self, key: EditKey, new_state: KeyState) {
+        self.text.update_edit_key(key, new_state);
+    }
+
+    pub fn update_ime_enabled(&mut self, is_enabled: bool) {
+        self.text.set_ime_enabled(is_enabled);
+    }
+
+    pub fn update_ime_preedit(&mut self, text: String, cursor: Option<(usize, usize)>) {
+        self.text.ime_preedit(text, cursor);
+    }
+
+    pub fn commit_ime_text(&mut self, text: String) {
+        self.text.ime_commit(text);
+    }
+
+    pub fn text_events(&self) -> &[TextInputEvent] {
+        self.text.events()
+    }
+
+    pub fn committed_text(&self) -> String {
+        self.text.committed_text()
+    }
+}
+
+
+// gamepad
+// -------
+impl InputManager {
+    pub fn set_gamepad_backend(&mut self, backend: Box<dyn GamepadBackend>) {
+        self.gamepad_backend = Some(backend);
+    }
+
+    // applies all events of the backend, has to be called once per frame before the game is updated
+    pub fn poll_gamepads(&mut self) {
+        let events = match &mut self.gamepad_backend {
+            Some(backend) => backend.poll_events(),
+            None => return,
+        };
+
+        events.into_iter().for_each(|e| self.handle_gamepad_event(e));
+    }
+
+    pub fn handle_gamepad_event(&mut self, event: GamepadEvent) {
+        match &event {
+            GamepadEvent::Connected { id, name } => {
+                self.gamepads.retain(|g| g.id() != *id);
+                self.gamepads.push(GamepadState::new(*id, name.clone()));
+                self.gamepads.sort_by_key(|g| g.id());
+                self.gamepad_connection_events.push(event);
+            }
+            GamepadEvent::Disconnected { id } => {
+                self.gamepads.retain(|g| g.id() != *id);
+                self.gamepad_connection_events.push(event);
+            }
+            GamepadEvent::Button { id, button, pressed } => {
+                let state = if *pressed { KeyState::Pressed } else { KeyState::Released };
+                if let Some(gamepad) = self.gamepad_mut(*id) {
+                    gamepad.update_button_state(*button, state);
+                } else {
+                    return;
+                }
+
+                self.push_event(InputEventKind::GamepadButton { id: *id, button: *button, pressed: *pressed });
+            }
+            GamepadEvent::Axis { id, axis, value } => {
+                if let Some(gamepad) = self.gamepad_mut(*id) {
+                    gamepad.update_axis(*axis, *value);
+                }
+            }
+        }
+    }
+
+    fn gamepad_mut(&mut self, id: GamepadId) -> Option<&mut GamepadState> {
+        self.gamepads.iter_mut().find(|g| g.id() == id)
+    }
+
+    // sorted by id
+    pub fn gamepads(&self) -> &[GamepadState] {
+        &self.gamepads
+    }
+
+    pub fn gamepad(&self, id: GamepadId) -> Option<&GamepadState> {
+        self.gamepads.iter().find(|g| g.id() == id)
+    }
+
+    pub fn gamepad_connection_events(&self) -> &[GamepadEvent] {
+        &self.gamepad_connection_events
+    }
+
+    pub fn gamepad_dead_zones(&self) -> &GamepadDeadZones {
+        &self.gamepad_dead_zones
+    }
+
+    pub fn set_gamepad_dead_zones(&mut self, dead_zones: GamepadDeadZones) {
+        self.gamepad_dead_zones = dead_zones;
+    }
+
+    // disconnected gamepads report 'NeverUsed'
+    pub fn gamepad_button_state(&self, id: GamepadId, button: GamepadButton) -> KeyState {
+        self.gamepad(id).map(|g| g.button_state(button)).unwrap_or(KeyState::NeverUsed)
+    }
+
+    // dead zones are applied, disconnected gamepads report 0
+    pub fn gamepad_axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        self.gamepad(id).map(|g| g.axis(axis, &self.gamepad_dead_zones)).unwrap_or(0.0)
+    }
+
+    pub fn gamepad_stick(&self, id: GamepadId, stick: GamepadStick) -> Vec2 {
+        self.gamepad(id).map(|g| g.stick(stick, &self.gamepad_dead_zones)).unwrap_or_else(Vec2::zero)
+    }
+}
+
+// actions / axes
+// --------------
+impl InputManager {
+    pub fn set_input_map(&mut self, input_map: InputMap) {
+        self.input_map = input_map;
+    }
+
+    pub fn input_map(&self) -> &InputMap {
+        &self.input_map
+    }
+
+    pub fn input_map_mut(&mut self) -> &mut InputMap {
+        &mut self.input_map
+    }
+
+    pub fn action_state(&self, action: &str) -> KeyState {
+        self.input_map.action_state(action, self)
+    }
+
+    // true in the frame the action went down, even if it was already released again within the same frame
+    pub fn action_pressed(&self, action: &str) -> bool {
+        self.input_map.action_pressed(action, self)
+    }
+
+    // true as long as the action is down, including the frame it was pressed in
+    pub fn action_held(&self, action: &str) -> bool {
+        self.action_state(action).is_down()
+    }
+
+    // true in the frame the action went up, even if it was only pressed within the same frame
+    pub fn action_released(&self, action: &str) -> bool {
+        self.input_map.action_released(action, self)
+    }
+
+    pub fn axis_value(&self, axis: &str) -> f32 {
+        self.input_map.axis_value(axis, self)
+    }
+}
+
+// recording
+// ---------
+impl InputManager {
+    // restarts the recording if one is already running
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(InputRecorder::new(self.event_time()));
+    }
+
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recorder.take().map(|r| r.finish())
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    // has to be called once per frame before the game is updated, stores all inputs received since the last call
+    pub fn record_frame(&mut self, delta_time: f32) {
+        let time = self.event_time();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.end_frame(delta_time, time);
+        }
+    }
+}
 
 impl Default for InputManager {
     fn default() -> Self {
@@ -120,3 +604,77 @@ impl fmt::Display for InputManager {
     }
 }
 
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_press_hold_release_cycle() {
+        let mut input = InputManager::new();
+        assert_eq!(input.key_state(KeyCode::A), KeyState::NeverUsed);
+
+        input.update_key_state(KeyCode::A, KeyState::Pressed).unwrap();
+        input.update_physical_key_state(KeyCode::Q, KeyState::Pressed).unwrap();
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Pressed);
+        assert_eq!(input.physical_key_state(KeyCode::Q), KeyState::Pressed);
+
+        // no new events, the key is still down
+        input.advance_frame();
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Held);
+        assert_eq!(input.physical_key_state(KeyCode::Q), KeyState::Held);
+        assert!(!input.just_pressed(KeyCode::A));
+
+        // os key repeat
+        input.update_key_state(KeyCode::A, KeyState::Pressed).unwrap();
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Held);
+        assert_eq!(input.press_count(KeyCode::A), 0);
+
+        input.advance_frame();
+        input.update_key_state(KeyCode::A, KeyState::Released).unwrap();
+        input.update_physical_key_state(KeyCode::Q, KeyState::Released).unwrap();
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Released);
+        assert_eq!(input.physical_key_state(KeyCode::Q), KeyState::Released);
+        assert!(input.just_released(KeyCode::A));
+
+        input.advance_frame();
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Inactive);
+        assert_eq!(input.physical_key_state(KeyCode::Q), KeyState::Inactive);
+    }
+
+    #[test]
+    fn press_and_release_within_one_frame() {
+        let mut input = InputManager::new();
+        input.update_key_state(KeyCode::A, KeyState::Pressed).unwrap();
+        input.update_key_state(KeyCode::A, KeyState::Released).unwrap();
+        input.update_key_state(KeyCode::A, KeyState::Pressed).unwrap();
+        input.update_key_state(KeyCode::A, KeyState::Released).unwrap();
+
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Released);
+        assert!(input.just_pressed(KeyCode::A));
+        assert!(input.just_released(KeyCode::A));
+        assert_eq!(input.press_count(KeyCode::A), 2);
+
+        input.advance_frame();
+        assert!(!input.just_pressed(KeyCode::A));
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Inactive);
+    }
+
+    #[test]
+    fn mouse_button_cycle() {
+        let mut input = InputManager::new();
+        input.update_mouse_button_state(MouseButton::Left, KeyState::Pressed);
+        assert!(input.mouse_button_just_pressed(MouseButton::Left));
+
+        input.advance_frame();
+        assert_eq!(input.mouse_button_state(MouseButton::Left), KeyState::Held);
+        assert!(!input.mouse_button_just_pressed(MouseButton::Left));
+
+        input.update_mouse_button_state(MouseButton::Left, KeyState::Released);
+        assert!(input.mouse_button_just_released(MouseButton::Left));
+        input.advance_frame();
+        assert_eq!(input.mouse_button_state(MouseButton::Left), KeyState::Inactive);
+    }
+}
@@ -1,8 +1,10 @@
+use core::fmt;
+
 use hell_core::error::{HellError, HellErrorKind, HellErrorContent};
 use num_traits::FromPrimitive;
 
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, strum::EnumCount, num_derive::FromPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, strum::EnumCount, num_derive::FromPrimitive, serde::Serialize, serde::Deserialize)]
 pub enum KeyCode {
     Invalid,
     A,
@@ -97,6 +99,84 @@ pub enum KeyCode {
     Escape,
     Return,
     Backspace,
+
+    // new keys are appended, so the values of existing keys (e.g. in input recordings) stay the same
+    Tab,
+    CapsLock,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    NumLock,
+    ContextMenu,
+
+    Grave,
+    Minus,
+    Equals,
+    BracketLeft,
+    BracketRight,
+    Backslash,
+    Semicolon,
+    Apostrophe,
+    Comma,
+    Period,
+    Slash,
+    IntlBackslash,
+    Plus,
+    Asterisk,
+    At,
+    Colon,
+    Underline,
+    Caret,
+
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadComma,
+    NumpadEnter,
+    NumpadEquals,
+
+    MediaPlayPause,
+    MediaStop,
+    MediaNextTrack,
+    MediaPrevTrack,
+    MediaSelect,
+    VolumeUp,
+    VolumeDown,
+    VolumeMute,
+
+    BrowserBack,
+    BrowserForward,
+    BrowserRefresh,
+    BrowserStop,
+    BrowserSearch,
+    BrowserFavorites,
+    BrowserHome,
+    LaunchMail,
+    LaunchCalculator,
+    LaunchMyComputer,
+
+    Power,
+    Sleep,
+    Wake,
+    Copy,
+    Paste,
+    Cut,
+    Compose,
+    Convert,
+    NonConvert,
+    KanaMode,
+    KanjiMode,
+    Yen,
+    AbntC1,
+    AbntC2,
 }
 
 impl TryFrom<u32> for KeyCode {
@@ -110,3 +190,176 @@ impl TryFrom<u32> for KeyCode {
         )
     }
 }
+
+impl KeyCode {
+    // names as printed on a us keyboard, e.g. for rebinding menus
+    // use 'InputManager::physical_key_name' to get the name of a physical key in the active layout
+    pub const fn display_name(&self) -> &'static str {
+        match self {
+            KeyCode::Invalid => "Invalid",
+            KeyCode::A => "A",
+            KeyCode::B => "B",
+            KeyCode::C => "C",
+            KeyCode::D => "D",
+            KeyCode::E => "E",
+            KeyCode::F => "F",
+            KeyCode::G => "G",
+            KeyCode::H => "H",
+            KeyCode::I => "I",
+            KeyCode::J => "J",
+            KeyCode::K => "K",
+            KeyCode::L => "L",
+            KeyCode::M => "M",
+            KeyCode::N => "N",
+            KeyCode::O => "O",
+            KeyCode::P => "P",
+            KeyCode::Q => "Q",
+            KeyCode::R => "R",
+            KeyCode::S => "S",
+            KeyCode::T => "T",
+            KeyCode::U => "U",
+            KeyCode::V => "V",
+            KeyCode::W => "W",
+            KeyCode::X => "X",
+            KeyCode::Y => "Y",
+            KeyCode::Z => "Z",
+            KeyCode::Alpha0 => "0",
+            KeyCode::Alpha1 => "1",
+            KeyCode::Alpha2 => "2",
+            KeyCode::Alpha3 => "3",
+            KeyCode::Alpha4 => "4",
+            KeyCode::Alpha5 => "5",
+            KeyCode::Alpha6 => "6",
+            KeyCode::Alpha7 => "7",
+            KeyCode::Alpha8 => "8",
+            KeyCode::Alpha9 => "9",
+            KeyCode::Numpad0 => "Numpad 0",
+            KeyCode::Numpad1 => "Numpad 1",
+            KeyCode::Numpad2 => "Numpad 2",
+            KeyCode::Numpad3 => "Numpad 3",
+            KeyCode::Numpad4 => "Numpad 4",
+            KeyCode::Numpad5 => "Numpad 5",
+            KeyCode::Numpad6 => "Numpad 6",
+            KeyCode::Numpad7 => "Numpad 7",
+            KeyCode::Numpad8 => "Numpad 8",
+            KeyCode::Numpad9 => "Numpad 9",
+            KeyCode::ArrowUp => "Up",
+            KeyCode::ArrowDown => "Down",
+            KeyCode::ArrowRight => "Right",
+            KeyCode::ArrowLeft => "Left",
+            KeyCode::F1 => "F1",
+            KeyCode::F2 => "F2",
+            KeyCode::F3 => "F3",
+            KeyCode::F4 => "F4",
+            KeyCode::F5 => "F5",
+            KeyCode::F6 => "F6",
+            KeyCode::F7 => "F7",
+            KeyCode::F8 => "F8",
+            KeyCode::F9 => "F9",
+            KeyCode::F10 => "F10",
+            KeyCode::F11 => "F11",
+            KeyCode::F12 => "F12",
+            KeyCode::F13 => "F13",
+            KeyCode::F14 => "F14",
+            KeyCode::F15 => "F15",
+            KeyCode::F16 => "F16",
+            KeyCode::F17 => "F17",
+            KeyCode::F18 => "F18",
+            KeyCode::F19 => "F19",
+            KeyCode::F20 => "F20",
+            KeyCode::F21 => "F21",
+            KeyCode::F22 => "F22",
+            KeyCode::F23 => "F23",
+            KeyCode::F24 => "F24",
+            KeyCode::ControlRight => "Right Ctrl",
+            KeyCode::ControlLeft => "Left Ctrl",
+            KeyCode::AltRight => "Right Alt",
+            KeyCode::AltLeft => "Left Alt",
+            KeyCode::ShiftLeft => "Left Shift",
+            KeyCode::ShiftRight => "Right Shift",
+            KeyCode::MetaLeft => "Left Super",
+            KeyCode::MetaRight => "Right Super",
+            KeyCode::Space => "Space",
+            KeyCode::Escape => "Escape",
+            KeyCode::Return => "Enter",
+            KeyCode::Backspace => "Backspace",
+            KeyCode::Tab => "Tab",
+            KeyCode::CapsLock => "Caps Lock",
+            KeyCode::Insert => "Insert",
+            KeyCode::Delete => "Delete",
+            KeyCode::Home => "Home",
+            KeyCode::End => "End",
+            KeyCode::PageUp => "Page Up",
+            KeyCode::PageDown => "Page Down",
+            KeyCode::PrintScreen => "Print Screen",
+            KeyCode::ScrollLock => "Scroll Lock",
+            KeyCode::Pause => "Pause",
+            KeyCode::NumLock => "Num Lock",
+            KeyCode::ContextMenu => "Menu",
+            KeyCode::Grave => "`",
+            KeyCode::Minus => "-",
+            KeyCode::Equals => "=",
+            KeyCode::BracketLeft => "[",
+            KeyCode::BracketRight => "]",
+            KeyCode::Backslash => "\\",
+            KeyCode::Semicolon => ";",
+            KeyCode::Apostrophe => "'",
+            KeyCode::Comma => ",",
+            KeyCode::Period => ".",
+            KeyCode::Slash => "/",
+            KeyCode::IntlBackslash => "Intl \\",
+            KeyCode::Plus => "+",
+            KeyCode::Asterisk => "*",
+            KeyCode::At => "@",
+            KeyCode::Colon => ":",
+            KeyCode::Underline => "_",
+            KeyCode::Caret => "^",
+            KeyCode::NumpadAdd => "Numpad +",
+            KeyCode::NumpadSubtract => "Numpad -",
+            KeyCode::NumpadMultiply => "Numpad *",
+            KeyCode::NumpadDivide => "Numpad /",
+            KeyCode::NumpadDecimal => "Numpad .",
+            KeyCode::NumpadComma => "Numpad ,",
+            KeyCode::NumpadEnter => "Numpad Enter",
+            KeyCode::NumpadEquals => "Numpad =",
+            KeyCode::MediaPlayPause => "Play/Pause",
+            KeyCode::MediaStop => "Stop",
+            KeyCode::MediaNextTrack => "Next Track",
+            KeyCode::MediaPrevTrack => "Previous Track",
+            KeyCode::MediaSelect => "Media",
+            KeyCode::VolumeUp => "Volume Up",
+            KeyCode::VolumeDown => "Volume Down",
+            KeyCode::VolumeMute => "Mute",
+            KeyCode::BrowserBack => "Browser Back",
+            KeyCode::BrowserForward => "Browser Forward",
+            KeyCode::BrowserRefresh => "Refresh",
+            KeyCode::BrowserStop => "Browser Stop",
+            KeyCode::BrowserSearch => "Search",
+            KeyCode::BrowserFavorites => "Favorites",
+            KeyCode::BrowserHome => "Home Page",
+            KeyCode::LaunchMail => "Mail",
+            KeyCode::LaunchCalculator => "Calculator",
+            KeyCode::LaunchMyComputer => "My Computer",
+            KeyCode::Power => "Power",
+            KeyCode::Sleep => "Sleep",
+            KeyCode::Wake => "Wake",
+            KeyCode::Copy => "Copy",
+            KeyCode::Paste => "Paste",
+            KeyCode::Cut => "Cut",
+            KeyCode::Compose => "Compose",
+            KeyCode::Convert => "Convert",
+            KeyCode::NonConvert => "Non Convert",
+            KeyCode::KanaMode => "Kana",
+            KeyCode::KanjiMode => "Kanji",
+            KeyCode::Yen => "Yen",
+            KeyCode::AbntC1 => "Abnt C1",
+            KeyCode::AbntC2 => "Abnt C2",
+        }
+    }
+}
+
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
@@ -5,7 +5,25 @@
 
 
 mod input;
+mod events;
 mod keycodes;
+mod mouse;
+mod text;
+mod gamepad;
+mod actions;
+mod recording;
+mod sequences;
+#[cfg(feature = "gilrs")]
+mod gilrs_backend;
 
-pub use input::{InputManager, KeyState};
+pub use input::{InputManager, KeyState, ModifiersState};
 pub use keycodes::KeyCode;
+pub use events::{InputEvent, InputEventKind};
+pub use mouse::{MouseButton, MouseState};
+pub use text::{EditKey, TextInputEvent, TextInputState};
+pub use gamepad::{GamepadId, GamepadButton, GamepadAxis, GamepadStick, GamepadEvent, GamepadState, DeadZone, GamepadDeadZones, GamepadBackend, MockGamepadBackend};
+pub use actions::{ActionBinding, AxisBinding, MouseAxis, InputMap, InputMapFile};
+pub use recording::{RecordedInput, TimedInput, RecordedFrame, InputRecording, InputRecorder, InputReplayer};
+pub use sequences::{InputPattern, PatternStep, SequenceMatcher, RecognizedPattern};
+#[cfg(feature = "gilrs")]
+pub use gilrs_backend::GilrsGamepadBackend;
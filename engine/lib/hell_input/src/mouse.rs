@@ -0,0 +1,164 @@
+use hell_math::Vec2;
+use strum::EnumCount;
+
+use crate::input::KeyState;
+
+
+
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, strum::EnumCount, serde::Serialize, serde::Deserialize)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+}
+
+
+
+
+// positions are in physical pixels with the origin in the top left corner of the window
+#[derive(Debug, Clone)]
+pub struct MouseState {
+    button_states: [KeyState; MouseButton::COUNT],
+    position: Vec2,
+    window_size: Vec2,
+    is_inside_window: bool,
+    // accumulated over the current frame
+    motion_delta: Vec2,
+    scroll_delta: Vec2,
+}
+
+impl Default for MouseState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MouseState {
+    pub fn new() -> Self {
+        Self {
+            button_states: [KeyState::NeverUsed; MouseButton::COUNT],
+            position: Vec2::zero(),
+            window_size: Vec2::new(1.0, 1.0),
+            is_inside_window: false,
+            motion_delta: Vec2::zero(),
+            scroll_delta: Vec2::zero(),
+        }
+    }
+}
+
+// update
+// ------
+impl MouseState {
+    pub fn update_button_state(&mut self, button: MouseButton, new_state: KeyState) {
+        let state = &mut self.button_states[button as usize];
+        *state = state.transition(new_state);
+    }
+
+    pub fn update_position(&mut self, position: Vec2, window_size: Vec2) {
+        self.position = position;
+        self.window_size = window_size.max(Vec2::new(1.0, 1.0));
+    }
+
+    pub fn update_inside_window(&mut self, is_inside: bool) {
+        self.is_inside_window = is_inside;
+    }
+
+    // raw device motion - keeps working while the cursor is grabbed
+    pub fn add_motion(&mut self, delta: Vec2) {
+        self.motion_delta += delta;
+    }
+
+    // measured in lines, positive y scrolls up
+    pub fn add_scroll(&mut self, delta: Vec2) {
+        self.scroll_delta += delta;
+    }
+
+    // pressed buttons become held, released buttons become inactive and the deltas are cleared
+    pub fn advance_frame(&mut self) {
+        self.button_states.iter_mut().for_each(|s| *s = s.advance_frame());
+
+        self.motion_delta = Vec2::zero();
+        self.scroll_delta = Vec2::zero();
+    }
+}
+
+// query
+// -----
+impl MouseState {
+    pub fn button_state(&self, button: MouseButton) -> KeyState {
+        self.button_states[button as usize]
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    // [0, 0] is the top left, [1, 1] the bottom right corner of the window
+    pub fn position_normalized(&self) -> Vec2 {
+        self.position / self.window_size
+    }
+
+    pub fn is_inside_window(&self) -> bool {
+        self.is_inside_window
+    }
+
+    pub fn motion_delta(&self) -> Vec2 {
+        self.motion_delta
+    }
+
+    pub fn scroll_delta(&self) -> Vec2 {
+        self.scroll_delta
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn press_hold_release_cycle() {
+        let mut mouse = MouseState::new();
+        assert_eq!(mouse.button_state(MouseButton::Left), KeyState::NeverUsed);
+
+        mouse.update_button_state(MouseButton::Left, KeyState::Pressed);
+        assert_eq!(mouse.button_state(MouseButton::Left), KeyState::Pressed);
+
+        mouse.advance_frame();
+        assert_eq!(mouse.button_state(MouseButton::Left), KeyState::Held);
+        mouse.advance_frame();
+        assert_eq!(mouse.button_state(MouseButton::Left), KeyState::Held);
+
+        mouse.update_button_state(MouseButton::Left, KeyState::Released);
+        assert_eq!(mouse.button_state(MouseButton::Left), KeyState::Released);
+
+        mouse.advance_frame();
+        assert_eq!(mouse.button_state(MouseButton::Left), KeyState::Inactive);
+        assert_eq!(mouse.button_state(MouseButton::Right), KeyState::NeverUsed);
+    }
+
+    #[test]
+    fn deltas_are_cleared_every_frame() {
+        let mut mouse = MouseState::new();
+        mouse.add_motion(Vec2::new(1.0, 2.0));
+        mouse.add_motion(Vec2::new(3.0, 4.0));
+        mouse.add_scroll(Vec2::new(0.0, 1.0));
+        assert_eq!(mouse.motion_delta(), Vec2::new(4.0, 6.0));
+        assert_eq!(mouse.scroll_delta(), Vec2::new(0.0, 1.0));
+
+        mouse.advance_frame();
+        assert_eq!(mouse.motion_delta(), Vec2::zero());
+        assert_eq!(mouse.scroll_delta(), Vec2::zero());
+    }
+
+    #[test]
+    fn normalized_position() {
+        let mut mouse = MouseState::new();
+        mouse.update_position(Vec2::new(200.0, 150.0), Vec2::new(800.0, 600.0));
+        assert_eq!(mouse.position_normalized(), Vec2::new(0.25, 0.25));
+    }
+}
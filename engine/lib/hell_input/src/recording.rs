@@ -0,0 +1,421 @@
+use std::{fs, path::Path};
+use std::time::{Duration, Instant};
+
+use hell_core::error::{HellResult, HellError, HellErrorKind};
+
+use crate::input::{InputManager, KeyState, ModifiersState};
+use crate::keycodes::KeyCode;
+
+
+
+// ----------------------------------------------------------------------------
+// recorded input
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RecordedInput {
+    // the state as it was passed to 'update_key_state', before 'Pressed' turns into 'Held'
+    Key { key: KeyCode, state: KeyState },
+    Modifiers(ModifiersState),
+    PhysicalKey { key: KeyCode, state: KeyState },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TimedInput {
+    // time since the start of the frame, the end of the previous frame
+    pub offset: Duration,
+    pub input: RecordedInput,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedFrame {
+    pub frame: u32,
+    pub delta_time: f32,
+    // in the order in which they were received
+    pub inputs: Vec<TimedInput>,
+}
+
+// every frame is stored, even if it didn't receive any input, so the delta times can be replayed as well
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InputRecording {
+    pub frames: Vec<RecordedFrame>,
+}
+
+
+
+// ----------------------------------------------------------------------------
+// file format
+// ----------------------------------------------------------------------------
+
+// all values are little endian
+//
+// header: magic 'HREC' | version: u8 | frame count: u32
+// frame:  frame: u32 | delta time: f32 | input count: u32 | inputs
+// input:  offset in microseconds: u32 | input
+//
+//         0: u8 | keycode: u16 | key state: u8
+//         1: u8 | modifiers: u32
+//         2: u8 | physical keycode: u16 | key state: u8
+
+impl InputRecording {
+    const MAGIC: &'static [u8; 4] = b"HREC";
+    const VERSION: u8 = 1;
+
+    const TAG_KEY: u8 = 0;
+    const TAG_MODIFIERS: u8 = 1;
+    const TAG_PHYSICAL_KEY: u8 = 2;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(9 + self.frames.len() * 12);
+        bytes.extend_from_slice(Self::MAGIC);
+        bytes.push(Self::VERSION);
+        bytes.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+
+        for frame in &self.frames {
+            bytes.extend_from_slice(&frame.frame.to_le_bytes());
+            bytes.extend_from_slice(&frame.delta_time.to_le_bytes());
+            bytes.extend_from_slice(&(frame.inputs.len() as u32).to_le_bytes());
+
+            for TimedInput { offset, input } in &frame.inputs {
+                bytes.extend_from_slice(&(offset.as_micros().min(u32::MAX as u128) as u32).to_le_bytes());
+
+                match input {
+                    RecordedInput::Key { key, state } => {
+                        bytes.push(Self::TAG_KEY);
+                        bytes.extend_from_slice(&(*key as u16).to_le_bytes());
+                        bytes.push(*state as u8);
+                    }
+                    RecordedInput::PhysicalKey { key, state } => {
+                        bytes.push(Self::TAG_PHYSICAL_KEY);
+                        bytes.extend_from_slice(&(*key as u16).to_le_bytes());
+                        bytes.push(*state as u8);
+                    }
+                    RecordedInput::Modifiers(modifiers) => {
+                        bytes.push(Self::TAG_MODIFIERS);
+                        bytes.extend_from_slice(&modifiers.bits().to_le_bytes());
+                    }
+                }
+            }
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> HellResult<Self> {
+        let mut reader = ByteReader { bytes, pos: 0 };
+
+        if reader.read::<4>()? != *Self::MAGIC {
+            return Err(recording_err("not an input recording".to_string()));
+        }
+
+        let version = reader.read_u8()?;
+        if version != Self::VERSION {
+            return Err(recording_err(format!("unsupported version '{}'", version)));
+        }
+
+        let frame_count = reader.read_u32()? as usize;
+        let mut frames = Vec::with_capacity(frame_count.min(bytes.len() / 10));
+
+        for _ in 0..frame_count {
+            let frame = reader.read_u32()?;
+            let delta_time = f32::from_le_bytes(reader.read()?);
+            let input_count = reader.read_u32()?;
+
+            let inputs = (0..input_count)
+                .map(|_| Self::read_input(&mut reader))
+                .collect::<HellResult<Vec<_>>>()?;
+
+            frames.push(RecordedFrame { frame, delta_time, inputs });
+        }
+
+        Ok(Self { frames })
+    }
+
+    fn read_input(reader: &mut ByteReader) -> HellResult<TimedInput> {
+        let offset = Duration::from_micros(reader.read_u32()? as u64);
+        let input = Self::read_recorded_input(reader)?;
+        Ok(TimedInput { offset, input })
+    }
+
+    fn read_recorded_input(reader: &mut ByteReader) -> HellResult<RecordedInput> {
+        match reader.read_u8()? {
+            Self::TAG_KEY => {
+                let (key, state) = Self::read_key(reader)?;
+                Ok(RecordedInput::Key { key, state })
+            }
+            Self::TAG_PHYSICAL_KEY => {
+                let (key, state) = Self::read_key(reader)?;
+                Ok(RecordedInput::PhysicalKey { key, state })
+            }
+            Self::TAG_MODIFIERS => {
+                let bits = reader.read_u32()?;
+                let modifiers = ModifiersState::from_bits(bits).ok_or_else(|| recording_err(format!("invalid modifiers '{:#b}'", bits)))?;
+                Ok(RecordedInput::Modifiers(modifiers))
+            }
+            tag => Err(recording_err(format!("unknown input tag '{}'", tag))),
+        }
+    }
+
+    fn read_key(reader: &mut ByteReader) -> HellResult<(KeyCode, KeyState)> {
+        let key = KeyCode::try_from(u16::from_le_bytes(reader.read()?) as u32)?;
+        let state = key_state_from_u8(reader.read_u8()?)?;
+        Ok((key, state))
+    }
+
+    pub fn load_file(path: impl AsRef<Path>) -> HellResult<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn save_file(&self, path: impl AsRef<Path>) -> HellResult<()> {
+        fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+}
+
+fn recording_err(msg: String) -> HellError {
+    HellError::from_msg(HellErrorKind::GenericError, format!("failed to read input recording: {}", msg))
+}
+
+fn key_state_from_u8(val: u8) -> HellResult<KeyState> {
+    match val {
+        0 => Ok(KeyState::NeverUsed),
+        1 => Ok(KeyState::Inactive),
+        2 => Ok(KeyState::Pressed),
+        3 => Ok(KeyState::Held),
+        4 => Ok(KeyState::Released),
+        _ => Err(recording_err(format!("invalid key state '{}'", val))),
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl ByteReader<'_> {
+    fn read<const N: usize>(&mut self) -> HellResult<[u8; N]> {
+        let chunk = self.bytes.get(self.pos..self.pos + N).ok_or_else(|| recording_err("unexpected end of file".to_string()))?;
+        self.pos += N;
+        Ok(chunk.try_into().unwrap())
+    }
+
+    fn read_u8(&mut self) -> HellResult<u8> {
+        Ok(self.read::<1>()?[0])
+    }
+
+    fn read_u32(&mut self) -> HellResult<u32> {
+        Ok(u32::from_le_bytes(self.read()?))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// recorder
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct InputRecorder {
+    recording: InputRecording,
+    // inputs received since the last call to 'end_frame'
+    pending: Vec<TimedInput>,
+    frame: u32,
+    frame_start: Instant,
+}
+
+impl InputRecorder {
+    // 'start' is the start of the first frame
+    pub fn new(start: Instant) -> Self {
+        Self {
+            recording: InputRecording::new(),
+            pending: Vec::new(),
+            frame: 0,
+            frame_start: start,
+        }
+    }
+
+    // 'time' is the time at which the input was received
+    pub fn record(&mut self, input: RecordedInput, time: Instant) {
+        let offset = time.saturating_duration_since(self.frame_start);
+        self.pending.push(TimedInput { offset, input });
+    }
+
+    // stores all pending inputs as the next frame, 'time' is the start of the next frame
+    pub fn end_frame(&mut self, delta_time: f32, time: Instant) {
+        self.recording.frames.push(RecordedFrame {
+            frame: self.frame,
+            delta_time,
+            inputs: std::mem::take(&mut self.pending),
+        });
+        self.frame += 1;
+        self.frame_start = time;
+    }
+
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+
+    // pending inputs of an unfinished frame are dropped
+    pub fn finish(self) -> InputRecording {
+        self.recording
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// replayer
+// ----------------------------------------------------------------------------
+
+// the replayed events are stamped with their recorded offsets, the frames are advanced by the recorded delta times
+#[derive(Debug, Clone)]
+pub struct InputReplayer {
+    recording: InputRecording,
+    next_frame: usize,
+    // start of the next frame, set when the first frame is replayed
+    frame_start: Option<Instant>,
+}
+
+impl InputReplayer {
+    pub fn new(recording: InputRecording) -> Self {
+        Self { recording, next_frame: 0, frame_start: None }
+    }
+
+    pub fn load_file(path: impl AsRef<Path>) -> HellResult<Self> {
+        Ok(Self::new(InputRecording::load_file(path)?))
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.recording.frames.len()
+    }
+
+    // index of the next frame that will be replayed
+    pub fn next_frame(&self) -> usize {
+        self.next_frame
+    }
+
+    // feeds all inputs of the next frame into the input manager and returns the recorded delta time,
+    // returns 'None' once all frames have been replayed
+    // the event time of the input manager is reset to the current time afterwards
+    pub fn replay_frame(&mut self, input: &mut InputManager) -> HellResult<Option<f32>> {
+        let frame = match self.recording.frames.get(self.next_frame) {
+            Some(frame) => frame,
+            None => return Ok(None),
+        };
+
+        let frame_start = *self.frame_start.get_or_insert_with(|| input.event_time());
+        let result = frame.inputs.iter().try_for_each(|TimedInput { offset, input: recorded }| {
+            input.set_event_time(Some(frame_start + *offset));
+            match recorded {
+                RecordedInput::Key { key, state } => input.update_key_state(*key, *state),
+                RecordedInput::Modifiers(modifiers) => { input.update_modifiers_state(*modifiers); Ok(()) }
+                RecordedInput::PhysicalKey { key, state } => input.update_physical_key_state(*key, *state),
+            }
+        });
+        input.set_event_time(None);
+        result?;
+
+        // invalid delta times of a corrupt file don't advance the clock
+        let delta_time = Duration::try_from_secs_f32(frame.delta_time).unwrap_or_default();
+        self.frame_start = Some(frame_start + delta_time);
+        self.next_frame += 1;
+        Ok(Some(frame.delta_time))
+    }
+
+    pub fn rewind(&mut self) {
+        self.next_frame = 0;
+        self.frame_start = None;
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    fn sample_recording() -> InputRecording {
+        let start = Instant::now();
+        let mut recorder = InputRecorder::new(start);
+        recorder.record(RecordedInput::Modifiers(ModifiersState::SHIFT | ModifiersState::LSHIFT), start + ms(1));
+        recorder.record(RecordedInput::Key { key: KeyCode::A, state: KeyState::Pressed }, start + ms(2));
+        recorder.record(RecordedInput::PhysicalKey { key: KeyCode::Q, state: KeyState::Pressed }, start + ms(2));
+        recorder.end_frame(0.016, start + ms(16));
+        recorder.end_frame(0.017, start + ms(33));
+        recorder.record(RecordedInput::Key { key: KeyCode::A, state: KeyState::Released }, start + ms(40));
+        recorder.end_frame(0.018, start + ms(51));
+        recorder.finish()
+    }
+
+    #[test]
+    fn round_trip() {
+        let recording = sample_recording();
+        assert_eq!(recording.frame_count(), 3);
+        assert_eq!(recording.frames[2].inputs[0].offset, ms(7));
+        assert_eq!(InputRecording::from_bytes(&recording.to_bytes()).unwrap(), recording);
+    }
+
+    #[test]
+    fn more_than_u16_inputs_per_frame() {
+        let start = Instant::now();
+        let mut recorder = InputRecorder::new(start);
+        for _ in 0..(u16::MAX as usize + 10) {
+            recorder.record(RecordedInput::Key { key: KeyCode::A, state: KeyState::Pressed }, start);
+        }
+        recorder.end_frame(0.016, start + ms(16));
+
+        let recording = recorder.finish();
+        let loaded = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+        assert_eq!(loaded.frames[0].inputs.len(), u16::MAX as usize + 10);
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        let bytes = sample_recording().to_bytes();
+        assert!(InputRecording::from_bytes(b"NOPE").is_err());
+        assert!(InputRecording::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut future_version = bytes.clone();
+        future_version[4] = InputRecording::VERSION + 1;
+        assert!(InputRecording::from_bytes(&future_version).is_err());
+    }
+
+    #[test]
+    fn replay() {
+        let mut input = InputManager::new();
+        let mut replayer = InputReplayer::new(sample_recording());
+
+        assert_eq!(replayer.replay_frame(&mut input).unwrap(), Some(0.016));
+        let start = input.events()[0].timestamp - ms(1);
+        assert_eq!(input.events()[1].timestamp, start + ms(2));
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Pressed);
+        assert_eq!(input.physical_key_state(KeyCode::Q), KeyState::Pressed);
+        assert!(input.modifiers_state().shift());
+        input.advance_frame();
+
+        assert_eq!(replayer.replay_frame(&mut input).unwrap(), Some(0.017));
+        assert_eq!(input.key_state(KeyCode::A), KeyState::Held);
+        input.advance_frame();
+
+        assert_eq!(replayer.replay_frame(&mut input).unwrap(), Some(0.018));
+        assert!(input.just_released(KeyCode::A));
+        // the frames are advanced by the recorded delta times, which aren't exact in f32
+        let release_time = input.events()[0].timestamp.duration_since(start).as_secs_f64();
+        assert!((release_time - 0.040).abs() < 1e-6, "{}", release_time);
+        assert!(replayer.is_finished());
+        assert_eq!(replayer.replay_frame(&mut input).unwrap(), None);
+    }
+}
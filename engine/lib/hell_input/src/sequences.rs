@@ -0,0 +1,436 @@
+use std::time::{Duration, Instant};
+
+use crate::events::InputEventKind;
+use crate::input::InputManager;
+use crate::keycodes::KeyCode;
+
+
+
+// ----------------------------------------------------------------------------
+// patterns
+// ----------------------------------------------------------------------------
+
+// name: hadouken
+// steps:
+//   - keys: [ArrowDown]
+//   - keys: [ArrowDown, ArrowRight]
+//     max_delay: 0.2
+//   - keys: [ArrowRight, J]
+//     max_delay: 0.2
+//     chord_window: 0.1
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InputPattern {
+    pub name: String,
+    pub steps: Vec<PatternStep>,
+}
+
+// all keys of a step have to be down at the same time, a single key is just a chord of size 1
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PatternStep {
+    pub keys: Vec<KeyCode>,
+    // max. seconds between the completion of the previous step and the completion of this one
+    #[serde(default = "PatternStep::default_max_delay")]
+    pub max_delay: f32,
+    // max. seconds between the first and the last key press of the chord
+    // keys that are still held from the previous step don't have to be pressed again
+    #[serde(default = "PatternStep::default_chord_window")]
+    pub chord_window: f32,
+}
+
+impl PatternStep {
+    pub const DEFAULT_MAX_DELAY: f32 = 0.25;
+    pub const DEFAULT_CHORD_WINDOW: f32 = 0.05;
+    // longer delays and chord windows are clamped by the matcher
+    pub const MAX_DURATION: f32 = 5.0;
+
+    pub fn new(keys: &[KeyCode]) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            max_delay: Self::DEFAULT_MAX_DELAY,
+            chord_window: Self::DEFAULT_CHORD_WINDOW,
+        }
+    }
+
+    pub fn with_max_delay(mut self, max_delay: f32) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_chord_window(mut self, chord_window: f32) -> Self {
+        self.chord_window = chord_window;
+        self
+    }
+
+    fn default_max_delay() -> f32 {
+        Self::DEFAULT_MAX_DELAY
+    }
+
+    fn default_chord_window() -> f32 {
+        Self::DEFAULT_CHORD_WINDOW
+    }
+}
+
+impl InputPattern {
+    pub fn new(name: impl Into<String>, steps: Vec<PatternStep>) -> Self {
+        Self { name: name.into(), steps }
+    }
+
+    // a single chord, e.g. 'punch + kick'
+    pub fn chord(name: impl Into<String>, keys: &[KeyCode], chord_window: f32) -> Self {
+        Self::new(name, vec![PatternStep::new(keys).with_chord_window(chord_window)])
+    }
+
+    // a sequence of single keys with the same delay between all of them
+    pub fn sequence(name: impl Into<String>, keys: &[KeyCode], max_delay: f32) -> Self {
+        let steps = keys.iter().map(|k| PatternStep::new(&[*k]).with_max_delay(max_delay)).collect();
+        Self::new(name, steps)
+    }
+
+    // upper bound of the time it takes to enter the whole pattern
+    fn duration(&self) -> f32 {
+        self.steps.iter().map(|s| s.max_delay + s.chord_window).sum()
+    }
+}
+impl PatternStep {
+    // negative and nan values are treated as 0, larger values (including infinity) as 'MAX_DURATION'
+    fn clamp_duration(secs: f32) -> f32 {
+        if secs.is_nan() { return 0.0; }
+        secs.clamp(0.0, Self::MAX_DURATION)
+    }
+
+    fn clamped(mut self) -> Self {
+        self.max_delay = Self::clamp_duration(self.max_delay);
+        self.chord_window = Self::clamp_duration(self.chord_window);
+        self
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// matcher
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecognizedPattern {
+    pub name: String,
+    // time of the key press that completed the pattern
+    pub time: Instant,
+}
+
+#[derive(Debug, Clone)]
+struct BufferedPress {
+    key: KeyCode,
+    time: Instant,
+    // all keys that are down after this press, together with the time they were pressed
+    held: Vec<(KeyCode, Instant)>,
+}
+
+impl BufferedPress {
+    fn press_time(&self, key: KeyCode) -> Option<Instant> {
+        self.held.iter().find(|(k, _)| *k == key).map(|(_, t)| *t)
+    }
+}
+
+// feed it with the events of every frame through 'update', or with synthetic key events through 'feed_key'
+// every pattern fires at most once per key press, key presses that completed a pattern can't start it again
+// the buffer keeps at most 'MAX_BUFFERED_PRESSES' presses, longer patterns can't be recognized
+#[derive(Debug, Clone, Default)]
+pub struct SequenceMatcher {
+    patterns: Vec<InputPattern>,
+    // time of the last match of every pattern
+    last_matches: Vec<Option<Instant>>,
+    buffer: Vec<BufferedPress>,
+    // presses older than this are dropped from the buffer
+    buffer_duration: Duration,
+    held: Vec<(KeyCode, Instant)>,
+    recognized: Vec<RecognizedPattern>,
+}
+
+impl SequenceMatcher {
+    pub const MAX_BUFFERED_PRESSES: usize = 64;
+
+    pub fn new(patterns: Vec<InputPattern>) -> Self {
+        let mut matcher = Self::default();
+        patterns.into_iter().for_each(|p| matcher.add_pattern(p));
+        matcher
+    }
+
+    // the durations of all steps are clamped to [0, 'PatternStep::MAX_DURATION']
+    pub fn add_pattern(&mut self, mut pattern: InputPattern) {
+        pattern.steps = pattern.steps.into_iter().map(PatternStep::clamped).collect();
+
+        let duration = Duration::from_secs_f32(pattern.duration());
+        self.buffer_duration = self.buffer_duration.max(duration);
+        self.patterns.push(pattern);
+        self.last_matches.push(None);
+    }
+
+    pub fn patterns(&self) -> &[InputPattern] {
+        &self.patterns
+    }
+
+    // forgets all buffered key presses
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.held.clear();
+        self.recognized.clear();
+        self.last_matches.iter_mut().for_each(|m| *m = None);
+    }
+}
+
+impl SequenceMatcher {
+    // consumes the key events of the current frame, the recognized patterns of the previous frame are cleared
+    pub fn update(&mut self, input: &InputManager) {
+        self.clear_recognized();
+
+        for event in input.events() {
+            if let InputEventKind::Key { key, pressed, repeat: false } = event.kind {
+                self.feed_key(key, pressed, event.timestamp);
+            }
+        }
+    }
+
+    // key repeats must not be passed in, events have to be ordered by their time
+    pub fn feed_key(&mut self, key: KeyCode, pressed: bool, time: Instant) {
+        self.held.retain(|(k, _)| *k != key);
+
+        if !pressed { return; }
+
+        self.held.push((key, time));
+        self.buffer.push(BufferedPress { key, time, held: self.held.clone() });
+        self.buffer.retain(|p| time.saturating_duration_since(p.time) <= self.buffer_duration);
+        if self.buffer.len() > Self::MAX_BUFFERED_PRESSES {
+            self.buffer.drain(..self.buffer.len() - Self::MAX_BUFFERED_PRESSES);
+        }
+
+        for (idx, pattern) in self.patterns.iter().enumerate() {
+            let last_step = match pattern.steps.len().checked_sub(1) {
+                Some(last_step) => last_step,
+                None => continue,
+            };
+
+            let newest = self.buffer.len() - 1;
+            if Self::match_step(pattern, last_step, newest, &self.buffer, self.last_matches[idx]) {
+                self.last_matches[idx] = Some(time);
+                self.recognized.push(RecognizedPattern { name: pattern.name.clone(), time });
+            }
+        }
+    }
+
+    // checks if step 'step_idx' is completed by the press at 'press_idx' and all previous steps match before it
+    fn match_step(pattern: &InputPattern, step_idx: usize, press_idx: usize, buffer: &[BufferedPress], last_match: Option<Instant>) -> bool {
+        let step = &pattern.steps[step_idx];
+        let press = &buffer[press_idx];
+
+        if !step.keys.contains(&press.key) { return false; }
+        if last_match.map(|t| press.time <= t).unwrap_or(false) { return false; }
+
+        // all keys have to be down, either pressed within the chord window or held since the previous step
+        let press_times: Option<Vec<Instant>> = step.keys.iter().map(|k| press.press_time(*k)).collect();
+        let press_times = match press_times {
+            Some(press_times) => press_times,
+            None => return false,
+        };
+
+        let chord_window = Duration::from_secs_f32(step.chord_window);
+        let in_chord_window = |t: Instant| press.time.saturating_duration_since(t) <= chord_window;
+
+        if step_idx == 0 {
+            return press_times.iter().all(|t| in_chord_window(*t));
+        }
+
+        // try every press of the previous step that is close enough
+        let max_delay = Duration::from_secs_f32(step.max_delay);
+        (0..press_idx).rev()
+            .take_while(|prev_idx| press.time.saturating_duration_since(buffer[*prev_idx].time) <= max_delay)
+            .any(|prev_idx| {
+                let prev_time = buffer[prev_idx].time;
+                let keys_valid = press_times.iter().all(|t| *t <= prev_time || in_chord_window(*t));
+                keys_valid && Self::match_step(pattern, step_idx - 1, prev_idx, buffer, last_match)
+            })
+    }
+}
+
+impl SequenceMatcher {
+    // in the order in which they were recognized
+    pub fn recognized(&self) -> &[RecognizedPattern] {
+        &self.recognized
+    }
+
+    pub fn is_recognized(&self, name: &str) -> bool {
+        self.recognized.iter().any(|r| r.name == name)
+    }
+
+    pub fn clear_recognized(&mut self) {
+        self.recognized.clear();
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Feeder {
+        matcher: SequenceMatcher,
+        start: Instant,
+    }
+
+    impl Feeder {
+        fn new(patterns: Vec<InputPattern>) -> Self {
+            Self { matcher: SequenceMatcher::new(patterns), start: Instant::now() }
+        }
+
+        // presses and releases the key at the given millisecond, returns the patterns that were recognized by the press
+        fn tap(&mut self, key: KeyCode, ms: u64) -> Vec<String> {
+            let names = self.press(key, ms);
+            self.release(key, ms);
+            names
+        }
+
+        fn press(&mut self, key: KeyCode, ms: u64) -> Vec<String> {
+            self.matcher.clear_recognized();
+            self.matcher.feed_key(key, true, self.start + Duration::from_millis(ms));
+            self.matcher.recognized().iter().map(|r| r.name.clone()).collect()
+        }
+
+        fn release(&mut self, key: KeyCode, ms: u64) {
+            self.matcher.feed_key(key, false, self.start + Duration::from_millis(ms));
+        }
+    }
+
+    #[test]
+    fn sequence_within_delay() {
+        let mut f = Feeder::new(vec![InputPattern::sequence("abc", &[KeyCode::A, KeyCode::B, KeyCode::C], 0.2)]);
+        assert!(f.tap(KeyCode::A, 0).is_empty());
+        assert!(f.tap(KeyCode::B, 100).is_empty());
+        assert_eq!(f.tap(KeyCode::C, 250), vec!["abc"]);
+    }
+
+    #[test]
+    fn sequence_timeout() {
+        let mut f = Feeder::new(vec![InputPattern::sequence("abc", &[KeyCode::A, KeyCode::B, KeyCode::C], 0.2)]);
+        f.tap(KeyCode::A, 0);
+        f.tap(KeyCode::B, 100);
+        assert!(f.tap(KeyCode::C, 400).is_empty());
+
+        // a fresh attempt still works after the timeout
+        f.tap(KeyCode::A, 1000);
+        f.tap(KeyCode::B, 1100);
+        assert_eq!(f.tap(KeyCode::C, 1200), vec!["abc"]);
+    }
+
+    #[test]
+    fn sequence_with_noise_in_between() {
+        let mut f = Feeder::new(vec![InputPattern::sequence("ab", &[KeyCode::A, KeyCode::B], 0.2)]);
+        f.tap(KeyCode::A, 0);
+        f.tap(KeyCode::K, 50);
+        assert_eq!(f.tap(KeyCode::B, 100), vec!["ab"]);
+    }
+
+    #[test]
+    fn chord_within_window() {
+        let mut f = Feeder::new(vec![InputPattern::chord("jk", &[KeyCode::J, KeyCode::K], 0.05)]);
+        assert!(f.press(KeyCode::J, 0).is_empty());
+        assert_eq!(f.press(KeyCode::K, 30), vec!["jk"]);
+    }
+
+    #[test]
+    fn chord_outside_window() {
+        let mut f = Feeder::new(vec![InputPattern::chord("jk", &[KeyCode::J, KeyCode::K], 0.05)]);
+        f.press(KeyCode::J, 0);
+        assert!(f.press(KeyCode::K, 100).is_empty());
+    }
+
+    #[test]
+    fn chord_needs_all_keys_down() {
+        let mut f = Feeder::new(vec![InputPattern::chord("jk", &[KeyCode::J, KeyCode::K], 0.05)]);
+        f.tap(KeyCode::J, 0);
+        assert!(f.press(KeyCode::K, 20).is_empty());
+    }
+
+    #[test]
+    fn held_keys_carry_over_into_the_next_step() {
+        let pattern = InputPattern::new("hadouken", vec![
+            PatternStep::new(&[KeyCode::ArrowDown]),
+            PatternStep::new(&[KeyCode::ArrowDown, KeyCode::ArrowRight]).with_max_delay(0.2),
+            PatternStep::new(&[KeyCode::ArrowRight, KeyCode::J]).with_max_delay(0.2).with_chord_window(0.1),
+        ]);
+        let mut f = Feeder::new(vec![pattern]);
+
+        f.press(KeyCode::ArrowDown, 0);
+        f.press(KeyCode::ArrowRight, 100);
+        f.release(KeyCode::ArrowDown, 150);
+        assert_eq!(f.press(KeyCode::J, 200), vec!["hadouken"]);
+    }
+
+    #[test]
+    fn overlapping_patterns() {
+        let mut f = Feeder::new(vec![
+            InputPattern::sequence("ab", &[KeyCode::A, KeyCode::B], 0.2),
+            InputPattern::sequence("abc", &[KeyCode::A, KeyCode::B, KeyCode::C], 0.2),
+            InputPattern::sequence("bc", &[KeyCode::B, KeyCode::C], 0.2),
+        ]);
+
+        f.tap(KeyCode::A, 0);
+        assert_eq!(f.tap(KeyCode::B, 100), vec!["ab"]);
+        assert_eq!(f.tap(KeyCode::C, 200), vec!["abc", "bc"]);
+    }
+
+    #[test]
+    fn presses_can_not_complete_a_pattern_twice() {
+        let mut f = Feeder::new(vec![InputPattern::sequence("aa", &[KeyCode::A, KeyCode::A], 0.2)]);
+        f.tap(KeyCode::A, 0);
+        assert_eq!(f.tap(KeyCode::A, 100), vec!["aa"]);
+        // the second press already completed the pattern, it can't start it again
+        assert!(f.tap(KeyCode::A, 200).is_empty());
+        assert_eq!(f.tap(KeyCode::A, 300), vec!["aa"]);
+    }
+
+    #[test]
+    fn non_finite_durations() {
+        let mut f = Feeder::new(vec![
+            InputPattern::sequence("inf", &[KeyCode::A, KeyCode::B], f32::INFINITY),
+            InputPattern::sequence("nan", &[KeyCode::A, KeyCode::C], f32::NAN),
+            InputPattern::sequence("huge", &[KeyCode::A, KeyCode::K], 1e30),
+        ]);
+
+        let max_ms = (PatternStep::MAX_DURATION * 1000.0) as u64;
+        assert!(f.matcher.patterns().iter().flat_map(|p| &p.steps).all(|s| s.max_delay.is_finite()));
+
+        f.tap(KeyCode::A, 0);
+        assert_eq!(f.tap(KeyCode::B, max_ms - 10), vec!["inf"]);
+        assert!(f.tap(KeyCode::C, max_ms - 9).is_empty());
+        assert_eq!(f.tap(KeyCode::K, max_ms - 8), vec!["huge"]);
+
+        // clamped to the max. duration
+        f.tap(KeyCode::A, 10_000);
+        assert!(f.tap(KeyCode::B, 10_000 + max_ms + 1).is_empty());
+    }
+
+    #[test]
+    fn buffer_is_bounded() {
+        let mut f = Feeder::new(vec![
+            InputPattern::sequence("inf", &[KeyCode::A, KeyCode::B, KeyCode::C, KeyCode::D], f32::INFINITY),
+        ]);
+
+        for i in 0..10_000 {
+            f.tap(if i % 2 == 0 { KeyCode::A } else { KeyCode::B }, i);
+            assert!(f.matcher.buffer.len() <= SequenceMatcher::MAX_BUFFERED_PRESSES);
+        }
+
+        assert_eq!(f.tap(KeyCode::C, 10_000), Vec::<String>::new());
+        assert_eq!(f.tap(KeyCode::D, 10_001), vec!["inf"]);
+    }
+
+    #[test]
+    fn reset_forgets_presses() {
+        let mut f = Feeder::new(vec![InputPattern::sequence("ab", &[KeyCode::A, KeyCode::B], 0.2)]);
+        f.tap(KeyCode::A, 0);
+        f.matcher.reset();
+        assert!(f.tap(KeyCode::B, 100).is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::input::KeyState;
+
+
+
+// ----------------------------------------------------------------------------
+// text input events
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EditKey {
+    Backspace,
+    Delete,
+    Home,
+    End,
+    Tab,
+    Enter,
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextInputEvent {
+    // a committed character, control characters are never reported here
+    Char(char),
+    // 'repeat' is set for presses generated by holding the key down
+    Edit { key: EditKey, repeat: bool },
+    ImeEnabled,
+    // the text that is currently being composed, it replaces the previous preedit text
+    // the cursor is a byte range into the text, 'None' hides the cursor
+    ImePreedit { text: String, cursor: Option<(usize, usize)> },
+    // the composition is finished, the preedit text has to be cleared
+    ImeCommit(String),
+    ImeDisabled,
+}
+
+
+
+// ----------------------------------------------------------------------------
+// text input state
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default)]
+pub struct TextInputState {
+    // events of the current frame, in the order in which they were received
+    events: Vec<TextInputEvent>,
+    // survives the end of the frame, as long as the composition is running
+    preedit: String,
+    preedit_cursor: Option<(usize, usize)>,
+    ime_enabled: bool,
+    // used to detect key repeats
+    held_edit_keys: Vec<EditKey>,
+}
+
+impl TextInputState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        if c.is_control() { return; }
+        self.events.push(TextInputEvent::Char(c));
+    }
+
+    // every press creates an event, presses of a key that is already down are reported as repeats
+    pub fn update_edit_key(&mut self, key: EditKey, state: KeyState) {
+        if state.is_down() {
+            let repeat = self.held_edit_keys.contains(&key);
+            if !repeat { self.held_edit_keys.push(key); }
+            self.events.push(TextInputEvent::Edit { key, repeat });
+        } else {
+            self.held_edit_keys.retain(|k| *k != key);
+        }
+    }
+
+    pub fn set_ime_enabled(&mut self, is_enabled: bool) {
+        if is_enabled == self.ime_enabled { return; }
+
+        self.ime_enabled = is_enabled;
+        if is_enabled {
+            self.events.push(TextInputEvent::ImeEnabled);
+        } else {
+            self.preedit.clear();
+            self.preedit_cursor = None;
+            self.events.push(TextInputEvent::ImeDisabled);
+        }
+    }
+
+    pub fn ime_preedit(&mut self, text: String, cursor: Option<(usize, usize)>) {
+        self.preedit = text.clone();
+        self.preedit_cursor = cursor;
+        self.events.push(TextInputEvent::ImePreedit { text, cursor });
+    }
+
+    pub fn ime_commit(&mut self, text: String) {
+        self.preedit.clear();
+        self.preedit_cursor = None;
+        self.events.push(TextInputEvent::ImeCommit(text));
+    }
+
+    pub fn advance_frame(&mut self) {
+        self.events.clear();
+    }
+}
+
+impl TextInputState {
+    pub fn events(&self) -> &[TextInputEvent] {
+        &self.events
+    }
+
+    // all characters and ime commits of the current frame
+    pub fn committed_text(&self) -> String {
+        let mut text = String::new();
+        for event in &self.events {
+            match event {
+                TextInputEvent::Char(c) => text.push(*c),
+                TextInputEvent::ImeCommit(commit) => text.push_str(commit),
+                _ => (),
+            }
+        }
+
+        text
+    }
+
+    pub fn edit_keys(&self) -> impl Iterator<Item = EditKey> + '_ {
+        self.events.iter().filter_map(|e| match e {
+            TextInputEvent::Edit { key, .. } => Some(*key),
+            _ => None,
+        })
+    }
+
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    pub fn preedit_cursor(&self) -> Option<(usize, usize)> {
+        self.preedit_cursor
+    }
+
+    pub fn is_ime_enabled(&self) -> bool {
+        self.ime_enabled
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_control_characters() {
+        let mut text = TextInputState::new();
+        for c in ['a', '\u{8}', '\r', '\n', '\t', '\u{7f}', '\u{1b}', 'ß', '語'] {
+            text.push_char(c);
+        }
+
+        assert_eq!(text.events(), &[TextInputEvent::Char('a'), TextInputEvent::Char('ß'), TextInputEvent::Char('語')]);
+    }
+
+    #[test]
+    fn edit_key_repeats() {
+        let mut text = TextInputState::new();
+        text.update_edit_key(EditKey::Backspace, KeyState::Pressed);
+        text.update_edit_key(EditKey::Backspace, KeyState::Pressed);
+        text.update_edit_key(EditKey::Left, KeyState::Pressed);
+        assert_eq!(text.events(), &[
+            TextInputEvent::Edit { key: EditKey::Backspace, repeat: false },
+            TextInputEvent::Edit { key: EditKey::Backspace, repeat: true },
+            TextInputEvent::Edit { key: EditKey::Left, repeat: false },
+        ]);
+        text.advance_frame();
+
+        // held keys survive the end of the frame
+        text.update_edit_key(EditKey::Backspace, KeyState::Pressed);
+        assert_eq!(text.events(), &[TextInputEvent::Edit { key: EditKey::Backspace, repeat: true }]);
+
+        // a release doesn't create an event, the next press isn't a repeat anymore
+        text.update_edit_key(EditKey::Backspace, KeyState::Released);
+        text.update_edit_key(EditKey::Backspace, KeyState::Pressed);
+        assert_eq!(text.edit_keys().collect::<Vec<_>>(), vec![EditKey::Backspace, EditKey::Backspace]);
+        assert_eq!(text.events()[1], TextInputEvent::Edit { key: EditKey::Backspace, repeat: false });
+    }
+
+    #[test]
+    fn ime_commit_clears_the_preedit() {
+        let mut text = TextInputState::new();
+        text.set_ime_enabled(true);
+        text.ime_preedit("にほ".to_string(), Some((6, 6)));
+        text.advance_frame();
+
+        // the preedit text survives the end of the frame
+        assert_eq!(text.preedit(), "にほ");
+        assert_eq!(text.preedit_cursor(), Some((6, 6)));
+
+        text.ime_commit("日本".to_string());
+        assert_eq!(text.preedit(), "");
+        assert_eq!(text.preedit_cursor(), None);
+        assert_eq!(text.events(), &[TextInputEvent::ImeCommit("日本".to_string())]);
+    }
+
+    #[test]
+    fn disabling_the_ime_clears_the_preedit() {
+        let mut text = TextInputState::new();
+        text.set_ime_enabled(true);
+        text.set_ime_enabled(true);
+        text.ime_preedit("か".to_string(), None);
+        assert!(text.is_ime_enabled());
+
+        text.set_ime_enabled(false);
+        assert!(!text.is_ime_enabled());
+        assert_eq!(text.preedit(), "");
+        assert_eq!(text.events(), &[
+            TextInputEvent::ImeEnabled,
+            TextInputEvent::ImePreedit { text: "か".to_string(), cursor: None },
+            TextInputEvent::ImeDisabled,
+        ]);
+    }
+
+    #[test]
+    fn committed_text() {
+        let mut text = TextInputState::new();
+        text.push_char('a');
+        text.update_edit_key(EditKey::Backspace, KeyState::Pressed);
+        text.ime_preedit("に".to_string(), None);
+        text.ime_commit("日本".to_string());
+        text.push_char('!');
+        assert_eq!(text.committed_text(), "a日本!");
+
+        text.advance_frame();
+        assert_eq!(text.committed_text(), "");
+    }
+}
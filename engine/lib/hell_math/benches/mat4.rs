@@ -0,0 +1,46 @@
+// compare the simd and the scalar paths with
+//   cargo bench -p hell_math --features simd
+// without the 'simd' feature both variants run the scalar implementation
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hell_math::{Mat4, Vec3, Vec4, Quat};
+
+
+
+fn sample_mat(seed: f32) -> Mat4 {
+    let rotation = Quat::from_axis_angle(&Vec3::new(1.0, seed, 0.5).unit(), seed);
+    Mat4::from_cols(Vec4::new(1.0, 0.0, 0.0, 0.0), Vec4::new(0.0, 2.0, 0.0, 0.0), Vec4::new(0.0, 0.0, 3.0, 0.0), Vec4::new(seed, -seed, 4.0, 1.0))
+        .mul_mat(&Mat4::from(rotation))
+}
+
+fn bench_mul_mat(c: &mut Criterion) {
+    let lhs = sample_mat(0.3);
+    let rhs = sample_mat(1.7);
+
+    let mut group = c.benchmark_group("mat4_mul_mat");
+    group.bench_function("simd", |b| b.iter(|| black_box(&lhs).mul_mat(black_box(&rhs))));
+    group.bench_function("scalar", |b| b.iter(|| black_box(&lhs).mul_mat_scalar(black_box(&rhs))));
+    group.finish();
+}
+
+fn bench_mul_vec(c: &mut Criterion) {
+    let mat = sample_mat(0.3);
+    let vec = Vec4::new(1.0, 2.0, 3.0, 1.0);
+
+    let mut group = c.benchmark_group("mat4_mul_vec");
+    group.bench_function("simd", |b| b.iter(|| black_box(&mat).mul_vec(black_box(&vec))));
+    group.bench_function("scalar", |b| b.iter(|| black_box(&mat).mul_vec_scalar(black_box(&vec))));
+    group.finish();
+}
+
+fn bench_inverse(c: &mut Criterion) {
+    let mat = sample_mat(0.3);
+
+    let mut group = c.benchmark_group("mat4_inverse");
+    group.bench_function("simd", |b| b.iter(|| black_box(mat).inverse()));
+    group.bench_function("scalar", |b| b.iter(|| black_box(mat).inverse_scalar()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_mul_mat, bench_mul_vec, bench_inverse);
+criterion_main!(benches);
@@ -0,0 +1,454 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg, Rem, RemAssign};
+
+use crate::vec::Scalar;
+
+
+
+
+// ----------------------------------------------------------------------------
+// fixed point scalars
+// ----------------------------------------------------------------------------
+// all operations are implemented with integer arithmetic only, so results are bit-identical on every machine
+// conversions from and to floats are only intended for setup and debugging
+// the operators wrap around on overflow in debug and release builds alike, use the 'saturating_*' methods to clamp instead
+
+macro_rules! impl_fixed {
+    ($fixed:ident : $raw:ident, $wide:ident, $wide_unsigned:ident => $frac_bits:literal) => {
+        #[repr(transparent)]
+        #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $fixed($raw);
+
+        // constants
+        // ---------
+        impl $fixed {
+            pub const FRAC_BITS: u32 = $frac_bits;
+            const ONE_RAW: $raw = 1 << $frac_bits;
+
+            pub const ZERO: $fixed = $fixed(0);
+            pub const ONE: $fixed = $fixed(Self::ONE_RAW);
+            pub const HALF: $fixed = $fixed(Self::ONE_RAW / 2);
+            pub const TWO: $fixed = $fixed(Self::ONE_RAW * 2);
+            pub const MIN: $fixed = $fixed($raw::MIN);
+            pub const MAX: $fixed = $fixed($raw::MAX);
+            // smallest representable step
+            pub const EPSILON: $fixed = $fixed(1);
+
+            // evaluated at compile time -> identical on all machines
+            pub const PI: $fixed = $fixed((std::f64::consts::PI * (1_u64 << $frac_bits) as f64 + 0.5) as $raw);
+            pub const TAU: $fixed = $fixed(Self::PI.0 * 2);
+            pub const FRAC_PI_2: $fixed = $fixed(Self::PI.0 / 2);
+
+            const SIN_COS_TERMS: $raw = 10;
+            const ATAN_TERMS: $raw = 12;
+        }
+
+        impl Scalar for $fixed {
+            const ZERO: Self = $fixed::ZERO;
+            const ONE: Self = $fixed::ONE;
+        }
+
+        // creators
+        // --------
+        impl $fixed {
+            pub const fn from_raw(raw: $raw) -> Self {
+                Self(raw)
+            }
+
+            pub const fn from_int(val: $raw) -> Self {
+                Self(val << $frac_bits)
+            }
+
+            // numerator / denominator, e.g. from_ratio(1, 10) for 0.1
+            pub const fn from_ratio(numerator: $raw, denominator: $raw) -> Self {
+                Self((((numerator as $wide) << $frac_bits) / denominator as $wide) as $raw)
+            }
+
+            pub fn from_f32(val: f32) -> Self {
+                Self((val as f64 * Self::ONE_RAW as f64).round() as $raw)
+            }
+
+            pub fn from_f64(val: f64) -> Self {
+                Self((val * Self::ONE_RAW as f64).round() as $raw)
+            }
+        }
+
+        // conversions
+        // -----------
+        impl $fixed {
+            pub const fn to_raw(self) -> $raw {
+                self.0
+            }
+
+            // rounds towards negative infinity
+            pub const fn to_int(self) -> $raw {
+                self.0 >> $frac_bits
+            }
+
+            pub fn to_f32(self) -> f32 {
+                (self.0 as f64 / Self::ONE_RAW as f64) as f32
+            }
+
+            pub fn to_f64(self) -> f64 {
+                self.0 as f64 / Self::ONE_RAW as f64
+            }
+        }
+
+        impl fmt::Debug for $fixed {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", stringify!($fixed), self.to_f64())
+            }
+        }
+
+        impl fmt::Display for $fixed {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.to_f64())
+            }
+        }
+
+        // operators
+        // ---------
+        impl Add for $fixed {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self { Self(self.0.wrapping_add(rhs.0)) }
+        }
+
+        impl AddAssign for $fixed {
+            fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+        }
+
+        impl Sub for $fixed {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self { Self(self.0.wrapping_sub(rhs.0)) }
+        }
+
+        impl SubAssign for $fixed {
+            fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs; }
+        }
+
+        impl Mul for $fixed {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                // the wide product can't overflow, only the upper bits are dropped when narrowing it
+                Self(Self::mul_wide(self, rhs) as $raw)
+            }
+        }
+
+        impl MulAssign for $fixed {
+            fn mul_assign(&mut self, rhs: Self) { *self = *self * rhs; }
+        }
+
+        impl Div for $fixed {
+            type Output = Self;
+            // panics on division by zero, like integer division
+            fn div(self, rhs: Self) -> Self {
+                Self((((self.0 as $wide) << $frac_bits) / rhs.0 as $wide) as $raw)
+            }
+        }
+
+        impl DivAssign for $fixed {
+            fn div_assign(&mut self, rhs: Self) { *self = *self / rhs; }
+        }
+
+        impl Rem for $fixed {
+            type Output = Self;
+            fn rem(self, rhs: Self) -> Self { Self(self.0.wrapping_rem(rhs.0)) }
+        }
+
+        impl RemAssign for $fixed {
+            fn rem_assign(&mut self, rhs: Self) { *self = *self % rhs; }
+        }
+
+        impl Neg for $fixed {
+            type Output = Self;
+            fn neg(self) -> Self { Self(self.0.wrapping_neg()) }
+        }
+
+        // overflow handling
+        // -----------------
+        impl $fixed {
+            const fn mul_wide(self, rhs: Self) -> $wide {
+                (self.0 as $wide * rhs.0 as $wide) >> $frac_bits
+            }
+
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                Self(self.0.saturating_add(rhs.0))
+            }
+
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                Self(self.0.saturating_sub(rhs.0))
+            }
+
+            pub const fn saturating_mul(self, rhs: Self) -> Self {
+                let wide = Self::mul_wide(self, rhs);
+                if wide > $raw::MAX as $wide {
+                    Self::MAX
+                } else if wide < $raw::MIN as $wide {
+                    Self::MIN
+                } else {
+                    Self(wide as $raw)
+                }
+            }
+        }
+
+        // additional operations
+        // ---------------------
+        impl $fixed {
+            // wraps for 'MIN', like the operators
+            pub const fn abs(self) -> Self {
+                Self(self.0.wrapping_abs())
+            }
+
+            pub const fn signum(self) -> Self {
+                Self(self.0.signum() << $frac_bits)
+            }
+
+            pub const fn is_negative(self) -> bool {
+                self.0 < 0
+            }
+
+            pub const fn floor(self) -> Self {
+                Self(self.0 & !(Self::ONE_RAW - 1))
+            }
+
+            pub const fn ceil(self) -> Self {
+                Self(self.0.wrapping_add(Self::ONE_RAW - 1) & !(Self::ONE_RAW - 1))
+            }
+
+            pub const fn round(self) -> Self {
+                Self(self.0.wrapping_add(Self::HALF.0) & !(Self::ONE_RAW - 1))
+            }
+
+            pub const fn fract(self) -> Self {
+                Self(self.0 & (Self::ONE_RAW - 1))
+            }
+
+            pub fn recip(self) -> Self {
+                Self::ONE / self
+            }
+
+            pub fn lerp(self, end: Self, t: Self) -> Self {
+                self + (end - self) * t
+            }
+
+            // negative values return zero
+            pub fn sqrt(self) -> Self {
+                if self.0 <= 0 {
+                    return Self::ZERO;
+                }
+
+                // sqrt(raw * 2^frac) = sqrt(raw / 2^frac) * 2^frac
+                let mut val = (self.0 as $wide_unsigned) << $frac_bits;
+                let mut result: $wide_unsigned = 0;
+                let mut bit: $wide_unsigned = 1 << ((<$wide_unsigned>::BITS - 2) - (val.leading_zeros() & !1));
+
+                while bit != 0 {
+                    if val >= result + bit {
+                        val -= result + bit;
+                        result = (result >> 1) + bit;
+                    } else {
+                        result >>= 1;
+                    }
+                    bit >>= 2;
+                }
+
+                Self(result as $raw)
+            }
+        }
+
+        // trigonometry
+        // ------------
+        // taylor series in horner form, the coefficients only require integer divisions
+        impl $fixed {
+            // wraps the angle into [-pi, pi]
+            pub fn wrap_angle(self) -> Self {
+                let mut angle = self % Self::TAU;
+                if angle > Self::PI {
+                    angle -= Self::TAU;
+                } else if angle < -Self::PI {
+                    angle += Self::TAU;
+                }
+                angle
+            }
+
+            pub fn sin(self) -> Self {
+                let mut x = self.wrap_angle();
+
+                // sin(x) = sin(pi - x) -> x in [-pi/2, pi/2]
+                if x > Self::FRAC_PI_2 {
+                    x = Self::PI - x;
+                } else if x < -Self::FRAC_PI_2 {
+                    x = -Self::PI - x;
+                }
+
+                let x2 = x * x;
+                let mut term = Self::ONE;
+                for k in (1..=Self::SIN_COS_TERMS).rev() {
+                    let denom = Self::from_int((2 * k) * (2 * k + 1));
+                    term = Self::ONE - x2 * term / denom;
+                }
+
+                x * term
+            }
+
+            pub fn cos(self) -> Self {
+                (self + Self::FRAC_PI_2).sin()
+            }
+
+            pub fn sin_cos(self) -> (Self, Self) {
+                (self.sin(), self.cos())
+            }
+
+            pub fn tan(self) -> Self {
+                let (sin, cos) = self.sin_cos();
+                sin / cos
+            }
+
+            // |val| <= 1
+            fn atan_unit(val: Self) -> Self {
+                // atan(x) = 2 * atan(x / (1 + sqrt(1 + x^2))), applied twice -> |x| <= tan(pi/16)
+                let mut x = val;
+                for _ in 0..2 {
+                    x = x / (Self::ONE + (Self::ONE + x * x).sqrt());
+                }
+
+                let x2 = x * x;
+                let mut result = Self::ZERO;
+                let mut power = x;
+                for k in 0..Self::ATAN_TERMS {
+                    let term = power / Self::from_int(2 * k + 1);
+                    result = if k % 2 == 0 { result + term } else { result - term };
+                    power *= x2;
+                }
+
+                Self(result.0.wrapping_mul(4))
+            }
+
+            pub fn atan(self) -> Self {
+                if self.abs() <= Self::ONE {
+                    Self::atan_unit(self)
+                } else {
+                    // atan(x) = sign(x) * pi/2 - atan(1/x)
+                    let offset = if self.is_negative() { -Self::FRAC_PI_2 } else { Self::FRAC_PI_2 };
+                    offset - Self::atan_unit(Self::ONE / self)
+                }
+            }
+
+            // angle of the vector (x, y) in [-pi, pi]
+            pub fn atan2(self, x: Self) -> Self {
+                let y = self;
+
+                if x == Self::ZERO {
+                    return match y.0.signum() {
+                        1  => Self::FRAC_PI_2,
+                        -1 => -Self::FRAC_PI_2,
+                        _  => Self::ZERO,
+                    };
+                }
+
+                let angle = if y.abs() <= x.abs() {
+                    Self::atan_unit(y / x)
+                } else {
+                    let offset = if (y.0 < 0) != (x.0 < 0) { -Self::FRAC_PI_2 } else { Self::FRAC_PI_2 };
+                    offset - Self::atan_unit(x / y)
+                };
+
+                if x.0 > 0 {
+                    angle
+                } else if y.0 >= 0 {
+                    angle + Self::PI
+                } else {
+                    angle - Self::PI
+                }
+            }
+        }
+    };
+}
+
+impl_fixed!(Fixed32: i32, i64, u64 => 16);
+impl_fixed!(Fixed64: i64, i128, u128 => 32);
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the raw values are part of the determinism guarantee, changing any of them breaks lockstep replays
+
+    #[test]
+    fn overflow_wraps() {
+        assert_eq!(Fixed32::MAX + Fixed32::EPSILON, Fixed32::MIN);
+        assert_eq!(Fixed32::MIN - Fixed32::EPSILON, Fixed32::MAX);
+        assert_eq!(-Fixed32::MIN, Fixed32::MIN);
+        assert_eq!((Fixed32::from_int(300) * Fixed32::from_int(300)).to_raw(), 1_603_272_704);
+    }
+
+    #[test]
+    fn saturating_ops() {
+        assert_eq!(Fixed32::MAX.saturating_add(Fixed32::ONE), Fixed32::MAX);
+        assert_eq!(Fixed32::MIN.saturating_sub(Fixed32::ONE), Fixed32::MIN);
+        assert_eq!(Fixed32::from_int(300).saturating_mul(Fixed32::from_int(300)), Fixed32::MAX);
+        assert_eq!(Fixed32::from_int(-300).saturating_mul(Fixed32::from_int(300)), Fixed32::MIN);
+        assert_eq!(Fixed32::from_int(3).saturating_mul(Fixed32::HALF), Fixed32::from_ratio(3, 2));
+    }
+
+    #[test]
+    fn sqrt_raw() {
+        assert_eq!(Fixed32::from_int(2).sqrt().to_raw(), 92_681);
+        assert_eq!(Fixed32::from_int(10).sqrt().to_raw(), 207_243);
+        assert_eq!(Fixed32::from_int(1000).sqrt().to_raw(), 2_072_430);
+        assert_eq!(Fixed32::HALF.sqrt().to_raw(), 46_340);
+        assert_eq!(Fixed32::from_int(-4).sqrt(), Fixed32::ZERO);
+        assert_eq!(Fixed64::from_int(2).sqrt().to_raw(), 6_074_000_999);
+    }
+
+    #[test]
+    fn sin_cos_raw() {
+        let cases = [
+            ((1, 1),   55_147,  35_409),
+            ((1, 2),   31_420,  57_513),
+            ((3, 1),    9_248, -64_880),
+            ((-2, 1), -59_592, -27_274),
+            ((10, 1), -35_655, -54_990),
+        ];
+
+        for ((num, denom), sin, cos) in cases {
+            let x = Fixed32::from_ratio(num, denom);
+            assert_eq!(x.sin().to_raw(), sin, "sin({}/{})", num, denom);
+            assert_eq!(x.cos().to_raw(), cos, "cos({}/{})", num, denom);
+        }
+
+        assert_eq!(Fixed64::ONE.sin().to_raw(), 3_614_090_361);
+        assert_eq!(Fixed64::ONE.cos().to_raw(), 2_320_580_734);
+    }
+
+    #[test]
+    fn atan2_raw() {
+        let cases = [
+            ((1, 1),     51_476),
+            ((1, -1),   154_415),
+            ((-1, -1), -154_411),
+            ((-1, 1),   -51_472),
+            ((3, 4),     42_168),
+            ((0, -1),   205_887),
+        ];
+
+        for ((y, x), expected) in cases {
+            assert_eq!(Fixed32::from_int(y).atan2(Fixed32::from_int(x)).to_raw(), expected, "atan2({}, {})", y, x);
+        }
+
+        assert_eq!(Fixed64::from_int(3).atan2(Fixed64::from_int(4)).to_raw(), 2_763_816_220);
+    }
+
+    #[test]
+    fn trig_is_close_to_float() {
+        for i in -40..=40 {
+            let x = Fixed32::from_ratio(i, 8);
+            let f = x.to_f64();
+            assert!((x.sin().to_f64() - f.sin()).abs() < 1e-3, "sin({})", f);
+            assert!((x.cos().to_f64() - f.cos()).abs() < 1e-3, "cos({})", f);
+            assert!((x.atan2(Fixed32::ONE).to_f64() - f.atan2(1.0)).abs() < 1e-3, "atan2({}, 1)", f);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::vec::{Vec3, Vec4};
+use crate::mat::Mat4;
+
+use super::Sphere;
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb3 {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb3 {
+    pub const fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_center_half_extents(center: &Vec3, half_extents: &Vec3) -> Self {
+        Self::new(*center - *half_extents, *center + *half_extents)
+    }
+
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        let (first, rest) = points.split_first()?;
+
+        let result = rest.iter().fold(Self::new(*first, *first), |mut aabb, p| {
+            aabb.expand_to_include(p);
+            aabb
+        });
+
+        Some(result)
+    }
+}
+
+impl Aabb3 {
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    pub fn contains_point(&self, point: &Vec3) -> bool {
+        (0..3).all(|i| point[i] >= self.min[i] && point[i] <= self.max[i])
+    }
+
+    pub fn intersects_aabb(&self, other: &Aabb3) -> bool {
+        (0..3).all(|i| self.min[i] <= other.max[i] && self.max[i] >= other.min[i])
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        sphere.intersects_aabb(self)
+    }
+
+    pub fn closest_point(&self, point: &Vec3) -> Vec3 {
+        point.clamp(self.min, self.max)
+    }
+
+    pub fn union(&self, other: &Aabb3) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    pub fn expand_to_include(&mut self, point: &Vec3) {
+        self.min = self.min.min(*point);
+        self.max = self.max.max(*point);
+    }
+
+    // returns the aabb that encloses the transformed box
+    pub fn transform(&self, mat: &Mat4) -> Self {
+        let center = self.center();
+        let half = self.half_extents();
+
+        let center = mat.mul_vec(&Vec4::new(center.x, center.y, center.z, 1.0));
+        let half = Vec3::new(
+            mat.x.x.abs() * half.x + mat.y.x.abs() * half.y + mat.z.x.abs() * half.z,
+            mat.x.y.abs() * half.x + mat.y.y.abs() * half.y + mat.z.y.abs() * half.z,
+            mat.x.z.abs() * half.x + mat.y.z.abs() * half.y + mat.z.z.abs() * half.z,
+        );
+
+        Self::from_center_half_extents(&Vec3::new(center.x, center.y, center.z), &half)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_points() {
+        assert_eq!(Aabb3::from_points(&[]), None);
+
+        let aabb = Aabb3::from_points(&[Vec3::new(1.0, -2.0, 0.0), Vec3::new(-1.0, 3.0, 0.5), Vec3::new(0.0, 0.0, -4.0)]).unwrap();
+        assert_eq!(aabb, Aabb3::new(Vec3::new(-1.0, -2.0, -4.0), Vec3::new(1.0, 3.0, 0.5)));
+        assert_eq!(aabb.center(), Vec3::new(0.0, 0.5, -1.75));
+        assert_eq!(aabb.half_extents(), Vec3::new(1.0, 2.5, 2.25));
+    }
+
+    #[test]
+    fn closest_point() {
+        let aabb = Aabb3::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+        assert_eq!(aabb.closest_point(&Vec3::new(3.0, 0.5, -4.0)), Vec3::new(1.0, 0.5, -1.0));
+        assert_eq!(aabb.closest_point(&Vec3::new(0.2, 0.3, 0.4)), Vec3::new(0.2, 0.3, 0.4));
+    }
+
+    #[test]
+    fn intersections() {
+        let aabb = Aabb3::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 2.0, 2.0));
+        assert!(aabb.intersects_aabb(&Aabb3::new(Vec3::new(2.0, 1.0, 1.0), Vec3::new(3.0, 3.0, 3.0))));
+        assert!(!aabb.intersects_aabb(&Aabb3::new(Vec3::new(2.1, 1.0, 1.0), Vec3::new(3.0, 3.0, 3.0))));
+        assert!(aabb.intersects_sphere(&Sphere::new(Vec3::new(3.0, 1.0, 1.0), 1.0)));
+        assert!(!aabb.intersects_sphere(&Sphere::new(Vec3::new(3.0, 3.0, 1.0), 1.4)));
+    }
+
+    #[test]
+    fn transform() {
+        let aabb = Aabb3::new(Vec3::new(-1.0, -2.0, -3.0), Vec3::new(1.0, 2.0, 3.0));
+
+        // 90 degrees around z swaps the x and y extents, then moves the box
+        let mat = Mat4::from_cols(
+            Vec4::new(0.0, 1.0, 0.0, 0.0),
+            Vec4::new(-1.0, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, 1.0, 0.0),
+            Vec4::new(5.0, 0.0, 0.0, 1.0),
+        );
+        assert_eq!(aabb.transform(&mat), Aabb3::new(Vec3::new(3.0, -1.0, -3.0), Vec3::new(7.0, 1.0, 3.0)));
+    }
+}
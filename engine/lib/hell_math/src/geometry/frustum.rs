@@ -0,0 +1,175 @@
+use crate::vec::{Vec3, Vec4};
+use crate::mat::Mat4;
+use crate::projection::{ClipSpace, DepthRange};
+
+use super::{Plane, Sphere, Aabb3};
+
+
+
+
+// all plane normals point into the frustum
+// the planes are labeled in view-space: 'TOP' is the plane at the top of the screen, 'NEAR' the one closest to the camera
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    pub const LEFT: usize   = 0;
+    pub const RIGHT: usize  = 1;
+    pub const BOTTOM: usize = 2;
+    pub const TOP: usize    = 3;
+    pub const NEAR: usize   = 4;
+    pub const FAR: usize    = 5;
+}
+
+impl Frustum {
+    pub const fn new(planes: [Plane; 6]) -> Self {
+        Self { planes }
+    }
+
+    // https://www.gamedevs.org/uploads/fast-extraction-viewing-frustum-planes-from-world-view-projection-matrix.pdf
+    // 'clip' has to be the clip-space the projection was built for, it decides which planes are top / bottom and near / far
+    // infinite projections have no far plane, it is replaced by a plane that contains everything
+    pub fn from_view_proj(view_proj: &Mat4, clip: ClipSpace) -> Self {
+        let r0 = view_proj.row(0);
+        let r1 = view_proj.row(1);
+        let r2 = view_proj.row(2);
+        let r3 = view_proj.row(3);
+
+        // -w <= y <= w
+        let (y_min, y_max) = (r3 + r1, r3 - r1);
+        let (bottom, top) = if clip.y_down { (y_max, y_min) } else { (y_min, y_max) };
+
+        // 0 <= z <= w or -w <= z <= w
+        let z_min = match clip.depth {
+            DepthRange::ZeroToOne   => r2,
+            DepthRange::NegOneToOne => r3 + r2,
+        };
+        let z_max = r3 - r2;
+        let (near, far) = if clip.reverse_z { (z_max, z_min) } else { (z_min, z_max) };
+
+        Self::new([
+            Self::plane(r3 + r0),
+            Self::plane(r3 - r0),
+            Self::plane(bottom),
+            Self::plane(top),
+            Self::plane(near),
+            Self::plane(far),
+        ])
+    }
+
+    fn plane(val: Vec4) -> Plane {
+        if Vec3::new(val.x, val.y, val.z).mag_squared() <= f32::EPSILON * val.w.abs() {
+            return Plane::new(Vec3::zero(), f32::INFINITY);
+        }
+
+        Plane::from_vec4(&val)
+    }
+}
+
+impl Frustum {
+    pub fn contains_point(&self, point: &Vec3) -> bool {
+        self.planes.iter().all(|p| p.signed_distance(point) >= 0.0)
+    }
+
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes.iter().all(|p| p.signed_distance(&sphere.center) >= -sphere.radius)
+    }
+
+    // conservative: may report boxes near the frustum corners as visible
+    pub fn intersects_aabb(&self, aabb: &Aabb3) -> bool {
+        self.planes.iter().all(|p| {
+            // corner of the box that lies furthest along the plane normal
+            let positive = Vec3::new(
+                if p.normal.x >= 0.0 { aabb.max.x } else { aabb.min.x },
+                if p.normal.y >= 0.0 { aabb.max.y } else { aabb.min.y },
+                if p.normal.z >= 0.0 { aabb.max.z } else { aabb.min.z },
+            );
+
+            p.signed_distance(&positive) >= 0.0
+        })
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEAR: f32 = 1.0;
+    const FAR: f32 = 10.0;
+
+    fn assert_vec_eq(lhs: Vec3, rhs: Vec3) {
+        assert!(lhs.distance(rhs) < 1e-5, "{:?} != {:?}", lhs, rhs);
+    }
+
+    // camera at (0, 0, -2) looking along +z with a 90 degree field of view, so the side planes are at 45 degrees
+    fn frustum(clip: ClipSpace) -> Frustum {
+        let view = Mat4::look_to_lh(&Vec3::new(0.0, 0.0, -2.0), &Vec3::new(0.0, 0.0, 1.0), &Vec3::new(0.0, 1.0, 0.0));
+        let proj = Mat4::perspective_lh(90_f32.to_radians(), 1.0, NEAR, FAR, clip);
+        Frustum::from_view_proj(&proj.mul_mat(&view), clip)
+    }
+
+    #[test]
+    fn plane_labels() {
+        let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+
+        for clip in [ClipSpace::VULKAN, ClipSpace::VULKAN_REVERSE_Z, ClipSpace::DIRECTX, ClipSpace::OPENGL, ClipSpace::OPENGL.with_reverse_z(true)] {
+            let frustum = frustum(clip);
+            let normal = |idx: usize| frustum.planes[idx].normal;
+
+            assert_vec_eq(normal(Frustum::LEFT),   Vec3::new( diagonal, 0.0, diagonal));
+            assert_vec_eq(normal(Frustum::RIGHT),  Vec3::new(-diagonal, 0.0, diagonal));
+            assert_vec_eq(normal(Frustum::BOTTOM), Vec3::new(0.0,  diagonal, diagonal));
+            assert_vec_eq(normal(Frustum::TOP),    Vec3::new(0.0, -diagonal, diagonal));
+            assert_vec_eq(normal(Frustum::NEAR),   Vec3::new(0.0, 0.0,  1.0));
+            assert_vec_eq(normal(Frustum::FAR),    Vec3::new(0.0, 0.0, -1.0));
+
+            // the near plane is at z = -1, the far plane at z = 8
+            assert!((frustum.planes[Frustum::NEAR].d - 1.0).abs() < 1e-4, "{:?}", clip);
+            assert!((frustum.planes[Frustum::FAR].d - 8.0).abs() < 1e-3, "{:?}", clip);
+        }
+    }
+
+    #[test]
+    fn contains_point() {
+        for clip in [ClipSpace::VULKAN, ClipSpace::VULKAN_REVERSE_Z, ClipSpace::OPENGL] {
+            let frustum = frustum(clip);
+            assert!(frustum.contains_point(&Vec3::new(0.0, 0.0, 0.0)));
+            assert!(frustum.contains_point(&Vec3::new(2.9, -2.9, 1.0)));
+            assert!(!frustum.contains_point(&Vec3::new(3.1, 0.0, 1.0)));
+            assert!(!frustum.contains_point(&Vec3::new(0.0, 0.0, -1.5)));
+            assert!(!frustum.contains_point(&Vec3::new(0.0, 0.0, 8.5)));
+        }
+    }
+
+    #[test]
+    fn culling() {
+        let frustum = frustum(ClipSpace::VULKAN);
+
+        // the left plane passes through (-3, 0, 1), the sphere center is 1 / sqrt(2) units outside of it
+        assert!(frustum.intersects_sphere(&Sphere::new(Vec3::new(-4.0, 0.0, 1.0), 0.75)));
+        assert!(!frustum.intersects_sphere(&Sphere::new(Vec3::new(-4.0, 0.0, 1.0), 0.65)));
+        assert!(!frustum.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, -5.0), 2.0)));
+        assert!(frustum.intersects_sphere(&Sphere::new(Vec3::new(0.0, 0.0, 9.0), 1.5)));
+
+        assert!(frustum.intersects_aabb(&Aabb3::new(Vec3::new(-1.0, -1.0, 2.0), Vec3::new(1.0, 1.0, 3.0))));
+        assert!(frustum.intersects_aabb(&Aabb3::new(Vec3::new(2.5, 2.5, 0.0), Vec3::new(5.0, 5.0, 1.0))));
+        assert!(!frustum.intersects_aabb(&Aabb3::new(Vec3::new(4.5, -1.0, 0.0), Vec3::new(6.0, 1.0, 1.0))));
+        assert!(!frustum.intersects_aabb(&Aabb3::new(Vec3::new(-1.0, -1.0, 8.5), Vec3::new(1.0, 1.0, 9.0))));
+    }
+
+    #[test]
+    fn infinite_projection() {
+        for clip in [ClipSpace::VULKAN, ClipSpace::VULKAN_REVERSE_Z] {
+            let proj = Mat4::perspective_infinite_lh(90_f32.to_radians(), 1.0, NEAR, clip);
+            let frustum = Frustum::from_view_proj(&proj, clip);
+
+            assert!(frustum.contains_point(&Vec3::new(0.0, 0.0, 1.0e6)));
+            assert!(!frustum.contains_point(&Vec3::new(0.0, 0.0, 0.5)));
+            assert!(frustum.intersects_aabb(&Aabb3::new(Vec3::new(-1.0, -1.0, 1.0e5), Vec3::new(1.0, 1.0, 1.0e6))));
+        }
+    }
+}
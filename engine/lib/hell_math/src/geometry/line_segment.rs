@@ -0,0 +1,134 @@
+use crate::vec::Vec3;
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineSegment {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl LineSegment {
+    pub const fn new(start: Vec3, end: Vec3) -> Self {
+        Self { start, end }
+    }
+}
+
+impl LineSegment {
+    pub fn direction(&self) -> Vec3 {
+        self.end - self.start
+    }
+
+    pub fn length(&self) -> f32 {
+        self.direction().mag()
+    }
+
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.start + self.direction() * t
+    }
+
+    // t in [0, 1] of the point on the segment, that is closest to 'point'
+    pub fn closest_t(&self, point: &Vec3) -> f32 {
+        let dir = self.direction();
+        let len_sq = dir.mag_squared();
+
+        if len_sq < f32::EPSILON {
+            return 0.0;
+        }
+
+        ((*point - self.start).dot(dir) / len_sq).clamp(0.0, 1.0)
+    }
+
+    pub fn closest_point(&self, point: &Vec3) -> Vec3 {
+        self.at(self.closest_t(point))
+    }
+
+    pub fn distance_to_point(&self, point: &Vec3) -> f32 {
+        self.closest_point(point).distance(*point)
+    }
+
+    // Real-Time Collision Detection (Ericson), 5.1.9
+    // returns the closest points on (self, other)
+    pub fn closest_points(&self, other: &LineSegment) -> (Vec3, Vec3) {
+        let d1 = self.direction();
+        let d2 = other.direction();
+        let r = self.start - other.start;
+        let a = d1.mag_squared();
+        let e = d2.mag_squared();
+        let f = d2.dot(r);
+
+        let (s, t) = if a < f32::EPSILON && e < f32::EPSILON {
+            // both segments degenerate into points
+            (0.0, 0.0)
+        } else if a < f32::EPSILON {
+            (0.0, (f / e).clamp(0.0, 1.0))
+        } else {
+            let c = d1.dot(r);
+
+            if e < f32::EPSILON {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else {
+                let b = d1.dot(d2);
+                let denom = a * e - b * b;
+
+                // segments are not parallel
+                let s = if denom != 0.0 { ((b * f - c * e) / denom).clamp(0.0, 1.0) } else { 0.0 };
+                let t = (b * s + f) / e;
+
+                if t < 0.0 {
+                    ((-c / a).clamp(0.0, 1.0), 0.0)
+                } else if t > 1.0 {
+                    (((b - c) / a).clamp(0.0, 1.0), 1.0)
+                } else {
+                    (s, t)
+                }
+            }
+        };
+
+        (self.at(s), other.at(t))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec_eq(lhs: Vec3, rhs: Vec3) {
+        assert!(lhs.distance(rhs) < 1e-5, "{:?} != {:?}", lhs, rhs);
+    }
+
+    #[test]
+    fn closest_point() {
+        let segment = LineSegment::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(4.0, 0.0, 0.0));
+        assert_eq!(segment.closest_t(&Vec3::new(1.0, 2.0, 0.0)), 0.25);
+        assert_vec_eq(segment.closest_point(&Vec3::new(6.0, 1.0, 0.0)), Vec3::new(4.0, 0.0, 0.0));
+        assert!((segment.distance_to_point(&Vec3::new(-3.0, 4.0, 0.0)) - 5.0).abs() < 1e-6);
+
+        let point = LineSegment::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(1.0, 1.0, 1.0));
+        assert_eq!(point.closest_t(&Vec3::new(5.0, 0.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn closest_points() {
+        // crossing segments, one above the other
+        let a = LineSegment::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        let b = LineSegment::new(Vec3::new(0.5, 2.0, -1.0), Vec3::new(0.5, 2.0, 1.0));
+        let (pa, pb) = a.closest_points(&b);
+        assert_vec_eq(pa, Vec3::new(0.5, 0.0, 0.0));
+        assert_vec_eq(pb, Vec3::new(0.5, 2.0, 0.0));
+
+        // the closest point of 'b' is clamped to its start
+        let b = LineSegment::new(Vec3::new(3.0, 1.0, 0.0), Vec3::new(5.0, 1.0, 0.0));
+        let (pa, pb) = a.closest_points(&b);
+        assert_vec_eq(pa, Vec3::new(1.0, 0.0, 0.0));
+        assert_vec_eq(pb, Vec3::new(3.0, 1.0, 0.0));
+
+        // parallel segments
+        let b = LineSegment::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(2.0, 1.0, 0.0));
+        let (pa, pb) = a.closest_points(&b);
+        assert!((pa.distance(pb) - 1.0).abs() < 1e-6);
+    }
+}
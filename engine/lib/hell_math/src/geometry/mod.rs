@@ -0,0 +1,20 @@
+mod ray;
+pub use ray::*;
+
+mod plane;
+pub use plane::*;
+
+mod sphere;
+pub use sphere::*;
+
+mod aabb;
+pub use aabb::*;
+
+mod frustum;
+pub use frustum::*;
+
+mod line_segment;
+pub use line_segment::*;
+
+mod rect;
+pub use rect::*;
@@ -0,0 +1,87 @@
+use crate::vec::{Vec3, Vec4};
+
+
+
+
+// all points p with: normal.dot(p) + d = 0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub d: f32,
+}
+
+impl Plane {
+    pub const fn new(normal: Vec3, d: f32) -> Self {
+        Self { normal, d }
+    }
+
+    pub fn from_point_normal(point: &Vec3, normal: &Vec3) -> Self {
+        let normal = normal.unit();
+        Self::new(normal, -normal.dot(*point))
+    }
+
+    // counter-clockwise winding defines the front side
+    pub fn from_points(a: &Vec3, b: &Vec3, c: &Vec3) -> Self {
+        let normal = (*b - *a).cross(&(*c - *a));
+        Self::from_point_normal(a, &normal)
+    }
+
+    // (x, y, z) = normal, w = d - the result is normalized
+    pub fn from_vec4(val: &Vec4) -> Self {
+        Self::new(Vec3::new(val.x, val.y, val.z), val.w).normalize()
+    }
+
+    pub fn normalize(&self) -> Self {
+        let rcp_mag = self.normal.mag().recip();
+        Self::new(self.normal * rcp_mag, self.d * rcp_mag)
+    }
+}
+
+impl Plane {
+    // positive in front of the plane, negative behind it
+    pub fn signed_distance(&self, point: &Vec3) -> f32 {
+        self.normal.dot(*point) + self.d
+    }
+
+    pub fn closest_point(&self, point: &Vec3) -> Vec3 {
+        *point - self.normal * self.signed_distance(point)
+    }
+
+    pub fn is_in_front(&self, point: &Vec3) -> bool {
+        self.signed_distance(point) >= 0.0
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec_eq(lhs: Vec3, rhs: Vec3) {
+        assert!(lhs.distance(rhs) < 1e-5, "{:?} != {:?}", lhs, rhs);
+    }
+
+    #[test]
+    fn construction() {
+        // (b - a) x (c - a) = (0, 0, 1) x (1, 0, 0)
+        let plane = Plane::from_points(&Vec3::new(0.0, 1.0, 0.0), &Vec3::new(0.0, 1.0, 1.0), &Vec3::new(1.0, 1.0, 0.0));
+        assert_vec_eq(plane.normal, Vec3::new(0.0, 1.0, 0.0));
+        assert!((plane.d - -1.0).abs() < 1e-6);
+
+        let plane = Plane::from_vec4(&Vec4::new(0.0, 0.0, 2.0, -4.0));
+        assert_vec_eq(plane.normal, Vec3::new(0.0, 0.0, 1.0));
+        assert!((plane.d - -2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn distance_and_closest_point() {
+        let plane = Plane::from_point_normal(&Vec3::new(1.0, 1.0, 1.0), &Vec3::new(1.0, 1.0, 0.0));
+        let point = Vec3::new(3.0, 3.0, 5.0);
+
+        assert!((plane.signed_distance(&point) - 8_f32.sqrt()).abs() < 1e-5);
+        assert!(plane.is_in_front(&point));
+        assert!(!plane.is_in_front(&Vec3::new(0.0, 0.0, 0.0)));
+        assert_vec_eq(plane.closest_point(&point), Vec3::new(1.0, 1.0, 5.0));
+    }
+}
@@ -0,0 +1,182 @@
+use crate::vec::Vec3;
+
+use super::{Plane, Sphere, Aabb3};
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    // always normalized
+    pub direction: Vec3,
+}
+
+impl Ray {
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.unit(),
+        }
+    }
+
+    pub fn from_points(start: Vec3, target: Vec3) -> Self {
+        Self::new(start, target - start)
+    }
+
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+}
+
+// closest point
+// -------------
+impl Ray {
+    pub fn closest_point(&self, point: &Vec3) -> Vec3 {
+        let t = (*point - self.origin).dot(self.direction).max(0.0);
+        self.at(t)
+    }
+
+    pub fn distance_to_point(&self, point: &Vec3) -> f32 {
+        self.closest_point(point).distance(*point)
+    }
+}
+
+// intersections
+// -------------
+// all intersections return the distance along the ray to the first hit
+impl Ray {
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+        let denom = plane.normal.dot(self.direction);
+
+        // ray is parallel to the plane
+        if denom.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let t = -plane.signed_distance(&self.origin) / denom;
+        (t >= 0.0).then_some(t)
+    }
+
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<f32> {
+        let to_origin = self.origin - sphere.center;
+        let b = to_origin.dot(self.direction);
+        let c = to_origin.mag_squared() - sphere.radius * sphere.radius;
+
+        // origin is outside of the sphere and the ray points away from it
+        if c > 0.0 && b > 0.0 {
+            return None;
+        }
+
+        let discriminant = b * b - c;
+        if discriminant < 0.0 {
+            return None;
+        }
+
+        // a negative t means that the origin lies inside the sphere
+        let t = -b - discriminant.sqrt();
+        Some(t.max(0.0))
+    }
+
+    // slab test
+    pub fn intersect_aabb(&self, aabb: &Aabb3) -> Option<f32> {
+        let mut t_min = 0.0_f32;
+        let mut t_max = f32::INFINITY;
+
+        for i in 0..3 {
+            let origin = self.origin[i];
+            let dir = self.direction[i];
+
+            if dir.abs() < f32::EPSILON {
+                if origin < aabb.min[i] || origin > aabb.max[i] {
+                    return None;
+                }
+                continue;
+            }
+
+            let rcp_dir = dir.recip();
+            let t0 = (aabb.min[i] - origin) * rcp_dir;
+            let t1 = (aabb.max[i] - origin) * rcp_dir;
+            let (t0, t1) = if t0 > t1 { (t1, t0) } else { (t0, t1) };
+
+            t_min = t_min.max(t0);
+            t_max = t_max.min(t1);
+
+            if t_min > t_max {
+                return None;
+            }
+        }
+
+        Some(t_min)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec_eq(lhs: Vec3, rhs: Vec3) {
+        assert!(lhs.distance(rhs) < 1e-5, "{:?} != {:?}", lhs, rhs);
+    }
+
+    #[test]
+    fn closest_point() {
+        let ray = Ray::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0));
+        assert_vec_eq(ray.direction, Vec3::new(1.0, 0.0, 0.0));
+        assert_vec_eq(ray.closest_point(&Vec3::new(4.0, 3.0, 0.0)), Vec3::new(4.0, 0.0, 0.0));
+        assert!((ray.distance_to_point(&Vec3::new(4.0, 3.0, 0.0)) - 3.0).abs() < 1e-6);
+
+        // points behind the origin are closest to the origin
+        assert_vec_eq(ray.closest_point(&Vec3::new(-2.0, 1.0, 0.0)), Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn plane() {
+        let plane = Plane::from_point_normal(&Vec3::new(0.0, 2.0, 0.0), &Vec3::new(0.0, 1.0, 0.0));
+        let ray = Ray::from_points(Vec3::new(0.0, 5.0, 0.0), Vec3::new(3.0, 1.0, 0.0));
+
+        // the ray moves down 4 units per 5 units of distance
+        let t = ray.intersect_plane(&plane).unwrap();
+        assert!((t - 3.75).abs() < 1e-5);
+        assert_vec_eq(ray.at(t), Vec3::new(2.25, 2.0, 0.0));
+
+        assert_eq!(Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(1.0, 0.0, 0.0)).intersect_plane(&plane), None);
+        assert_eq!(Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, 1.0, 0.0)).intersect_plane(&plane), None);
+    }
+
+    #[test]
+    fn sphere() {
+        let sphere = Sphere::new(Vec3::new(5.0, 0.0, 0.0), 2.0);
+
+        let hit = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!((hit.intersect_sphere(&sphere).unwrap() - 3.0).abs() < 1e-5);
+
+        // (x - 5)^2 + 1^2 = 2^2
+        let offset = Ray::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!((offset.intersect_sphere(&sphere).unwrap() - (5.0 - 3_f32.sqrt())).abs() < 1e-5);
+
+        assert_eq!(Ray::new(Vec3::new(0.0, 2.5, 0.0), Vec3::new(1.0, 0.0, 0.0)).intersect_sphere(&sphere), None);
+        assert_eq!(Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0)).intersect_sphere(&sphere), None);
+
+        // starting inside the sphere hits immediately
+        assert_eq!(Ray::new(Vec3::new(5.5, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)).intersect_sphere(&sphere), Some(0.0));
+    }
+
+    #[test]
+    fn aabb() {
+        let aabb = Aabb3::new(Vec3::new(1.0, -1.0, -1.0), Vec3::new(3.0, 1.0, 1.0));
+
+        let hit = Ray::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+        assert!((hit.intersect_aabb(&aabb).unwrap() - 2.0).abs() < 1e-5);
+
+        // enters through the top face at (2, 1, 0)
+        let diagonal = Ray::from_points(Vec3::new(0.0, 3.0, 0.0), Vec3::new(2.0, 1.0, 0.0));
+        assert!((diagonal.intersect_aabb(&aabb).unwrap() - 8_f32.sqrt()).abs() < 1e-5);
+
+        assert_eq!(Ray::new(Vec3::new(-1.0, 2.0, 0.0), Vec3::new(1.0, 0.0, 0.0)).intersect_aabb(&aabb), None);
+        assert_eq!(Ray::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0)).intersect_aabb(&aabb), None);
+        assert_eq!(Ray::new(Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0)).intersect_aabb(&aabb), Some(0.0));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::vec::{Vec2, IVec2};
+
+
+
+
+// axis aligned rectangle, 'min' is the top left corner
+// the rect covers the half-open range [min, max) - a rect with max <= min on any axis is empty
+macro_rules! impl_rect {
+    ($rect:ident : $vec:ident : $scalar:ident, two: $two:literal) => {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $rect {
+            pub min: $vec,
+            pub max: $vec,
+        }
+
+        // creators
+        // --------
+        impl $rect {
+            pub const fn new(min: $vec, max: $vec) -> Self {
+                Self { min, max }
+            }
+
+            pub fn from_pos_size(pos: $vec, size: $vec) -> Self {
+                Self::new(pos, pos + size)
+            }
+
+            pub fn from_xywh(x: $scalar, y: $scalar, width: $scalar, height: $scalar) -> Self {
+                Self::from_pos_size($vec::new(x, y), $vec::new(width, height))
+            }
+
+            pub fn from_center_size(center: $vec, size: $vec) -> Self {
+                let half = size / $two;
+                Self::new(center - half, center - half + size)
+            }
+
+            // smallest rect that contains both points
+            pub fn from_corners(a: $vec, b: $vec) -> Self {
+                Self::new(a.min(b), a.max(b))
+            }
+        }
+
+        // properties
+        // ----------
+        impl $rect {
+            pub fn width(&self) -> $scalar {
+                self.max.x - self.min.x
+            }
+
+            pub fn height(&self) -> $scalar {
+                self.max.y - self.min.y
+            }
+
+            pub fn size(&self) -> $vec {
+                self.max - self.min
+            }
+
+            pub fn area(&self) -> $scalar {
+                if self.is_empty() { return $vec::SCALAR_ZERO; }
+                self.width() * self.height()
+            }
+
+            pub fn center(&self) -> $vec {
+                self.min + self.size() / $two
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.max.x <= self.min.x || self.max.y <= self.min.y
+            }
+        }
+
+        // queries
+        // -------
+        impl $rect {
+            pub fn contains_point(&self, point: &$vec) -> bool {
+                point.x >= self.min.x && point.x < self.max.x &&
+                point.y >= self.min.y && point.y < self.max.y
+            }
+
+            pub fn contains_rect(&self, other: &$rect) -> bool {
+                other.min.x >= self.min.x && other.max.x <= self.max.x &&
+                other.min.y >= self.min.y && other.max.y <= self.max.y
+            }
+
+            pub fn intersects(&self, other: &$rect) -> bool {
+                self.intersection(other).is_some()
+            }
+
+            // returns None if the overlap is empty
+            pub fn intersection(&self, other: &$rect) -> Option<Self> {
+                let result = Self::new(self.min.max(other.min), self.max.min(other.max));
+                if result.is_empty() { None } else { Some(result) }
+            }
+
+            pub fn union(&self, other: &$rect) -> Self {
+                if self.is_empty()  { return *other; }
+                if other.is_empty() { return *self; }
+                Self::new(self.min.min(other.min), self.max.max(other.max))
+            }
+
+            // clamps to the closed range [min, max], unlike 'contains_point' the max edge is included
+            // e.g. clamping a cursor to a 800x600 'IRect' can return x = 800, which is not a valid pixel
+            pub fn clamp_point(&self, point: &$vec) -> $vec {
+                point.clamp(self.min, self.max)
+            }
+        }
+
+        // layout
+        // ------
+        impl $rect {
+            pub fn translate(&self, offset: $vec) -> Self {
+                Self::new(self.min + offset, self.max + offset)
+            }
+
+            // shrinks the rect by 'amount' on every side - negative values grow it
+            pub fn inset(&self, amount: $scalar) -> Self {
+                self.inset_sides(amount, amount, amount, amount)
+            }
+
+            pub fn inset_sides(&self, left: $scalar, top: $scalar, right: $scalar, bottom: $scalar) -> Self {
+                Self::new(
+                    $vec::new(self.min.x + left, self.min.y + top),
+                    $vec::new(self.max.x - right, self.max.y - bottom),
+                )
+            }
+
+            // splits into a left and a right part, 'offset' is measured from min.x and clamped to the rect
+            pub fn split_x(&self, offset: $scalar) -> (Self, Self) {
+                let split = (self.min.x + offset).clamp(self.min.x, self.max.x.max(self.min.x));
+                (
+                    Self::new(self.min, $vec::new(split, self.max.y)),
+                    Self::new($vec::new(split, self.min.y), self.max),
+                )
+            }
+
+            // splits into a top and a bottom part, 'offset' is measured from min.y and clamped to the rect
+            pub fn split_y(&self, offset: $scalar) -> (Self, Self) {
+                let split = (self.min.y + offset).clamp(self.min.y, self.max.y.max(self.min.y));
+                (
+                    Self::new(self.min, $vec::new(self.max.x, split)),
+                    Self::new($vec::new(self.min.x, split), self.max),
+                )
+            }
+        }
+    };
+}
+
+impl_rect!(Rect: Vec2: f32, two: 2.0);
+impl_rect!(IRect: IVec2: i32, two: 2);
+
+// conversions
+// -----------
+impl Rect {
+    // smallest integer rect that covers this rect
+    pub fn round_out(&self) -> IRect {
+        IRect::new(self.min.floor_to_int(), self.max.ceil_to_int())
+    }
+}
+
+impl From<IRect> for Rect {
+    fn from(val: IRect) -> Self {
+        Self::new(val.min.as_vec2(), val.max.as_vec2())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_point_is_half_open() {
+        let rect = IRect::from_xywh(0, 0, 800, 600);
+        assert!(rect.contains_point(&IVec2::new(0, 0)));
+        assert!(rect.contains_point(&IVec2::new(799, 599)));
+        assert!(!rect.contains_point(&IVec2::new(800, 0)));
+        assert!(!rect.contains_point(&IVec2::new(0, 600)));
+        assert!(!IRect::from_xywh(5, 5, 0, 10).contains_point(&IVec2::new(5, 5)));
+    }
+
+    #[test]
+    fn clamp_point_is_closed() {
+        let rect = IRect::from_xywh(0, 0, 800, 600);
+        assert_eq!(rect.clamp_point(&IVec2::new(-5, 300)), IVec2::new(0, 300));
+        assert_eq!(rect.clamp_point(&IVec2::new(1000, 1000)), IVec2::new(800, 600));
+        assert!(!rect.contains_point(&rect.clamp_point(&IVec2::new(1000, 0))));
+
+        let rect = Rect::from_xywh(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(rect.clamp_point(&Vec2::new(2.0, 0.5)), Vec2::new(1.0, 0.5));
+        assert!(rect.contains_point(&rect.clamp_point(&Vec2::new(-1.0, 0.5))));
+    }
+
+    #[test]
+    fn intersection_and_union() {
+        let a = Rect::from_xywh(0.0, 0.0, 2.0, 2.0);
+        let b = Rect::from_xywh(1.0, 1.0, 2.0, 2.0);
+        let touching = Rect::from_xywh(2.0, 0.0, 1.0, 1.0);
+
+        assert_eq!(a.intersection(&b), Some(Rect::from_xywh(1.0, 1.0, 1.0, 1.0)));
+        assert_eq!(a.intersection(&touching), None);
+        assert_eq!(a.union(&b), Rect::from_xywh(0.0, 0.0, 3.0, 3.0));
+        assert_eq!(a.union(&Rect::from_xywh(9.0, 9.0, 0.0, 0.0)), a);
+    }
+}
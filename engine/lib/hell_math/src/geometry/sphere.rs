@@ -0,0 +1,73 @@
+use crate::vec::Vec3;
+
+use super::Aabb3;
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub const fn new(center: Vec3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+}
+
+impl Sphere {
+    pub fn contains_point(&self, point: &Vec3) -> bool {
+        (*point - self.center).mag_squared() <= self.radius * self.radius
+    }
+
+    pub fn closest_point(&self, point: &Vec3) -> Vec3 {
+        let offset = *point - self.center;
+        let dist = offset.mag();
+
+        if dist <= self.radius {
+            *point
+        } else {
+            self.center + offset * (self.radius / dist)
+        }
+    }
+
+    pub fn intersects_sphere(&self, other: &Sphere) -> bool {
+        let radius = self.radius + other.radius;
+        (other.center - self.center).mag_squared() <= radius * radius
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb3) -> bool {
+        self.contains_point(&aabb.closest_point(&self.center))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_point() {
+        let sphere = Sphere::new(Vec3::new(1.0, 0.0, 0.0), 2.0);
+        assert!(sphere.closest_point(&Vec3::new(1.0, 0.0, 5.0)).distance(Vec3::new(1.0, 0.0, 2.0)) < 1e-6);
+        assert_eq!(sphere.closest_point(&Vec3::new(1.5, 0.5, 0.0)), Vec3::new(1.5, 0.5, 0.0));
+    }
+
+    #[test]
+    fn intersections() {
+        let sphere = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0);
+        assert!(sphere.contains_point(&Vec3::new(0.0, 1.0, 0.0)));
+        assert!(!sphere.contains_point(&Vec3::new(0.8, 0.8, 0.0)));
+
+        assert!(sphere.intersects_sphere(&Sphere::new(Vec3::new(3.0, 0.0, 0.0), 2.0)));
+        assert!(!sphere.intersects_sphere(&Sphere::new(Vec3::new(3.0, 0.0, 0.0), 1.9)));
+
+        // the closest corner (1, 1, 1) is sqrt(3) units away
+        let aabb = Aabb3::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(2.0, 2.0, 2.0));
+        assert!(!sphere.intersects_aabb(&aabb));
+        assert!(Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.75).intersects_aabb(&aabb));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::vec::{Vec2, Vec3, Vec4, IVec2, IVec3, IVec4, UVec2, UVec3, UVec4};
+use crate::mat::{Mat2, Mat3, Mat4};
+use crate::quat::Quat;
+
+
+
+
+// ----------------------------------------------------------------------------
+// glam interop
+// ----------------------------------------------------------------------------
+
+macro_rules! impl_glam_vector {
+    ($vec:ident <=> $glam:ty : { $($field:ident),+ }) => {
+        impl From<$glam> for $vec {
+            #[inline]
+            fn from(val: $glam) -> Self {
+                Self::new($(val.$field),+)
+            }
+        }
+
+        impl From<$vec> for $glam {
+            #[inline]
+            fn from(val: $vec) -> Self {
+                <$glam>::new($(val.$field),+)
+            }
+        }
+    };
+}
+
+macro_rules! impl_glam_matrix {
+    ($mat:ident <=> $glam:ty : $vec:ident : { $($field:ident => $glam_field:ident),+ }) => {
+        impl From<$glam> for $mat {
+            #[inline]
+            fn from(val: $glam) -> Self {
+                Self::from_cols($($vec::from(val.$glam_field)),+)
+            }
+        }
+
+        impl From<$mat> for $glam {
+            #[inline]
+            fn from(val: $mat) -> Self {
+                <$glam>::from_cols($(val.$field.into()),+)
+            }
+        }
+    };
+}
+
+impl_glam_vector!(Vec2  <=> glam::Vec2:  { x, y });
+impl_glam_vector!(Vec3  <=> glam::Vec3:  { x, y, z });
+impl_glam_vector!(Vec4  <=> glam::Vec4:  { x, y, z, w });
+impl_glam_vector!(IVec2 <=> glam::IVec2: { x, y });
+impl_glam_vector!(IVec3 <=> glam::IVec3: { x, y, z });
+impl_glam_vector!(IVec4 <=> glam::IVec4: { x, y, z, w });
+impl_glam_vector!(UVec2 <=> glam::UVec2: { x, y });
+impl_glam_vector!(UVec3 <=> glam::UVec3: { x, y, z });
+impl_glam_vector!(UVec4 <=> glam::UVec4: { x, y, z, w });
+
+impl_glam_matrix!(Mat2 <=> glam::Mat2: Vec2: { x => x_axis, y => y_axis });
+impl_glam_matrix!(Mat3 <=> glam::Mat3: Vec3: { x => x_axis, y => y_axis, z => z_axis });
+impl_glam_matrix!(Mat4 <=> glam::Mat4: Vec4: { x => x_axis, y => y_axis, z => z_axis, w => w_axis });
+
+impl From<glam::Quat> for Quat {
+    #[inline]
+    fn from(val: glam::Quat) -> Self {
+        Self::from_xyzw(val.x, val.y, val.z, val.w)
+    }
+}
+
+impl From<Quat> for glam::Quat {
+    #[inline]
+    fn from(val: Quat) -> Self {
+        glam::Quat::from_xyzw(val.x, val.y, val.z, val.w)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // awkward values that would be changed by any arithmetic on the way
+    const VALUES: [f32; 16] = [
+        1.0, -0.0, f32::MIN_POSITIVE, f32::MAX,
+        1e-40, -3.5, 0.1, f32::EPSILON,
+        7.0, f32::MIN, -1e-45, 0.3,
+        std::f32::consts::PI, 2.0, -0.7, 123456.79,
+    ];
+
+    fn bits<const N: usize>(vals: [f32; N]) -> [u32; N] {
+        vals.map(f32::to_bits)
+    }
+
+    #[test]
+    fn vector_round_trip() {
+        let v2 = Vec2::new(VALUES[0], VALUES[1]);
+        let v3 = Vec3::new(VALUES[2], VALUES[3], VALUES[4]);
+        let v4 = Vec4::new(VALUES[5], VALUES[6], VALUES[10], VALUES[11]);
+
+        let g2: glam::Vec2 = v2.into();
+        let g3: glam::Vec3 = v3.into();
+        let g4: glam::Vec4 = v4.into();
+        assert_eq!(bits(g2.to_array()), bits([v2.x, v2.y]));
+        assert_eq!(bits(g3.to_array()), bits([v3.x, v3.y, v3.z]));
+        assert_eq!(bits(g4.to_array()), bits([v4.x, v4.y, v4.z, v4.w]));
+
+        let back: Vec4 = g4.into();
+        assert_eq!(bits([back.x, back.y, back.z, back.w]), bits([v4.x, v4.y, v4.z, v4.w]));
+
+        let i: glam::IVec3 = IVec3::new(i32::MIN, -1, i32::MAX).into();
+        assert_eq!(IVec3::from(i), IVec3::new(i32::MIN, -1, i32::MAX));
+        let u: glam::UVec2 = UVec2::new(0, u32::MAX).into();
+        assert_eq!(UVec2::from(u), UVec2::new(0, u32::MAX));
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        let g = glam::Mat4::from_cols_array(&VALUES);
+        let m: Mat4 = g.into();
+
+        // column major on both sides
+        assert_eq!(bits([m.x.x, m.x.y, m.x.z, m.x.w]), bits([VALUES[0], VALUES[1], VALUES[2], VALUES[3]]));
+        assert_eq!(bits([m.w.x, m.w.y, m.w.z, m.w.w]), bits([VALUES[12], VALUES[13], VALUES[14], VALUES[15]]));
+        assert_eq!(bits(glam::Mat4::from(m).to_cols_array()), bits(VALUES));
+
+        let g3 = glam::Mat3::from_cols_array(&[VALUES[0], VALUES[1], VALUES[2], VALUES[3], VALUES[4], VALUES[5], VALUES[6], VALUES[7], VALUES[8]]);
+        assert_eq!(bits(glam::Mat3::from(Mat3::from(g3)).to_cols_array()), bits(g3.to_cols_array()));
+
+        let g2 = glam::Mat2::from_cols_array(&[VALUES[9], VALUES[10], VALUES[11], VALUES[12]]);
+        assert_eq!(bits(glam::Mat2::from(Mat2::from(g2)).to_cols_array()), bits(g2.to_cols_array()));
+    }
+
+    #[test]
+    fn quat_round_trip() {
+        let g = glam::Quat::from_xyzw(VALUES[6], VALUES[10], VALUES[14], VALUES[15]);
+        let q: Quat = g.into();
+        assert_eq!(bits([q.x, q.y, q.z, q.w]), bits(g.to_array()));
+        assert_eq!(bits(glam::Quat::from(q).to_array()), bits(g.to_array()));
+    }
+}
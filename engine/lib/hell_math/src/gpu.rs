@@ -0,0 +1,132 @@
+use crate::vec::{Vec2, Vec3, Vec4, IVec2, IVec3, IVec4, UVec2, UVec3, UVec4};
+use crate::mat::{Mat2, Mat3, Mat4};
+use crate::quat::Quat;
+
+
+
+
+// ----------------------------------------------------------------------------
+// gpu data
+// ----------------------------------------------------------------------------
+
+/// Marker for plain-old-data types, that can be copied into gpu-memory byte by byte.
+///
+/// All types are tightly packed: `Vec3` is 12 bytes and `Mat3` stores three 12 byte columns.
+/// That matches std430 vertex attributes, but not std140 uniform blocks, which align `vec3` and every
+/// `mat3` column to 16 bytes - use `Vec4` / `Mat4` (or pad manually) for uniforms.
+///
+/// # Safety
+/// Implementors must be `#[repr(C)]` (or a primitive), must not contain any padding and must be valid for every bit-pattern.
+pub unsafe trait GpuData: Copy + 'static {
+    fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(self as *const Self as *const u8, std::mem::size_of::<Self>())
+        }
+    }
+}
+
+pub fn cast_slice<T: GpuData>(val: &[T]) -> &[u8] {
+    unsafe {
+        std::slice::from_raw_parts(val.as_ptr() as *const u8, std::mem::size_of_val(val))
+    }
+}
+
+macro_rules! impl_gpu_data {
+    ($($t:ty : $scalar:ty => $count:literal),+ $(,)?) => {
+        $(
+            unsafe impl GpuData for $t { }
+
+            // layout guarantees: same size and alignment as a tightly packed array of scalars
+            const _: () = assert!(std::mem::size_of::<$t>()  == std::mem::size_of::<[$scalar; $count]>());
+            const _: () = assert!(std::mem::align_of::<$t>() == std::mem::align_of::<$scalar>());
+        )+
+    };
+}
+
+impl_gpu_data!(
+    f32: f32 => 1,
+    i32: i32 => 1,
+    u32: u32 => 1,
+
+    Vec2:  f32 => 2,
+    Vec3:  f32 => 3,
+    Vec4:  f32 => 4,
+    IVec2: i32 => 2,
+    IVec3: i32 => 3,
+    IVec4: i32 => 4,
+    UVec2: u32 => 2,
+    UVec3: u32 => 3,
+    UVec4: u32 => 4,
+
+    Mat2: f32 => 4,
+    Mat3: f32 => 9,
+    Mat4: f32 => 16,
+
+    Quat: f32 => 4,
+);
+
+unsafe impl<T: GpuData, const N: usize> GpuData for [T; N] { }
+
+// glam types may be over-aligned (e.g. 'glam::Vec4' with sse2), but must not contain padding
+// 'glam::Vec3A' and 'glam::Mat3A' are padded and therefore not supported
+macro_rules! impl_gpu_data_glam {
+    ($($t:ty : $scalar:ty => $count:literal),+ $(,)?) => {
+        $(
+            unsafe impl GpuData for $t { }
+
+            const _: () = assert!(std::mem::size_of::<$t>() == std::mem::size_of::<[$scalar; $count]>());
+        )+
+    };
+}
+
+impl_gpu_data_glam!(
+    glam::Vec2:  f32 => 2,
+    glam::Vec3:  f32 => 3,
+    glam::Vec4:  f32 => 4,
+    glam::IVec2: i32 => 2,
+    glam::IVec3: i32 => 3,
+    glam::IVec4: i32 => 4,
+    glam::UVec2: u32 => 2,
+    glam::UVec3: u32 => 3,
+    glam::UVec4: u32 => 4,
+
+    glam::Mat2: f32 => 4,
+    glam::Mat3: f32 => 9,
+    glam::Mat4: f32 => 16,
+
+    glam::Quat: f32 => 4,
+);
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout() {
+        assert_eq!((std::mem::size_of::<Vec3>(), std::mem::align_of::<Vec3>()), (12, 4));
+        assert_eq!((std::mem::size_of::<Vec4>(), std::mem::align_of::<Vec4>()), (16, 4));
+        assert_eq!((std::mem::size_of::<Mat3>(), std::mem::align_of::<Mat3>()), (36, 4));
+        assert_eq!((std::mem::size_of::<Mat4>(), std::mem::align_of::<Mat4>()), (64, 4));
+        assert_eq!((std::mem::size_of::<Quat>(), std::mem::align_of::<Quat>()), (16, 4));
+    }
+
+    #[test]
+    fn bytes_match_glam() {
+        let m = Mat4::from_cols(
+            Vec4::new(1.0, 2.0, 3.0, 4.0),
+            Vec4::new(5.0, 6.0, 7.0, 8.0),
+            Vec4::new(9.0, 10.0, 11.0, 12.0),
+            Vec4::new(13.0, 14.0, 15.0, 16.0),
+        );
+        let g: glam::Mat4 = m.into();
+        assert_eq!(m.as_bytes(), g.as_bytes());
+
+        let v = Vec3::new(1.5, -2.0, 1e-7);
+        assert_eq!(v.as_bytes(), glam::Vec3::from(v).as_bytes());
+
+        let vs = [Vec2::new(1.0, 2.0), Vec2::new(3.0, 4.0)];
+        assert_eq!(cast_slice(&vs), cast_slice(&[1.0f32, 2.0, 3.0, 4.0]));
+    }
+}
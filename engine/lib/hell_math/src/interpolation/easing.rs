@@ -0,0 +1,212 @@
+use std::f32::consts::PI;
+
+
+
+
+// https://easings.net/
+// all curves map [0, 1] -> [0, 1], 'back' and 'elastic' overshoot in between
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Easing {
+    #[default]
+    Linear,
+
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+
+    SineIn,
+    SineOut,
+    SineInOut,
+
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+
+    CircIn,
+    CircOut,
+    CircInOut,
+
+    BackIn,
+    BackOut,
+    BackInOut,
+
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Easing {
+    const BACK_C1: f32 = 1.70158;
+    const BACK_C2: f32 = Self::BACK_C1 * 1.525;
+    const BACK_C3: f32 = Self::BACK_C1 + 1.0;
+    const ELASTIC_C4: f32 = (2.0 * PI) / 3.0;
+    const ELASTIC_C5: f32 = (2.0 * PI) / 4.5;
+}
+
+impl Easing {
+    // t is clamped to [0, 1]
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+
+            Easing::QuadIn    => t * t,
+            Easing::QuadOut   => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => Self::in_out(t, |t| t * t),
+
+            Easing::CubicIn    => t.powi(3),
+            Easing::CubicOut   => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => Self::in_out(t, |t| t.powi(3)),
+
+            Easing::QuartIn    => t.powi(4),
+            Easing::QuartOut   => 1.0 - (1.0 - t).powi(4),
+            Easing::QuartInOut => Self::in_out(t, |t| t.powi(4)),
+
+            Easing::QuintIn    => t.powi(5),
+            Easing::QuintOut   => 1.0 - (1.0 - t).powi(5),
+            Easing::QuintInOut => Self::in_out(t, |t| t.powi(5)),
+
+            Easing::SineIn    => 1.0 - ((t * PI) / 2.0).cos(),
+            Easing::SineOut   => ((t * PI) / 2.0).sin(),
+            Easing::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+
+            Easing::ExpoIn    => if t == 0.0 { 0.0 } else { 2_f32.powf(10.0 * t - 10.0) },
+            Easing::ExpoOut   => if t == 1.0 { 1.0 } else { 1.0 - 2_f32.powf(-10.0 * t) },
+            Easing::ExpoInOut => {
+                if t == 0.0 || t == 1.0 { t }
+                else if t < 0.5 { 2_f32.powf(20.0 * t - 10.0) / 2.0 }
+                else { (2.0 - 2_f32.powf(-20.0 * t + 10.0)) / 2.0 }
+            }
+
+            Easing::CircIn    => 1.0 - (1.0 - t * t).sqrt(),
+            Easing::CircOut   => (1.0 - (t - 1.0) * (t - 1.0)).sqrt(),
+            Easing::CircInOut => Self::in_out(t, |t| 1.0 - (1.0 - t * t).sqrt()),
+
+            Easing::BackIn    => Self::BACK_C3 * t.powi(3) - Self::BACK_C1 * t * t,
+            Easing::BackOut   => 1.0 + Self::BACK_C3 * (t - 1.0).powi(3) + Self::BACK_C1 * (t - 1.0).powi(2),
+            Easing::BackInOut => {
+                if t < 0.5 { ((2.0 * t).powi(2) * ((Self::BACK_C2 + 1.0) * 2.0 * t - Self::BACK_C2)) / 2.0 }
+                else { ((2.0 * t - 2.0).powi(2) * ((Self::BACK_C2 + 1.0) * (t * 2.0 - 2.0) + Self::BACK_C2) + 2.0) / 2.0 }
+            }
+
+            Easing::ElasticIn => {
+                if t == 0.0 || t == 1.0 { t }
+                else { -(2_f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * Self::ELASTIC_C4).sin() }
+            }
+            Easing::ElasticOut => {
+                if t == 0.0 || t == 1.0 { t }
+                else { 2_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * Self::ELASTIC_C4).sin() + 1.0 }
+            }
+            Easing::ElasticInOut => {
+                if t == 0.0 || t == 1.0 { t }
+                else if t < 0.5 { -(2_f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * Self::ELASTIC_C5).sin()) / 2.0 }
+                else { (2_f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * Self::ELASTIC_C5).sin()) / 2.0 + 1.0 }
+            }
+
+            Easing::BounceIn    => 1.0 - Self::bounce_out(1.0 - t),
+            Easing::BounceOut   => Self::bounce_out(t),
+            Easing::BounceInOut => {
+                if t < 0.5 { (1.0 - Self::bounce_out(1.0 - 2.0 * t)) / 2.0 }
+                else { (1.0 + Self::bounce_out(2.0 * t - 1.0)) / 2.0 }
+            }
+        }
+    }
+
+    // builds the in-out variant of an 'in' curve
+    fn in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+        if t < 0.5 {
+            ease_in(2.0 * t) / 2.0
+        } else {
+            1.0 - ease_in(2.0 - 2.0 * t) / 2.0
+        }
+    }
+
+    fn bounce_out(t: f32) -> f32 {
+        const N1: f32 = 7.5625;
+        const D1: f32 = 2.75;
+
+        if t < 1.0 / D1 {
+            N1 * t * t
+        } else if t < 2.0 / D1 {
+            let t = t - 1.5 / D1;
+            N1 * t * t + 0.75
+        } else if t < 2.5 / D1 {
+            let t = t - 2.25 / D1;
+            N1 * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / D1;
+            N1 * t * t + 0.984375
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 31] = [
+        Easing::Linear,
+        Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut,
+        Easing::CubicIn, Easing::CubicOut, Easing::CubicInOut,
+        Easing::QuartIn, Easing::QuartOut, Easing::QuartInOut,
+        Easing::QuintIn, Easing::QuintOut, Easing::QuintInOut,
+        Easing::SineIn, Easing::SineOut, Easing::SineInOut,
+        Easing::ExpoIn, Easing::ExpoOut, Easing::ExpoInOut,
+        Easing::CircIn, Easing::CircOut, Easing::CircInOut,
+        Easing::BackIn, Easing::BackOut, Easing::BackInOut,
+        Easing::ElasticIn, Easing::ElasticOut, Easing::ElasticInOut,
+        Easing::BounceIn, Easing::BounceOut, Easing::BounceInOut,
+    ];
+
+    #[test]
+    fn endpoints() {
+        for easing in ALL {
+            assert!(easing.apply(0.0).abs() < 1e-5, "{:?}", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-5, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn clamps_t() {
+        for easing in ALL {
+            assert_eq!(easing.apply(-1.0), easing.apply(0.0), "{:?}", easing);
+            assert_eq!(easing.apply(2.0), easing.apply(1.0), "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn in_out_is_symmetric() {
+        for easing in [Easing::QuadInOut, Easing::CubicInOut, Easing::SineInOut, Easing::ExpoInOut, Easing::CircInOut, Easing::BounceInOut] {
+            assert!((easing.apply(0.5) - 0.5).abs() < 1e-5, "{:?}", easing);
+            assert!((easing.apply(0.2) + easing.apply(0.8) - 1.0).abs() < 1e-5, "{:?}", easing);
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+        assert_eq!(Easing::QuadOut.apply(0.5), 0.75);
+        assert_eq!(Easing::CubicIn.apply(0.5), 0.125);
+        assert!(Easing::BackIn.apply(0.2) < 0.0);
+        assert!(Easing::BackOut.apply(0.8) > 1.0);
+    }
+}
@@ -0,0 +1,99 @@
+use std::ops::{Add, Sub, Mul};
+
+use crate::vec::{Vec2, Vec3, Vec4};
+use crate::quat::Quat;
+
+
+
+
+// ----------------------------------------------------------------------------
+// interpolate
+// ----------------------------------------------------------------------------
+
+pub trait Interpolate: Copy {
+    // t outside of [0, 1] extrapolates
+    fn interpolate(&self, end: &Self, t: f32) -> Self;
+    // used for arc-length parameterization
+    fn distance_to(&self, other: &Self) -> f32;
+}
+
+// types that support hermite splines
+pub trait VectorSpace: Interpolate + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self> { }
+
+impl Interpolate for f32 {
+    fn interpolate(&self, end: &Self, t: f32) -> Self {
+        self + (end - self) * t
+    }
+
+    fn distance_to(&self, other: &Self) -> f32 {
+        (other - self).abs()
+    }
+}
+
+impl VectorSpace for f32 { }
+
+macro_rules! impl_interpolate_vector {
+    ($($vec:ident),+) => {
+        $(
+            impl Interpolate for $vec {
+                fn interpolate(&self, end: &Self, t: f32) -> Self {
+                    *self + (*end - *self) * t
+                }
+
+                fn distance_to(&self, other: &Self) -> f32 {
+                    self.distance(*other)
+                }
+            }
+
+            impl VectorSpace for $vec { }
+        )+
+    };
+}
+
+impl_interpolate_vector!(Vec2, Vec3, Vec4);
+
+// quaternions are interpolated on the unit-sphere
+impl Interpolate for Quat {
+    fn interpolate(&self, end: &Self, t: f32) -> Self {
+        self.slerp(end, t)
+    }
+
+    fn distance_to(&self, other: &Self) -> f32 {
+        self.angle_between(other)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// helpers
+// ----------------------------------------------------------------------------
+
+pub fn lerp<T: Interpolate>(start: T, end: T, t: f32) -> T {
+    start.interpolate(&end, t)
+}
+
+// returns the t for which lerp(start, end, t) == val
+pub fn inverse_lerp(start: f32, end: f32, val: f32) -> f32 {
+    if (end - start).abs() < f32::EPSILON {
+        return 0.0;
+    }
+
+    (val - start) / (end - start)
+}
+
+// maps val from [in_start, in_end] to [out_start, out_end]
+pub fn remap(in_start: f32, in_end: f32, out_start: f32, out_end: f32, val: f32) -> f32 {
+    lerp(out_start, out_end, inverse_lerp(in_start, in_end, val))
+}
+
+pub fn smoothstep(edge_0: f32, edge_1: f32, val: f32) -> f32 {
+    let t = inverse_lerp(edge_0, edge_1, val).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+// ken perlin's variant with zero first and second derivative at the edges
+pub fn smootherstep(edge_0: f32, edge_1: f32, val: f32) -> f32 {
+    let t = inverse_lerp(edge_0, edge_1, val).clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
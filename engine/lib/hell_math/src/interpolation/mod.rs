@@ -0,0 +1,8 @@
+mod lerp;
+pub use lerp::*;
+
+mod easing;
+pub use easing::*;
+
+mod spline;
+pub use spline::*;
@@ -0,0 +1,298 @@
+use super::{Interpolate, VectorSpace};
+
+
+
+
+// ----------------------------------------------------------------------------
+// curve
+// ----------------------------------------------------------------------------
+
+pub trait Curve<T> {
+    // t in [0, 1] covers the whole curve
+    fn sample(&self, t: f32) -> T;
+}
+
+
+
+// ----------------------------------------------------------------------------
+// cubic bezier
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier<T> {
+    pub points: [T; 4],
+}
+
+impl<T: Interpolate> CubicBezier<T> {
+    pub const fn new(p0: T, p1: T, p2: T, p3: T) -> Self {
+        Self { points: [p0, p1, p2, p3] }
+    }
+}
+
+impl<T: Interpolate> Curve<T> for CubicBezier<T> {
+    // de casteljau: only needs interpolation, so it also works on the unit-sphere
+    fn sample(&self, t: f32) -> T {
+        let [p0, p1, p2, p3] = &self.points;
+
+        let a = p0.interpolate(p1, t);
+        let b = p1.interpolate(p2, t);
+        let c = p2.interpolate(p3, t);
+
+        let d = a.interpolate(&b, t);
+        let e = b.interpolate(&c, t);
+
+        d.interpolate(&e, t)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// cubic hermite
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicHermite<T> {
+    pub start: T,
+    pub start_tangent: T,
+    pub end: T,
+    pub end_tangent: T,
+}
+
+impl<T: VectorSpace> CubicHermite<T> {
+    pub const fn new(start: T, start_tangent: T, end: T, end_tangent: T) -> Self {
+        Self { start, start_tangent, end, end_tangent }
+    }
+}
+
+impl<T: VectorSpace> Curve<T> for CubicHermite<T> {
+    fn sample(&self, t: f32) -> T {
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        let h00 =  2.0 * t3 - 3.0 * t2 + 1.0;
+        let h10 =        t3 - 2.0 * t2 + t;
+        let h01 = -2.0 * t3 + 3.0 * t2;
+        let h11 =        t3 -       t2;
+
+        self.start * h00 + self.start_tangent * h10 + self.end * h01 + self.end_tangent * h11
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// catmull-rom
+// ----------------------------------------------------------------------------
+
+// uniform catmull-rom spline through all points
+// the first and last point are duplicated, so the curve starts and ends at them
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom<T> {
+    // never empty
+    points: Vec<T>,
+}
+
+impl<T: Interpolate> CatmullRom<T> {
+    // returns 'None' if there are no points
+    pub fn new(points: Vec<T>) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+
+        Some(Self { points })
+    }
+
+    pub fn points(&self) -> &[T] {
+        &self.points
+    }
+
+    pub fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(1)
+    }
+
+    // barry-goldman pyramidal formulation: only needs interpolation, so it also works on the unit-sphere
+    // segments past the end are clamped to the last one
+    pub fn sample_segment(&self, segment: usize, t: f32) -> T {
+        let last = self.points.len() - 1;
+        let segment = segment.min(last.saturating_sub(1));
+        let p0 = &self.points[segment.saturating_sub(1)];
+        let p1 = &self.points[segment];
+        let p2 = &self.points[(segment + 1).min(last)];
+        let p3 = &self.points[(segment + 2).min(last)];
+
+        let a1 = p0.interpolate(p1, t + 1.0);
+        let a2 = p1.interpolate(p2, t);
+        let a3 = p2.interpolate(p3, t - 1.0);
+
+        let b1 = a1.interpolate(&a2, (t + 1.0) / 2.0);
+        let b2 = a2.interpolate(&a3, t / 2.0);
+
+        b1.interpolate(&b2, t)
+    }
+}
+
+impl<T: Interpolate> Curve<T> for CatmullRom<T> {
+    fn sample(&self, t: f32) -> T {
+        let segment_count = self.segment_count();
+        if segment_count == 0 {
+            return self.points[0];
+        }
+
+        let scaled = t.clamp(0.0, 1.0) * segment_count as f32;
+        let segment = (scaled as usize).min(segment_count - 1);
+
+        self.sample_segment(segment, scaled - segment as f32)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// arc-length
+// ----------------------------------------------------------------------------
+
+// maps distances along a curve to curve parameters, so that curves can be traversed with constant speed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcLengthTable {
+    // cumulative length at t = idx / (len - 1)
+    lengths: Vec<f32>,
+}
+
+impl ArcLengthTable {
+    pub fn new<T: Interpolate>(curve: &impl Curve<T>, sample_count: usize) -> Self {
+        let sample_count = sample_count.max(1);
+        let mut lengths = Vec::with_capacity(sample_count + 1);
+        let mut prev = curve.sample(0.0);
+        let mut total = 0.0;
+        lengths.push(total);
+
+        for idx in 1..=sample_count {
+            let curr = curve.sample(idx as f32 / sample_count as f32);
+            total += prev.distance_to(&curr);
+            lengths.push(total);
+            prev = curr;
+        }
+
+        Self { lengths }
+    }
+
+    pub fn total_length(&self) -> f32 {
+        *self.lengths.last().unwrap_or(&0.0)
+    }
+
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        let total = self.total_length();
+        if total <= 0.0 {
+            return 0.0;
+        }
+
+        let distance = distance.clamp(0.0, total);
+        let idx = self.lengths.partition_point(|l| *l < distance).max(1);
+
+        let start = self.lengths[idx - 1];
+        let end = self.lengths[idx];
+        let local = if end > start { (distance - start) / (end - start) } else { 0.0 };
+
+        (idx as f32 - 1.0 + local) / (self.lengths.len() - 1) as f32
+    }
+
+    // fraction in [0, 1] of the total length
+    pub fn t_at_fraction(&self, fraction: f32) -> f32 {
+        self.t_at_distance(fraction * self.total_length())
+    }
+
+    pub fn sample<T: Interpolate>(&self, curve: &impl Curve<T>, fraction: f32) -> T {
+        curve.sample(self.t_at_fraction(fraction))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec::Vec3;
+
+    fn assert_vec_eq(lhs: Vec3, rhs: Vec3) {
+        assert!(lhs.distance(rhs) < 1e-5, "{:?} != {:?}", lhs, rhs);
+    }
+
+    fn control_points() -> Vec<Vec3> {
+        vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 0.0), Vec3::new(3.0, 2.0, -1.0), Vec3::new(4.0, 0.0, 2.0), Vec3::new(6.0, 1.0, 1.0)]
+    }
+
+    #[test]
+    fn bezier_passes_through_its_end_points() {
+        let [p0, p1, p2, p3] = [0, 1, 2, 3].map(|i| control_points()[i]);
+        let bezier = CubicBezier::new(p0, p1, p2, p3);
+        assert_vec_eq(bezier.sample(0.0), p0);
+        assert_vec_eq(bezier.sample(1.0), p3);
+        // (p0 + 3 p1 + 3 p2 + p3) / 8
+        assert_vec_eq(bezier.sample(0.5), (p0 + p1 * 3.0 + p2 * 3.0 + p3) * 0.125);
+    }
+
+    #[test]
+    fn hermite_passes_through_its_end_points() {
+        let hermite = CubicHermite::new(1.0_f32, 4.0, 3.0, -2.0);
+        assert_eq!(hermite.sample(0.0), 1.0);
+        assert_eq!(hermite.sample(1.0), 3.0);
+
+        // the tangents are the derivatives at the end points
+        let h = 1e-3;
+        assert!(((hermite.sample(h) - hermite.sample(0.0)) / h - 4.0).abs() < 1e-2);
+        assert!(((hermite.sample(1.0) - hermite.sample(1.0 - h)) / h - -2.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_all_points() {
+        let points = control_points();
+        let spline = CatmullRom::new(points.clone()).unwrap();
+        assert_eq!(spline.segment_count(), 4);
+
+        for (idx, point) in points.iter().enumerate() {
+            assert_vec_eq(spline.sample(idx as f32 / 4.0), *point);
+        }
+
+        for segment in 0..4 {
+            assert_vec_eq(spline.sample_segment(segment, 0.0), points[segment]);
+            assert_vec_eq(spline.sample_segment(segment, 1.0), points[segment + 1]);
+        }
+
+        // clamped to the last segment
+        assert_vec_eq(spline.sample_segment(10, 1.0), points[4]);
+    }
+
+    #[test]
+    fn catmull_rom_needs_points() {
+        assert!(CatmullRom::<Vec3>::new(Vec::new()).is_none());
+
+        let single = CatmullRom::new(vec![Vec3::new(1.0, 2.0, 3.0)]).unwrap();
+        assert_eq!(single.segment_count(), 0);
+        assert_vec_eq(single.sample(0.7), Vec3::new(1.0, 2.0, 3.0));
+        assert_vec_eq(single.sample_segment(3, 0.5), Vec3::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn arc_length_of_a_straight_line() {
+        // evenly spaced control points move with constant speed
+        let uniform = CubicBezier::new(0.0_f32, 1.0, 2.0, 3.0);
+        let table = ArcLengthTable::new(&uniform, 64);
+        assert!((table.total_length() - 3.0).abs() < 1e-5);
+        for fraction in [0.0, 0.25, 0.5, 0.9, 1.0] {
+            assert!((table.t_at_fraction(fraction) - fraction).abs() < 1e-5);
+        }
+
+        // slow at both ends, the table compensates for it
+        let eased = CubicBezier::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0), Vec3::new(3.0, 4.0, 0.0), Vec3::new(3.0, 4.0, 0.0));
+        let table = ArcLengthTable::new(&eased, 256);
+        assert!((table.total_length() - 5.0).abs() < 1e-4);
+        for fraction in [0.1, 0.3, 0.5, 0.8] {
+            let dist = table.sample(&eased, fraction).distance(Vec3::new(0.0, 0.0, 0.0));
+            assert!((dist - fraction * 5.0).abs() < 1e-2, "{} at {}", dist, fraction);
+        }
+
+        assert_eq!(table.t_at_fraction(-1.0), 0.0);
+        assert_eq!(table.t_at_fraction(2.0), 1.0);
+    }
+}
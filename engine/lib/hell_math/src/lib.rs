@@ -5,8 +5,24 @@
 
 
 mod vec;
+mod fixed;
 mod mat;
+mod quat;
+mod projection;
+pub mod geometry;
+pub mod interpolation;
+pub mod noise;
+mod random;
+mod gpu;
+mod glam_interop;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
 
 
-pub use vec::{Vec2, Vec3, Vec4, IVec4, UVec4};
-pub use mat::Mat4;
+pub use vec::{Scalar, Vec2, Vec3, Vec4, IVec2, IVec3, IVec4, UVec2, UVec3, UVec4, Fx32Vec2, Fx32Vec3, Fx64Vec2, Fx64Vec3};
+pub use fixed::{Fixed32, Fixed64};
+pub use mat::{Mat2, Mat3, Mat4, DecomposeError};
+pub use quat::Quat;
+pub use projection::{ClipSpace, DepthRange};
+pub use random::Pcg32;
+pub use gpu::{GpuData, cast_slice};
@@ -1,7 +1,7 @@
 use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Neg, Index, IndexMut};
 
-use crate::vec::Vec3;
-use crate::Vec4;
+use crate::vec::{Vec2, Vec3, Vec4};
+use crate::quat::Quat;
 
 
 
@@ -9,6 +9,7 @@ use crate::Vec4;
 macro_rules! impl_matrix_common {
     ($mat:ident : $vec:ident : $scalar:ident => $mat_size:literal : { $($field_idx:literal : $field:ident),+ }) => {
         #[repr(C)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
         pub struct $mat {
             $(pub $field: $vec),+
         }
@@ -104,17 +105,37 @@ macro_rules! impl_matrix_common {
         // -----------------
         impl $mat {
             // #[inline]
-            pub fn mul_vec(&self, rhs: &$vec) -> $vec {
+            pub fn mul_vec_scalar(&self, rhs: &$vec) -> $vec {
                 let mut result = $vec::zero();
                 $(result.add_assign(self.$field.mul_scalar(rhs.$field));)+
                 result
             }
 
             // #[inline]
-            pub fn mul_mat(&self, rhs: &Self) -> Self {
+            pub fn mul_mat_scalar(&self, rhs: &Self) -> Self {
                 Self {
-                    $($field: self.mul_vec(&rhs.$field)),+
+                    $($field: self.mul_vec_scalar(&rhs.$field)),+
+                }
+            }
+
+            pub fn transpose(&self) -> Self {
+                let mut result = *self;
+                for col in 0..$mat_size {
+                    for row in 0..$mat_size {
+                        result[col][row] = self[row][col];
+                    }
                 }
+                result
+            }
+
+            pub fn row(&self, idx: usize) -> $vec {
+                $vec::new(
+                    $(self.$field[idx]),+
+                )
+            }
+
+            pub fn col(&self, idx: usize) -> $vec {
+                self[idx]
             }
         }
 
@@ -124,10 +145,7 @@ macro_rules! impl_matrix_common {
             type Output = $vec;
             fn index(&self, index: usize) -> &Self::Output {
                 match index {
-                    0 => &self.x,
-                    1 => &self.y,
-                    2 => &self.z,
-                    3 => &self.w,
+                    $($field_idx => &self.$field,)+
                     _ => panic!("index out of bounds!"),
                 }
             }
@@ -136,10 +154,7 @@ macro_rules! impl_matrix_common {
         impl IndexMut<usize> for $mat {
             fn index_mut(&mut self, index: usize) -> &mut Self::Output {
                 match index {
-                    0 => &mut self.x,
-                    1 => &mut self.y,
-                    2 => &mut self.z,
-                    3 => &mut self.w,
+                    $($field_idx => &mut self.$field,)+
                     _ => panic!("index out of bounds!"),
                 }
             }
@@ -148,6 +163,23 @@ macro_rules! impl_matrix_common {
     };
 }
 
+// portable multiplication, matrices with a simd path provide their own 'mul_vec' and 'mul_mat'
+macro_rules! impl_matrix_mul_scalar {
+    ($mat:ident : $vec:ident) => {
+        impl $mat {
+            #[inline]
+            pub fn mul_vec(&self, rhs: &$vec) -> $vec {
+                self.mul_vec_scalar(rhs)
+            }
+
+            #[inline]
+            pub fn mul_mat(&self, rhs: &Self) -> Self {
+                self.mul_mat_scalar(rhs)
+            }
+        }
+    };
+}
+
 macro_rules! impl_matrix_signed{
     ($mat:ident : $vec:ident : $scalar:ident => $mat_size:literal : { $($field_idx:literal : $field:ident),+ }) => {
         // neg operations
@@ -163,13 +195,210 @@ macro_rules! impl_matrix_signed{
     };
 }
 
-impl_matrix_common!(Mat4: Vec4: f32 => 2: { 0:x, 1:y, 2:z, 3:w });
-impl_matrix_signed!(Mat4: Vec4: f32 => 2: { 0:x, 1:y, 2:z, 3:w });
+impl_matrix_common!(Mat2: Vec2: f32 => 2: { 0:x, 1:y });
+impl_matrix_signed!(Mat2: Vec2: f32 => 2: { 0:x, 1:y });
+
+impl_matrix_common!(Mat3: Vec3: f32 => 3: { 0:x, 1:y, 2:z });
+impl_matrix_signed!(Mat3: Vec3: f32 => 3: { 0:x, 1:y, 2:z });
+
+impl_matrix_common!(Mat4: Vec4: f32 => 4: { 0:x, 1:y, 2:z, 3:w });
+impl_matrix_signed!(Mat4: Vec4: f32 => 4: { 0:x, 1:y, 2:z, 3:w });
+
+impl_matrix_mul_scalar!(Mat2: Vec2);
+impl_matrix_mul_scalar!(Mat3: Vec3);
+#[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+impl_matrix_mul_scalar!(Mat4: Vec4);
+
+
+
+// ----------------------------------------------------------------------------
+// mat2
+// ----------------------------------------------------------------------------
+
+impl Mat2 {
+    pub fn determinant(&self) -> f32 {
+        self.x.x * self.y.y - self.y.x * self.x.y
+    }
+
+    pub fn inverse(self) -> Self {
+        let rcp_det = self.determinant().recip();
+
+        Self::from_cols(
+            Vec2::new( self.y.y, -self.x.y),
+            Vec2::new(-self.y.x,  self.x.x),
+        ).mul(rcp_det)
+    }
+}
+
+impl Mat2 {
+    pub fn from_scale(val: &Vec2) -> Self {
+        Self::from_cols(
+            Vec2::new(val.x, 0.0),
+            Vec2::new(0.0, val.y),
+        )
+    }
+
+    // counter-clockwise rotation in radians
+    pub fn from_angle(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Self::from_cols(
+            Vec2::new( cos, sin),
+            Vec2::new(-sin, cos),
+        )
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// mat3
+// ----------------------------------------------------------------------------
+
+impl Mat3 {
+    pub fn determinant(&self) -> f32 {
+        self.z.dot(self.x.cross(&self.y))
+    }
+
+    pub fn inverse(self) -> Self {
+        let tmp0 = self.y.cross(&self.z);
+        let tmp1 = self.z.cross(&self.x);
+        let tmp2 = self.x.cross(&self.y);
+        let rcp_det = self.z.dot(tmp2).recip();
+
+        // the cross products are the rows of the adjugate
+        Self::from_cols(tmp0, tmp1, tmp2)
+            .transpose()
+            .mul(rcp_det)
+    }
+}
+
+impl Mat3 {
+    // upper 3x3 of the matrix
+    pub fn from_mat4(val: &Mat4) -> Self {
+        Self::from_cols(
+            Vec3::new(val.x.x, val.x.y, val.x.z),
+            Vec3::new(val.y.x, val.y.y, val.y.z),
+            Vec3::new(val.z.x, val.z.y, val.z.z),
+        )
+    }
+
+    // inverse-transpose of the models upper 3x3, used to transform normals under non-uniform scale
+    pub fn normal_matrix(model: &Mat4) -> Self {
+        Self::from_mat4(model).inverse().transpose()
+    }
+}
+
+// 2d affine transformations (ui): z-column holds the translation
+impl Mat3 {
+    pub fn from_translation_2d(val: &Vec2) -> Self {
+        Self::from_cols(
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(val.x, val.y, 1.0),
+        )
+    }
+
+    pub fn from_scale_2d(val: &Vec2) -> Self {
+        Self::from_cols(
+            Vec3::new(val.x, 0.0, 0.0),
+            Vec3::new(0.0, val.y, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        )
+    }
+
+    // counter-clockwise rotation in radians
+    pub fn from_angle_2d(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Self::from_cols(
+            Vec3::new( cos, sin, 0.0),
+            Vec3::new(-sin, cos, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        )
+    }
+
+    // oder: Mt * Mr * Ms
+    pub fn from_scale_angle_translation_2d(scale: &Vec2, angle: f32, translation: &Vec2) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Self::from_cols(
+            Vec3::new( cos * scale.x, sin * scale.x, 0.0),
+            Vec3::new(-sin * scale.y, cos * scale.y, 0.0),
+            Vec3::new(translation.x, translation.y, 1.0),
+        )
+    }
+
+    pub fn transform_point_2d(&self, val: &Vec2) -> Vec2 {
+        let result = self.mul_vec(&Vec3::new(val.x, val.y, 1.0));
+        Vec2::new(result.x, result.y)
+    }
+
+    // ignores the translation
+    pub fn transform_vector_2d(&self, val: &Vec2) -> Vec2 {
+        let result = self.mul_vec(&Vec3::new(val.x, val.y, 0.0));
+        Vec2::new(result.x, result.y)
+    }
+}
+
+impl From<Mat2> for Mat3 {
+    fn from(val: Mat2) -> Self {
+        Self::from_cols(
+            Vec3::new(val.x.x, val.x.y, 0.0),
+            Vec3::new(val.y.x, val.y.y, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        )
+    }
+}
+
+impl From<Mat3> for Mat4 {
+    fn from(val: Mat3) -> Self {
+        Self::from_cols(
+            Vec4::new(val.x.x, val.x.y, val.x.z, 0.0),
+            Vec4::new(val.y.x, val.y.y, val.y.z, 0.0),
+            Vec4::new(val.z.x, val.z.y, val.z.z, 0.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+        )
+    }
+}
+
+
 
+// ----------------------------------------------------------------------------
+// mat4
+// ----------------------------------------------------------------------------
 
 impl Mat4 {
+    pub fn determinant(&self) -> f32 {
+        let (m00, m01, m02, m03) = self.x.into();
+        let (m10, m11, m12, m13) = self.y.into();
+        let (m20, m21, m22, m23) = self.z.into();
+        let (m30, m31, m32, m33) = self.w.into();
+
+        let a2323 = m22 * m33 - m23 * m32;
+        let a1323 = m21 * m33 - m23 * m31;
+        let a1223 = m21 * m32 - m22 * m31;
+        let a0323 = m20 * m33 - m23 * m30;
+        let a0223 = m20 * m32 - m22 * m30;
+        let a0123 = m20 * m31 - m21 * m30;
+
+        m00 * (m11 * a2323 - m12 * a1323 + m13 * a1223)
+            - m01 * (m10 * a2323 - m12 * a0323 + m13 * a0223)
+            + m02 * (m10 * a1323 - m11 * a0323 + m13 * a0123)
+            - m03 * (m10 * a1223 - m11 * a0223 + m12 * a0123)
+    }
+
+    #[inline]
     pub fn inverse(self) -> Self {
-         let (m00, m01, m02, m03) = self.x.into();
+        #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+        return crate::simd::mat4_inverse(&self);
+
+        #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+        return self.inverse_scalar();
+    }
+
+    pub fn inverse_scalar(self) -> Self {
+        let (m00, m01, m02, m03) = self.x.into();
         let (m10, m11, m12, m13) = self.y.into();
         let (m20, m21, m22, m23) = self.z.into();
         let (m30, m31, m32, m33) = self.w.into();
@@ -240,6 +469,99 @@ impl Mat4 {
     }
 }
 
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+impl Mat4 {
+    #[inline]
+    pub fn mul_vec(&self, rhs: &Vec4) -> Vec4 {
+        crate::simd::mat4_mul_vec(self, rhs)
+    }
+
+    #[inline]
+    pub fn mul_mat(&self, rhs: &Self) -> Self {
+        crate::simd::mat4_mul_mat(self, rhs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecomposeError {
+    // at least one axis has (almost) zero length
+    Degenerate,
+    // the axes are not orthogonal to each other
+    Skewed,
+    // the last row is not (0, 0, 0, 1)
+    Projective,
+}
+
+impl std::fmt::Display for DecomposeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecomposeError::Degenerate => write!(f, "matrix has a zero-length axis"),
+            DecomposeError::Skewed     => write!(f, "matrix contains skew"),
+            DecomposeError::Projective => write!(f, "matrix contains a projection"),
+        }
+    }
+}
+
+impl std::error::Error for DecomposeError { }
+
+impl Mat4 {
+    const DECOMPOSE_EPSILON: f32 = 1e-4;
+
+    // inverse of 'Mt * Mr * Ms'
+    // a negative determinant is resolved by flipping the scale of the x-axis
+    pub fn to_scale_rotation_translation(&self) -> Result<(Vec3, Quat, Vec3), DecomposeError> {
+        let last_row = self.row(3);
+        if last_row.x.abs() > Self::DECOMPOSE_EPSILON
+            || last_row.y.abs() > Self::DECOMPOSE_EPSILON
+            || last_row.z.abs() > Self::DECOMPOSE_EPSILON
+            || (last_row.w - 1.0).abs() > Self::DECOMPOSE_EPSILON
+        {
+            return Err(DecomposeError::Projective);
+        }
+
+        let translation = Vec3::new(self.w.x, self.w.y, self.w.z);
+
+        let axes = Mat3::from_mat4(self);
+        let mut scale = Vec3::new(axes.x.mag(), axes.y.mag(), axes.z.mag());
+
+        if scale.x < Self::DECOMPOSE_EPSILON || scale.y < Self::DECOMPOSE_EPSILON || scale.z < Self::DECOMPOSE_EPSILON {
+            return Err(DecomposeError::Degenerate);
+        }
+
+        if axes.determinant() < 0.0 {
+            scale.x = -scale.x;
+        }
+
+        let rotation = Mat3::from_cols(
+            axes.x / scale.x,
+            axes.y / scale.y,
+            axes.z / scale.z,
+        );
+
+        if rotation.x.dot(rotation.y).abs() > Self::DECOMPOSE_EPSILON
+            || rotation.x.dot(rotation.z).abs() > Self::DECOMPOSE_EPSILON
+            || rotation.y.dot(rotation.z).abs() > Self::DECOMPOSE_EPSILON
+        {
+            return Err(DecomposeError::Skewed);
+        }
+
+        let rotation = Quat::from_mat4(&rotation.into());
+
+        Ok((scale, rotation, translation))
+    }
+
+    pub fn from_scale_rotation_translation(scale: &Vec3, rotation: &Quat, translation: &Vec3) -> Self {
+        let rot = rotation.to_mat4();
+
+        Self::from_cols(
+            rot.x * scale.x,
+            rot.y * scale.y,
+            rot.z * scale.z,
+            Vec4::new(translation.x, translation.y, translation.z, 1.0),
+        )
+    }
+}
+
 // oder: Mt * Mr * Ms * V
 impl Mat4 {
     // TODO:
@@ -349,6 +671,8 @@ impl Mat4 {
         Self::from_cols(x, y, z, w)
     }
 
+    // same as 'perspective_rh(vertical_fov.to_radians(), aspect_ratio, z_near, z_far, ClipSpace::VULKAN_REVERSE_Z)'
+    #[deprecated(note = "takes degrees unlike all other angles, use 'Mat4::perspective_rh' with radians and 'ClipSpace::VULKAN_REVERSE_Z' instead")]
     pub fn from_perspective_rh(vertical_fov: f32, aspect_ratio: f32, z_near: f32, z_far: f32) -> Self {
         let fov_rad = vertical_fov * 2.0 * std::f32::consts::PI / 360.0;
         let focal_length = 1.0 / (fov_rad / 2.0).tan();
@@ -386,6 +710,8 @@ impl Mat4 {
 }
 
 impl Mat4 {
+    // takes a direction, not a target - only correct for a normalized 'direction'
+    #[deprecated(note = "use 'Mat4::look_to_rh' for a direction or 'Mat4::look_at_target_rh' for a target instead")]
     pub fn look_at_rh(pos: &Vec3, direction: &Vec3, up: &Vec3) -> Self {
         let cam_right = direction.cross(up).unit();
         let cam_up = cam_right.cross(direction);
@@ -404,3 +730,154 @@ impl Mat4 {
         (translation * rotation).inverse()
     }
 }
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec_eq(lhs: Vec3, rhs: glam::Vec3) {
+        assert!(glam::Vec3::from(lhs).abs_diff_eq(rhs, 1e-4), "{:?} != {:?}", lhs, rhs);
+    }
+
+    fn assert_mat4_eq(lhs: &Mat4, rhs: &Mat4) {
+        assert!(glam::Mat4::from(*lhs).abs_diff_eq(glam::Mat4::from(*rhs), 1e-4), "{:?} != {:?}", lhs, rhs);
+    }
+
+    // q and -q are the same rotation
+    fn assert_quat_eq(lhs: Quat, rhs: glam::Quat) {
+        assert!(glam::Quat::from(lhs).dot(rhs).abs() > 1.0 - 1e-5, "{:?} != {:?}", lhs, rhs);
+    }
+
+    fn srt(scale: Vec3) -> Mat4 {
+        let rotation = Quat::from_axis_angle(&Vec3::new(1.0, 2.0, -0.5).unit(), 0.8);
+        Mat4::from_scale_rotation_translation(&scale, &rotation, &Vec3::new(4.0, -5.0, 6.0))
+    }
+
+    // decomposition
+    // -------------
+    #[test]
+    fn decompose_round_trip() {
+        for scale in [Vec3::new(2.0, 3.0, 4.0), Vec3::new(0.5, 0.5, 0.5), Vec3::new(1.0, 1.0, 1.0)] {
+            let mat = srt(scale);
+            let (scale, rotation, translation) = mat.to_scale_rotation_translation().unwrap();
+            let (g_scale, g_rotation, g_translation) = glam::Mat4::from(mat).to_scale_rotation_translation();
+
+            assert_vec_eq(scale, g_scale);
+            assert_quat_eq(rotation, g_rotation);
+            assert_vec_eq(translation, g_translation);
+            assert_mat4_eq(&Mat4::from_scale_rotation_translation(&scale, &rotation, &translation), &mat);
+        }
+    }
+
+    #[test]
+    fn decompose_mirrored() {
+        // every mirror is resolved by flipping the x-axis, the rotation absorbs the rest
+        for scale in [Vec3::new(-2.0, 3.0, 4.0), Vec3::new(2.0, -3.0, 4.0), Vec3::new(2.0, 3.0, -4.0), Vec3::new(-1.0, -1.0, -1.0)] {
+            let mat = srt(scale);
+            let (scale, rotation, translation) = mat.to_scale_rotation_translation().unwrap();
+            let (g_scale, g_rotation, _) = glam::Mat4::from(mat).to_scale_rotation_translation();
+
+            assert!(scale.x < 0.0 && scale.y > 0.0 && scale.z > 0.0, "{:?}", scale);
+            assert_vec_eq(scale, g_scale);
+            assert_quat_eq(rotation, g_rotation);
+            assert_mat4_eq(&Mat4::from_scale_rotation_translation(&scale, &rotation, &translation), &mat);
+        }
+    }
+
+    #[test]
+    fn decompose_errors() {
+        let mut skewed = srt(Vec3::new(1.0, 1.0, 1.0));
+        skewed.y += skewed.x * 0.5;
+        assert_eq!(skewed.to_scale_rotation_translation(), Err(DecomposeError::Skewed));
+
+        let mut projective = srt(Vec3::new(1.0, 1.0, 1.0));
+        projective.z.w = 0.25;
+        assert_eq!(projective.to_scale_rotation_translation(), Err(DecomposeError::Projective));
+
+        let mut homogeneous = srt(Vec3::new(1.0, 1.0, 1.0));
+        homogeneous.w.w = 2.0;
+        assert_eq!(homogeneous.to_scale_rotation_translation(), Err(DecomposeError::Projective));
+
+        assert_eq!(srt(Vec3::new(1.0, 0.0, 1.0)).to_scale_rotation_translation(), Err(DecomposeError::Degenerate));
+        assert_eq!(Mat4::from_cols(Vec4::zero(), Vec4::zero(), Vec4::zero(), Vec4::new(0.0, 0.0, 0.0, 1.0)).to_scale_rotation_translation(), Err(DecomposeError::Degenerate));
+    }
+
+    // mat2
+    // ----
+    fn mat2() -> Mat2 {
+        Mat2::from_cols(Vec2::new(2.0, -1.0), Vec2::new(0.5, 3.0))
+    }
+
+    #[test]
+    fn mat2_against_glam() {
+        let mat = mat2();
+        let g_mat = glam::Mat2::from(mat);
+
+        assert_eq!(glam::Mat2::from(mat.transpose()), g_mat.transpose());
+        assert!((mat.determinant() - g_mat.determinant()).abs() < 1e-6);
+        assert!(glam::Mat2::from(mat.inverse()).abs_diff_eq(g_mat.inverse(), 1e-6));
+        assert!(glam::Mat2::from(mat.mul_mat(&mat.inverse())).abs_diff_eq(glam::Mat2::IDENTITY, 1e-6));
+
+        assert_eq!(glam::Mat2::from(Mat2::from_scale(&Vec2::new(2.0, -3.0))), glam::Mat2::from_diagonal(glam::Vec2::new(2.0, -3.0)));
+        assert!(glam::Mat2::from(Mat2::from_angle(0.7)).abs_diff_eq(glam::Mat2::from_angle(0.7), 1e-6));
+    }
+
+    // mat3
+    // ----
+    fn mat3() -> Mat3 {
+        Mat3::from_cols(Vec3::new(2.0, -1.0, 0.5), Vec3::new(0.5, 3.0, -2.0), Vec3::new(1.0, 0.25, 4.0))
+    }
+
+    #[test]
+    fn mat3_against_glam() {
+        let mat = mat3();
+        let g_mat = glam::Mat3::from(mat);
+
+        assert_eq!(glam::Mat3::from(mat.transpose()), g_mat.transpose());
+        assert!((mat.determinant() - g_mat.determinant()).abs() < 1e-5);
+        assert!(glam::Mat3::from(mat.inverse()).abs_diff_eq(g_mat.inverse(), 1e-6));
+        assert!(glam::Mat3::from(mat.mul_mat(&mat.inverse())).abs_diff_eq(glam::Mat3::IDENTITY, 1e-6));
+
+        assert_eq!(glam::Mat3::from(Mat3::from(mat2())), glam::Mat3::from_mat2(glam::Mat2::from(mat2())));
+        assert_eq!(glam::Mat4::from(Mat4::from(mat)), glam::Mat4::from_mat3(g_mat));
+    }
+
+    #[test]
+    fn normal_matrix() {
+        let model = srt(Vec3::new(2.0, 0.5, 3.0));
+        let g_normal = glam::Mat3::from_mat4(model.into()).inverse().transpose();
+        assert!(glam::Mat3::from(Mat3::normal_matrix(&model)).abs_diff_eq(g_normal, 1e-5));
+
+        // normals stay perpendicular to the transformed surface under non-uniform scale
+        let (tangent, normal) = (Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, -1.0, 0.0));
+        let tangent = Mat3::from_mat4(&model).mul_vec(&tangent);
+        let normal = Mat3::normal_matrix(&model).mul_vec(&normal);
+        assert!(tangent.dot(normal).abs() < 1e-5);
+    }
+
+    #[test]
+    fn affine_2d_against_glam() {
+        let (scale, angle, translation) = (Vec2::new(2.0, -0.5), 0.9, Vec2::new(3.0, -4.0));
+        let (g_scale, g_translation) = (glam::Vec2::from(scale), glam::Vec2::from(translation));
+
+        assert_eq!(glam::Mat3::from(Mat3::from_translation_2d(&translation)), glam::Mat3::from_translation(g_translation));
+        assert_eq!(glam::Mat3::from(Mat3::from_scale_2d(&scale)), glam::Mat3::from_scale(g_scale));
+        assert!(glam::Mat3::from(Mat3::from_angle_2d(angle)).abs_diff_eq(glam::Mat3::from_angle(angle), 1e-6));
+
+        let mat = Mat3::from_scale_angle_translation_2d(&scale, angle, &translation);
+        let g_mat = glam::Mat3::from_scale_angle_translation(g_scale, angle, g_translation);
+        assert!(glam::Mat3::from(mat).abs_diff_eq(g_mat, 1e-6));
+
+        // same order as the single transformations
+        let composed = Mat3::from_translation_2d(&translation)
+            .mul_mat(&Mat3::from_angle_2d(angle))
+            .mul_mat(&Mat3::from_scale_2d(&scale));
+        assert!(glam::Mat3::from(composed).abs_diff_eq(g_mat, 1e-6));
+
+        let point = Vec2::new(1.5, -2.0);
+        assert!(glam::Vec2::from(mat.transform_point_2d(&point)).abs_diff_eq(g_mat.transform_point2(point.into()), 1e-5));
+        assert!(glam::Vec2::from(mat.transform_vector_2d(&point)).abs_diff_eq(g_mat.transform_vector2(point.into()), 1e-5));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::random::Pcg32;
+
+
+
+
+// noise traits
+// ------------
+// all noise functions are deterministic for a given seed
+
+pub trait Noise2 {
+    fn sample2(&self, x: f32, y: f32) -> f32;
+}
+
+pub trait Noise3 {
+    fn sample3(&self, x: f32, y: f32, z: f32) -> f32;
+}
+
+pub trait Noise4 {
+    fn sample4(&self, x: f32, y: f32, z: f32, w: f32) -> f32;
+}
+
+// the noise repeats after 'period' units on each axis, a period of 0 is treated as 1
+pub trait TileableNoise2 {
+    fn sample2_tiled(&self, x: f32, y: f32, period: (u32, u32)) -> f32;
+}
+
+pub trait TileableNoise3 {
+    fn sample3_tiled(&self, x: f32, y: f32, z: f32, period: (u32, u32, u32)) -> f32;
+}
+
+
+
+// permutation table
+// -----------------
+
+// the shuffled values 0..256 twice in a row, so that nested lookups never have to wrap
+#[derive(Clone)]
+pub(crate) struct PermutationTable {
+    perm: [u8; 512],
+}
+
+impl PermutationTable {
+    pub fn new(seed: u64) -> Self {
+        let mut values: [u8; 256] = [0; 256];
+        values.iter_mut().enumerate().for_each(|(idx, v)| *v = idx as u8);
+        Pcg32::from_seed(seed).shuffle(&mut values);
+
+        let mut perm = [0; 512];
+        perm[..256].copy_from_slice(&values);
+        perm[256..].copy_from_slice(&values);
+
+        Self { perm }
+    }
+
+    #[inline]
+    pub fn get(&self, idx: usize) -> usize {
+        self.perm[idx] as usize
+    }
+
+    #[inline]
+    pub fn hash2(&self, x: usize, y: usize) -> usize {
+        self.get(self.get(x & 255) + (y & 255))
+    }
+
+    #[inline]
+    pub fn hash3(&self, x: usize, y: usize, z: usize) -> usize {
+        self.get(self.hash2(x, y) + (z & 255))
+    }
+
+    #[inline]
+    pub fn hash4(&self, x: usize, y: usize, z: usize, w: usize) -> usize {
+        self.get(self.hash3(x, y, z) + (w & 255))
+    }
+}
+
+
+
+// helpers
+// -------
+
+pub(crate) const GRAD_2: [[f32; 2]; 8] = [
+    [ 1.0,  1.0], [-1.0,  1.0], [ 1.0, -1.0], [-1.0, -1.0],
+    [ 1.0,  0.0], [-1.0,  0.0], [ 0.0,  1.0], [ 0.0, -1.0],
+];
+
+pub(crate) const GRAD_3: [[f32; 3]; 12] = [
+    [ 1.0,  1.0,  0.0], [-1.0,  1.0,  0.0], [ 1.0, -1.0,  0.0], [-1.0, -1.0,  0.0],
+    [ 1.0,  0.0,  1.0], [-1.0,  0.0,  1.0], [ 1.0,  0.0, -1.0], [-1.0,  0.0, -1.0],
+    [ 0.0,  1.0,  1.0], [ 0.0, -1.0,  1.0], [ 0.0,  1.0, -1.0], [ 0.0, -1.0, -1.0],
+];
+
+pub(crate) const GRAD_4: [[f32; 4]; 32] = [
+    [ 0.0,  1.0,  1.0,  1.0], [ 0.0,  1.0,  1.0, -1.0], [ 0.0,  1.0, -1.0,  1.0], [ 0.0,  1.0, -1.0, -1.0],
+    [ 0.0, -1.0,  1.0,  1.0], [ 0.0, -1.0,  1.0, -1.0], [ 0.0, -1.0, -1.0,  1.0], [ 0.0, -1.0, -1.0, -1.0],
+    [ 1.0,  0.0,  1.0,  1.0], [ 1.0,  0.0,  1.0, -1.0], [ 1.0,  0.0, -1.0,  1.0], [ 1.0,  0.0, -1.0, -1.0],
+    [-1.0,  0.0,  1.0,  1.0], [-1.0,  0.0,  1.0, -1.0], [-1.0,  0.0, -1.0,  1.0], [-1.0,  0.0, -1.0, -1.0],
+    [ 1.0,  1.0,  0.0,  1.0], [ 1.0,  1.0,  0.0, -1.0], [ 1.0, -1.0,  0.0,  1.0], [ 1.0, -1.0,  0.0, -1.0],
+    [-1.0,  1.0,  0.0,  1.0], [-1.0,  1.0,  0.0, -1.0], [-1.0, -1.0,  0.0,  1.0], [-1.0, -1.0,  0.0, -1.0],
+    [ 1.0,  1.0,  1.0,  0.0], [ 1.0,  1.0, -1.0,  0.0], [ 1.0, -1.0,  1.0,  0.0], [ 1.0, -1.0, -1.0,  0.0],
+    [-1.0,  1.0,  1.0,  0.0], [-1.0,  1.0, -1.0,  0.0], [-1.0, -1.0,  1.0,  0.0], [-1.0, -1.0, -1.0,  0.0],
+];
+
+// 6t^5 - 15t^4 + 10t^3
+#[inline]
+pub(crate) fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[inline]
+pub(crate) fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+// lattice coordinate, wrapped into [0, period) if a period is given, a period of 0 is treated as 1
+#[inline]
+pub(crate) fn wrap(val: i32, period: Option<u32>) -> usize {
+    match period {
+        Some(p) => (val as i64).rem_euclid(p.max(1) as i64) as usize,
+        None    => val as usize,
+    }
+}
+
+// https://nullprogram.com/blog/2018/07/31/ (lowbias32)
+#[inline]
+pub(crate) fn hash_u32(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846ca68b);
+    x ^= x >> 16;
+    x
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_periods() {
+        assert_eq!(wrap(-1, Some(4)), 3);
+        assert_eq!(wrap(9, Some(4)), 1);
+        assert_eq!(wrap(-3, None), -3_i32 as usize);
+    }
+
+    #[test]
+    fn wrap_degenerate_periods() {
+        assert_eq!(wrap(5, Some(0)), 0);
+        assert_eq!(wrap(-5, Some(1)), 0);
+        assert_eq!(wrap(-1, Some(u32::MAX)), u32::MAX as usize - 1);
+        assert_eq!(wrap(i32::MAX, Some(u32::MAX)), i32::MAX as usize);
+    }
+}
@@ -0,0 +1,191 @@
+use super::{Noise2, Noise3, Noise4, TileableNoise2, TileableNoise3};
+
+
+
+
+// sums up several octaves of a base noise with increasing frequency and decreasing amplitude
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fractal {
+    pub octaves: u32,
+    // frequency multiplier per octave
+    pub lacunarity: f32,
+    // amplitude multiplier per octave
+    pub gain: f32,
+}
+
+impl Default for Fractal {
+    fn default() -> Self {
+        Self {
+            octaves: 6,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl Fractal {
+    pub fn new(octaves: u32, lacunarity: f32, gain: f32) -> Self {
+        Self { octaves, lacunarity, gain }
+    }
+
+    // calls 'sample' with the frequency of every octave and sums up the weighted results
+    // the result is divided by the sum of all amplitudes
+    fn accumulate(&self, mut sample: impl FnMut(u32, f32) -> f32) -> f32 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut max_amplitude = 0.0;
+        let mut frequency = 1.0;
+
+        for octave in 0..self.octaves.max(1) {
+            sum += sample(octave, frequency) * amplitude;
+            max_amplitude += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+
+        sum / max_amplitude
+    }
+
+    // musgrave's ridged multifractal: every octave is weighted by the previous one
+    fn accumulate_ridged(&self, mut sample: impl FnMut(f32) -> f32) -> f32 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut max_amplitude = 0.0;
+        let mut frequency = 1.0;
+        let mut weight = 1.0;
+
+        for _ in 0..self.octaves.max(1) {
+            let signal = 1.0 - sample(frequency).abs();
+            let signal = signal * signal * weight;
+            weight = (signal * 2.0).clamp(0.0, 1.0);
+
+            sum += signal * amplitude;
+            max_amplitude += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+
+        sum / max_amplitude
+    }
+}
+
+// fbm
+// ---
+impl Fractal {
+    pub fn fbm2(&self, noise: &impl Noise2, x: f32, y: f32) -> f32 {
+        self.accumulate(|_, f| noise.sample2(x * f, y * f))
+    }
+
+    pub fn fbm3(&self, noise: &impl Noise3, x: f32, y: f32, z: f32) -> f32 {
+        self.accumulate(|_, f| noise.sample3(x * f, y * f, z * f))
+    }
+
+    pub fn fbm4(&self, noise: &impl Noise4, x: f32, y: f32, z: f32, w: f32) -> f32 {
+        self.accumulate(|_, f| noise.sample4(x * f, y * f, z * f, w * f))
+    }
+}
+
+// ridged
+// ------
+// results are in [0, 1]
+impl Fractal {
+    pub fn ridged2(&self, noise: &impl Noise2, x: f32, y: f32) -> f32 {
+        self.accumulate_ridged(|f| noise.sample2(x * f, y * f))
+    }
+
+    pub fn ridged3(&self, noise: &impl Noise3, x: f32, y: f32, z: f32) -> f32 {
+        self.accumulate_ridged(|f| noise.sample3(x * f, y * f, z * f))
+    }
+
+    pub fn ridged4(&self, noise: &impl Noise4, x: f32, y: f32, z: f32, w: f32) -> f32 {
+        self.accumulate_ridged(|f| noise.sample4(x * f, y * f, z * f, w * f))
+    }
+}
+
+// turbulence
+// ----------
+// sums up the absolute values of every octave, results are in [0, 1]
+impl Fractal {
+    pub fn turbulence2(&self, noise: &impl Noise2, x: f32, y: f32) -> f32 {
+        self.accumulate(|_, f| noise.sample2(x * f, y * f).abs())
+    }
+
+    pub fn turbulence3(&self, noise: &impl Noise3, x: f32, y: f32, z: f32) -> f32 {
+        self.accumulate(|_, f| noise.sample3(x * f, y * f, z * f).abs())
+    }
+
+    pub fn turbulence4(&self, noise: &impl Noise4, x: f32, y: f32, z: f32, w: f32) -> f32 {
+        self.accumulate(|_, f| noise.sample4(x * f, y * f, z * f, w * f).abs())
+    }
+}
+
+// tileable fbm
+// ------------
+// the period of every octave is scaled by the lacunarity, so it should be a whole number for the result to tile
+impl Fractal {
+    fn octave_period(&self, period: u32, octave: u32) -> u32 {
+        ((period as f32) * self.lacunarity.powi(octave as i32)).round().max(1.0) as u32
+    }
+
+    pub fn fbm2_tiled(&self, noise: &impl TileableNoise2, x: f32, y: f32, period: (u32, u32)) -> f32 {
+        self.accumulate(|o, f| {
+            let period = (self.octave_period(period.0, o), self.octave_period(period.1, o));
+            noise.sample2_tiled(x * f, y * f, period)
+        })
+    }
+
+    pub fn fbm3_tiled(&self, noise: &impl TileableNoise3, x: f32, y: f32, z: f32, period: (u32, u32, u32)) -> f32 {
+        self.accumulate(|o, f| {
+            let period = (self.octave_period(period.0, o), self.octave_period(period.1, o), self.octave_period(period.2, o));
+            noise.sample3_tiled(x * f, y * f, z * f, period)
+        })
+    }
+
+    pub fn turbulence2_tiled(&self, noise: &impl TileableNoise2, x: f32, y: f32, period: (u32, u32)) -> f32 {
+        self.accumulate(|o, f| {
+            let period = (self.octave_period(period.0, o), self.octave_period(period.1, o));
+            noise.sample2_tiled(x * f, y * f, period).abs()
+        })
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise::{Perlin, Worley};
+
+    #[test]
+    fn fixed_seed_fbm() {
+        let perlin = Perlin::new(42);
+        assert!((Fractal::default().fbm2(&perlin, 0.3, 1.7) - 0.28202248).abs() < 1e-6);
+        assert!((Fractal::default().fbm2(&perlin, 12.5, -3.25) - -0.043774802).abs() < 1e-6);
+    }
+
+    #[test]
+    fn single_octave_is_the_base_noise() {
+        let perlin = Perlin::new(42);
+        let fractal = Fractal::new(1, 2.0, 0.5);
+        assert_eq!(fractal.fbm2(&perlin, 0.3, 1.7), perlin.sample2(0.3, 1.7));
+        assert_eq!(fractal.turbulence3(&perlin, 0.3, 1.7, -2.2), perlin.sample3(0.3, 1.7, -2.2).abs());
+    }
+
+    #[test]
+    fn tiling() {
+        let fractal = Fractal::default();
+        let (perlin, worley) = (Perlin::new(42), Worley::new(42));
+        let (px, py, pz) = (4, 6, 5);
+
+        for &(x, y, z) in &[(0.3, 1.7, -2.2), (-1.6, 2.25, 0.9)] {
+            let fbm = fractal.fbm2_tiled(&perlin, x, y, (px, py));
+            assert!((fractal.fbm2_tiled(&perlin, x + px as f32, y - py as f32, (px, py)) - fbm).abs() < 1e-5);
+
+            let fbm = fractal.fbm3_tiled(&worley, x, y, z, (px, py, pz));
+            assert!((fractal.fbm3_tiled(&worley, x, y, z + pz as f32, (px, py, pz)) - fbm).abs() < 1e-5);
+
+            let turbulence = fractal.turbulence2_tiled(&perlin, x, y, (px, py));
+            assert!((fractal.turbulence2_tiled(&perlin, x - px as f32, y, (px, py)) - turbulence).abs() < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+mod common;
+pub use common::*;
+
+mod perlin;
+pub use perlin::*;
+
+mod simplex;
+pub use simplex::*;
+
+mod worley;
+pub use worley::*;
+
+mod fractal;
+pub use fractal::*;
@@ -0,0 +1,196 @@
+use super::{Noise2, Noise3, Noise4, TileableNoise2, TileableNoise3, PermutationTable, GRAD_2, GRAD_3, GRAD_4, fade, lerp, wrap};
+
+
+
+
+// improved gradient noise (perlin 2002), results are roughly in [-1, 1]
+#[derive(Clone)]
+pub struct Perlin {
+    perm: PermutationTable,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            perm: PermutationTable::new(seed),
+        }
+    }
+}
+
+impl Perlin {
+    // periods are limited to 256
+    fn perlin2(&self, x: f32, y: f32, period: Option<(u32, u32)>) -> f32 {
+        let (xf, yf) = (x.floor(), y.floor());
+        let (xi, yi) = (xf as i32, yf as i32);
+        let (x, y) = (x - xf, y - yf);
+
+        let (px, py) = (period.map(|p| p.0), period.map(|p| p.1));
+        let (x0, x1) = (wrap(xi, px), wrap(xi + 1, px));
+        let (y0, y1) = (wrap(yi, py), wrap(yi + 1, py));
+
+        let grad = |hash: usize, dx: f32, dy: f32| {
+            let g = GRAD_2[hash & 7];
+            g[0] * dx + g[1] * dy
+        };
+
+        let n00 = grad(self.perm.hash2(x0, y0), x,       y);
+        let n10 = grad(self.perm.hash2(x1, y0), x - 1.0, y);
+        let n01 = grad(self.perm.hash2(x0, y1), x,       y - 1.0);
+        let n11 = grad(self.perm.hash2(x1, y1), x - 1.0, y - 1.0);
+
+        let (u, v) = (fade(x), fade(y));
+        lerp(lerp(n00, n10, u), lerp(n01, n11, u), v)
+    }
+
+    fn perlin3(&self, x: f32, y: f32, z: f32, period: Option<(u32, u32, u32)>) -> f32 {
+        let (xf, yf, zf) = (x.floor(), y.floor(), z.floor());
+        let (xi, yi, zi) = (xf as i32, yf as i32, zf as i32);
+        let (x, y, z) = (x - xf, y - yf, z - zf);
+
+        let (px, py, pz) = (period.map(|p| p.0), period.map(|p| p.1), period.map(|p| p.2));
+        let (x0, x1) = (wrap(xi, px), wrap(xi + 1, px));
+        let (y0, y1) = (wrap(yi, py), wrap(yi + 1, py));
+        let (z0, z1) = (wrap(zi, pz), wrap(zi + 1, pz));
+
+        let grad = |hash: usize, dx: f32, dy: f32, dz: f32| {
+            let g = GRAD_3[hash % 12];
+            g[0] * dx + g[1] * dy + g[2] * dz
+        };
+
+        let n000 = grad(self.perm.hash3(x0, y0, z0), x,       y,       z);
+        let n100 = grad(self.perm.hash3(x1, y0, z0), x - 1.0, y,       z);
+        let n010 = grad(self.perm.hash3(x0, y1, z0), x,       y - 1.0, z);
+        let n110 = grad(self.perm.hash3(x1, y1, z0), x - 1.0, y - 1.0, z);
+        let n001 = grad(self.perm.hash3(x0, y0, z1), x,       y,       z - 1.0);
+        let n101 = grad(self.perm.hash3(x1, y0, z1), x - 1.0, y,       z - 1.0);
+        let n011 = grad(self.perm.hash3(x0, y1, z1), x,       y - 1.0, z - 1.0);
+        let n111 = grad(self.perm.hash3(x1, y1, z1), x - 1.0, y - 1.0, z - 1.0);
+
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        lerp(
+            lerp(lerp(n000, n100, u), lerp(n010, n110, u), v),
+            lerp(lerp(n001, n101, u), lerp(n011, n111, u), v),
+            w
+        )
+    }
+
+    fn perlin4(&self, x: f32, y: f32, z: f32, w: f32) -> f32 {
+        let (xf, yf, zf, wf) = (x.floor(), y.floor(), z.floor(), w.floor());
+        let base = [xf as i32 as usize, yf as i32 as usize, zf as i32 as usize, wf as i32 as usize];
+        let local = [x - xf, y - yf, z - zf, w - wf];
+
+        // interpolate over the 16 corners of the hypercube
+        let mut values = [0.0_f32; 16];
+        for (corner, value) in values.iter_mut().enumerate() {
+            let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1, (corner >> 3) & 1];
+            let hash = self.perm.hash4(
+                base[0].wrapping_add(offset[0]),
+                base[1].wrapping_add(offset[1]),
+                base[2].wrapping_add(offset[2]),
+                base[3].wrapping_add(offset[3]),
+            );
+
+            let g = GRAD_4[hash & 31];
+            *value = (0..4).map(|i| g[i] * (local[i] - offset[i] as f32)).sum();
+        }
+
+        let mut count = 16;
+        for l in local {
+            let t = fade(l);
+            count /= 2;
+            for idx in 0..count {
+                values[idx] = lerp(values[idx * 2], values[idx * 2 + 1], t);
+            }
+        }
+
+        values[0]
+    }
+}
+
+impl Noise2 for Perlin {
+    fn sample2(&self, x: f32, y: f32) -> f32 {
+        self.perlin2(x, y, None)
+    }
+}
+
+impl Noise3 for Perlin {
+    fn sample3(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.perlin3(x, y, z, None)
+    }
+}
+
+impl Noise4 for Perlin {
+    fn sample4(&self, x: f32, y: f32, z: f32, w: f32) -> f32 {
+        self.perlin4(x, y, z, w)
+    }
+}
+
+impl TileableNoise2 for Perlin {
+    fn sample2_tiled(&self, x: f32, y: f32, period: (u32, u32)) -> f32 {
+        self.perlin2(x, y, Some(period))
+    }
+}
+
+impl TileableNoise3 for Perlin {
+    fn sample3_tiled(&self, x: f32, y: f32, z: f32, period: (u32, u32, u32)) -> f32 {
+        self.perlin3(x, y, z, Some(period))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn fixed_seed_samples() {
+        let perlin = Perlin::new(42);
+        assert_close(perlin.sample2(0.3, 1.7), 0.22874695);
+        assert_close(perlin.sample3(0.3, 1.7, -2.2), -0.089125335);
+        assert_close(perlin.sample4(0.3, 1.7, -2.2, 0.4), -0.074650764);
+        assert_close(perlin.sample2(12.5, -3.25), -0.21118164);
+        assert_close(perlin.sample3(12.5, -3.25, 0.75), -0.18790293);
+        assert_close(perlin.sample4(12.5, -3.25, 0.75, 0.4), 0.5127177);
+    }
+
+    #[test]
+    fn zero_at_lattice_points() {
+        let perlin = Perlin::new(7);
+        assert_eq!(perlin.sample2(3.0, -5.0), 0.0);
+        assert_eq!(perlin.sample3(3.0, -5.0, 11.0), 0.0);
+        assert_eq!(perlin.sample4(3.0, -5.0, 11.0, 2.0), 0.0);
+    }
+
+    #[test]
+    fn different_seeds() {
+        assert_ne!(Perlin::new(1).sample2(0.3, 1.7), Perlin::new(2).sample2(0.3, 1.7));
+    }
+
+    #[test]
+    fn tiling() {
+        let perlin = Perlin::new(42);
+        let (px, py, pz) = (4, 7, 3);
+
+        for &(x, y, z) in &[(0.3, 1.7, -2.2), (-5.6, 2.25, 0.9), (3.9, -0.1, 8.4)] {
+            let tiled2 = perlin.sample2_tiled(x, y, (px, py));
+            assert_close(perlin.sample2_tiled(x + px as f32, y, (px, py)), tiled2);
+            assert_close(perlin.sample2_tiled(x, y - py as f32, (px, py)), tiled2);
+
+            let tiled3 = perlin.sample3_tiled(x, y, z, (px, py, pz));
+            assert_close(perlin.sample3_tiled(x - px as f32, y, z, (px, py, pz)), tiled3);
+            assert_close(perlin.sample3_tiled(x, y + py as f32, z + 2.0 * pz as f32, (px, py, pz)), tiled3);
+        }
+    }
+
+    #[test]
+    fn degenerate_periods() {
+        let perlin = Perlin::new(42);
+        assert!(perlin.sample2_tiled(0.3, 1.7, (0, 0)).is_finite());
+        assert!(perlin.sample3_tiled(0.3, 1.7, -2.2, (0, u32::MAX, 1)).is_finite());
+    }
+}
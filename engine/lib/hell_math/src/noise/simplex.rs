@@ -0,0 +1,183 @@
+use super::{Noise2, Noise3, Noise4, PermutationTable, GRAD_3, GRAD_4};
+
+
+
+
+// simplex noise based on 'simplex noise demystified' (gustavson 2005), results are roughly in [-1, 1]
+// there is no tileable variant: the lattice is skewed, so its cells never line up with an axis-aligned period
+// for tiling 2d noise, sample 'Noise4' on a torus, i.e. map x and y to circles in the xz and yw planes
+#[derive(Clone)]
+pub struct Simplex {
+    perm: PermutationTable,
+}
+
+impl Simplex {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            perm: PermutationTable::new(seed),
+        }
+    }
+}
+
+// skewing factors
+// ---------------
+impl Simplex {
+    // (sqrt(3) - 1) / 2
+    const F2: f32 = 0.366_025_42;
+    // (3 - sqrt(3)) / 6
+    const G2: f32 = 0.211_324_87;
+    const F3: f32 = 1.0 / 3.0;
+    const G3: f32 = 1.0 / 6.0;
+    // (sqrt(5) - 1) / 4
+    const F4: f32 = 0.309_017;
+    // (5 - sqrt(5)) / 20
+    const G4: f32 = 0.138_196_6;
+}
+
+// contribution of a single simplex corner
+#[inline]
+fn corner(radius: f32, offset: &[f32], grad: &[f32]) -> f32 {
+    let t = radius - offset.iter().map(|o| o * o).sum::<f32>();
+    if t < 0.0 {
+        return 0.0;
+    }
+
+    let t = t * t;
+    t * t * offset.iter().zip(grad).map(|(o, g)| o * g).sum::<f32>()
+}
+
+impl Noise2 for Simplex {
+    fn sample2(&self, x: f32, y: f32) -> f32 {
+        let s = (x + y) * Self::F2;
+        let (i, j) = ((x + s).floor(), (y + s).floor());
+        let t = (i + j) * Self::G2;
+        let (x0, y0) = (x - (i - t), y - (j - t));
+
+        // the lower or the upper triangle of the skewed square
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let (x1, y1) = (x0 - i1 as f32 + Self::G2, y0 - j1 as f32 + Self::G2);
+        let (x2, y2) = (x0 - 1.0 + 2.0 * Self::G2, y0 - 1.0 + 2.0 * Self::G2);
+
+        let (ii, jj) = (i as i32 as usize, j as i32 as usize);
+        let g0 = self.perm.hash2(ii, jj) % 12;
+        let g1 = self.perm.hash2(ii.wrapping_add(i1), jj.wrapping_add(j1)) % 12;
+        let g2 = self.perm.hash2(ii.wrapping_add(1), jj.wrapping_add(1)) % 12;
+
+        let n0 = corner(0.5, &[x0, y0], &GRAD_3[g0][..2]);
+        let n1 = corner(0.5, &[x1, y1], &GRAD_3[g1][..2]);
+        let n2 = corner(0.5, &[x2, y2], &GRAD_3[g2][..2]);
+
+        70.0 * (n0 + n1 + n2)
+    }
+}
+
+impl Noise3 for Simplex {
+    fn sample3(&self, x: f32, y: f32, z: f32) -> f32 {
+        let s = (x + y + z) * Self::F3;
+        let (i, j, k) = ((x + s).floor(), (y + s).floor(), (z + s).floor());
+        let t = (i + j + k) * Self::G3;
+        let (x0, y0, z0) = (x - (i - t), y - (j - t), z - (k - t));
+
+        // which of the six tetrahedra the point is in
+        let (o1, o2) = if x0 >= y0 {
+            if y0 >= z0      { ([1, 0, 0], [1, 1, 0]) }
+            else if x0 >= z0 { ([1, 0, 0], [1, 0, 1]) }
+            else             { ([0, 0, 1], [1, 0, 1]) }
+        } else if y0 < z0    { ([0, 0, 1], [0, 1, 1]) }
+        else if x0 < z0      { ([0, 1, 0], [0, 1, 1]) }
+        else                 { ([0, 1, 0], [1, 1, 0]) };
+
+        let p0 = [x0, y0, z0];
+        let p1 = [x0 - o1[0] as f32 + Self::G3, y0 - o1[1] as f32 + Self::G3, z0 - o1[2] as f32 + Self::G3];
+        let p2 = [x0 - o2[0] as f32 + 2.0 * Self::G3, y0 - o2[1] as f32 + 2.0 * Self::G3, z0 - o2[2] as f32 + 2.0 * Self::G3];
+        let p3 = [x0 - 1.0 + 3.0 * Self::G3, y0 - 1.0 + 3.0 * Self::G3, z0 - 1.0 + 3.0 * Self::G3];
+
+        let (ii, jj, kk) = (i as i32 as usize, j as i32 as usize, k as i32 as usize);
+        let hash = |o: [usize; 3]| self.perm.hash3(ii.wrapping_add(o[0]), jj.wrapping_add(o[1]), kk.wrapping_add(o[2])) % 12;
+
+        let n0 = corner(0.6, &p0, &GRAD_3[hash([0, 0, 0])]);
+        let n1 = corner(0.6, &p1, &GRAD_3[hash(o1)]);
+        let n2 = corner(0.6, &p2, &GRAD_3[hash(o2)]);
+        let n3 = corner(0.6, &p3, &GRAD_3[hash([1, 1, 1])]);
+
+        32.0 * (n0 + n1 + n2 + n3)
+    }
+}
+
+impl Noise4 for Simplex {
+    fn sample4(&self, x: f32, y: f32, z: f32, w: f32) -> f32 {
+        let s = (x + y + z + w) * Self::F4;
+        let cell = [(x + s).floor(), (y + s).floor(), (z + s).floor(), (w + s).floor()];
+        let t = cell.iter().sum::<f32>() * Self::G4;
+        let p0 = [x - (cell[0] - t), y - (cell[1] - t), z - (cell[2] - t), w - (cell[3] - t)];
+
+        // rank the coordinates to find out which of the 24 simplices the point is in
+        let mut rank = [0; 4];
+        for a in 0..4 {
+            for b in (a + 1)..4 {
+                if p0[a] > p0[b] { rank[a] += 1; } else { rank[b] += 1; }
+            }
+        }
+
+        let base = cell.map(|c| c as i32 as usize);
+        let mut result = 0.0;
+
+        // corner 0 is the cell origin, corner 4 is the opposite corner
+        for step in 0..5 {
+            let offset = rank.map(|r| usize::from(r >= 4 - step));
+            let g = Self::G4 * step as f32;
+            let p = [
+                p0[0] - offset[0] as f32 + g,
+                p0[1] - offset[1] as f32 + g,
+                p0[2] - offset[2] as f32 + g,
+                p0[3] - offset[3] as f32 + g,
+            ];
+
+            let hash = self.perm.hash4(
+                base[0].wrapping_add(offset[0]),
+                base[1].wrapping_add(offset[1]),
+                base[2].wrapping_add(offset[2]),
+                base[3].wrapping_add(offset[3]),
+            );
+
+            result += corner(0.6, &p, &GRAD_4[hash & 31]);
+        }
+
+        27.0 * result
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn fixed_seed_samples() {
+        let simplex = Simplex::new(42);
+        assert_close(simplex.sample2(0.3, 1.7), -0.2751434);
+        assert_close(simplex.sample3(0.3, 1.7, -2.2), -0.077729955);
+        assert_close(simplex.sample4(0.3, 1.7, -2.2, 0.4), -0.09842409);
+        assert_close(simplex.sample2(12.5, -3.25), 0.8448828);
+        assert_close(simplex.sample3(12.5, -3.25, 0.75), -0.77743524);
+        assert_close(simplex.sample4(12.5, -3.25, 0.75, 0.4), 0.7553115);
+    }
+
+    #[test]
+    fn range() {
+        let simplex = Simplex::new(3);
+        for i in 0..1000 {
+            let t = i as f32 * 0.173;
+            let (x, y, z, w) = (t, t * 0.7 - 20.0, 5.0 - t * 1.3, t * 0.31);
+            assert!(simplex.sample2(x, y).abs() <= 1.0);
+            assert!(simplex.sample3(x, y, z).abs() <= 1.0);
+            assert!(simplex.sample4(x, y, z, w).abs() <= 1.0);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use super::{Noise2, Noise3, TileableNoise2, TileableNoise3, hash_u32, wrap};
+
+
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorleyDistance {
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+// f1 is the distance to the closest feature point, f2 the distance to the second closest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorleyReturn {
+    F1,
+    F2,
+    F2MinusF1,
+}
+
+// cellular noise with one feature point per unit cell, results are >= 0 and mostly below 1.5
+#[derive(Debug, Clone, Copy)]
+pub struct Worley {
+    seed: u32,
+    pub distance: WorleyDistance,
+    pub ret: WorleyReturn,
+}
+
+impl Worley {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed: hash_u32(seed as u32 ^ hash_u32((seed >> 32) as u32)),
+            distance: WorleyDistance::Euclidean,
+            ret: WorleyReturn::F1,
+        }
+    }
+
+    pub fn with_distance(mut self, distance: WorleyDistance) -> Self {
+        self.distance = distance;
+        self
+    }
+
+    pub fn with_return(mut self, ret: WorleyReturn) -> Self {
+        self.ret = ret;
+        self
+    }
+}
+
+impl Worley {
+    fn cell_hash(&self, cell: &[usize]) -> u32 {
+        cell.iter().fold(self.seed, |acc, c| hash_u32(acc ^ *c as u32))
+    }
+
+    // position of the feature point inside the cell, each component in [0, 1)
+    fn feature_point<const N: usize>(&self, cell: &[usize; N]) -> [f32; N] {
+        let mut hash = self.cell_hash(cell);
+        [0; N].map(|_| {
+            hash = hash_u32(hash);
+            (hash >> 8) as f32 / (1 << 24) as f32
+        })
+    }
+
+    fn distance(&self, delta: &[f32]) -> f32 {
+        match self.distance {
+            WorleyDistance::Euclidean => delta.iter().map(|d| d * d).sum::<f32>().sqrt(),
+            WorleyDistance::Manhattan => delta.iter().map(|d| d.abs()).sum(),
+            WorleyDistance::Chebyshev => delta.iter().fold(0.0, |acc, d| d.abs().max(acc)),
+        }
+    }
+
+    fn result(&self, f1: f32, f2: f32) -> f32 {
+        match self.ret {
+            WorleyReturn::F1        => f1,
+            WorleyReturn::F2        => f2,
+            WorleyReturn::F2MinusF1 => f2 - f1,
+        }
+    }
+
+    fn worley2(&self, x: f32, y: f32, period: Option<(u32, u32)>) -> f32 {
+        let (xi, yi) = (x.floor() as i32, y.floor() as i32);
+        let (mut f1, mut f2) = (f32::MAX, f32::MAX);
+
+        for oy in -1..=1 {
+            for ox in -1..=1 {
+                let (cx, cy) = (xi + ox, yi + oy);
+                let cell = [wrap(cx, period.map(|p| p.0)), wrap(cy, period.map(|p| p.1))];
+                let point = self.feature_point(&cell);
+                let dist = self.distance(&[cx as f32 + point[0] - x, cy as f32 + point[1] - y]);
+
+                if dist < f1 {
+                    f2 = f1;
+                    f1 = dist;
+                } else if dist < f2 {
+                    f2 = dist;
+                }
+            }
+        }
+
+        self.result(f1, f2)
+    }
+
+    fn worley3(&self, x: f32, y: f32, z: f32, period: Option<(u32, u32, u32)>) -> f32 {
+        let (xi, yi, zi) = (x.floor() as i32, y.floor() as i32, z.floor() as i32);
+        let (mut f1, mut f2) = (f32::MAX, f32::MAX);
+
+        for oz in -1..=1 {
+            for oy in -1..=1 {
+                for ox in -1..=1 {
+                    let (cx, cy, cz) = (xi + ox, yi + oy, zi + oz);
+                    let cell = [
+                        wrap(cx, period.map(|p| p.0)),
+                        wrap(cy, period.map(|p| p.1)),
+                        wrap(cz, period.map(|p| p.2)),
+                    ];
+                    let point = self.feature_point(&cell);
+                    let dist = self.distance(&[
+                        cx as f32 + point[0] - x,
+                        cy as f32 + point[1] - y,
+                        cz as f32 + point[2] - z,
+                    ]);
+
+                    if dist < f1 {
+                        f2 = f1;
+                        f1 = dist;
+                    } else if dist < f2 {
+                        f2 = dist;
+                    }
+                }
+            }
+        }
+
+        self.result(f1, f2)
+    }
+}
+
+impl Noise2 for Worley {
+    fn sample2(&self, x: f32, y: f32) -> f32 {
+        self.worley2(x, y, None)
+    }
+}
+
+impl Noise3 for Worley {
+    fn sample3(&self, x: f32, y: f32, z: f32) -> f32 {
+        self.worley3(x, y, z, None)
+    }
+}
+
+impl TileableNoise2 for Worley {
+    fn sample2_tiled(&self, x: f32, y: f32, period: (u32, u32)) -> f32 {
+        self.worley2(x, y, Some(period))
+    }
+}
+
+impl TileableNoise3 for Worley {
+    fn sample3_tiled(&self, x: f32, y: f32, z: f32, period: (u32, u32, u32)) -> f32 {
+        self.worley3(x, y, z, Some(period))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn fixed_seed_samples() {
+        let worley = Worley::new(42);
+        assert_close(worley.sample2(0.3, 1.7), 0.24077715);
+        assert_close(worley.sample3(0.3, 1.7, -2.2), 0.2686968);
+        assert_close(worley.sample2(12.5, -3.25), 0.42284375);
+        assert_close(worley.sample3(12.5, -3.25, 0.75), 0.24627516);
+    }
+
+    #[test]
+    fn distances() {
+        let worley = Worley::new(42).with_return(WorleyReturn::F2MinusF1);
+        assert!(worley.sample2(0.3, 1.7) >= 0.0);
+
+        let euclidean = Worley::new(42).sample2(0.3, 1.7);
+        let chebyshev = Worley::new(42).with_distance(WorleyDistance::Chebyshev).sample2(0.3, 1.7);
+        let manhattan = Worley::new(42).with_distance(WorleyDistance::Manhattan).sample2(0.3, 1.7);
+        assert!(chebyshev <= euclidean && euclidean <= manhattan);
+    }
+
+    #[test]
+    fn tiling() {
+        let worley = Worley::new(42);
+        let (px, py, pz) = (3, 5, 4);
+
+        for &(x, y, z) in &[(0.3, 1.7, -2.2), (-5.6, 2.25, 0.9), (3.9, -0.1, 8.4)] {
+            let tiled2 = worley.sample2_tiled(x, y, (px, py));
+            assert_close(worley.sample2_tiled(x + px as f32, y, (px, py)), tiled2);
+            assert_close(worley.sample2_tiled(x, y - py as f32, (px, py)), tiled2);
+
+            let tiled3 = worley.sample3_tiled(x, y, z, (px, py, pz));
+            assert_close(worley.sample3_tiled(x - px as f32, y, z, (px, py, pz)), tiled3);
+            assert_close(worley.sample3_tiled(x, y + py as f32, z + pz as f32, (px, py, pz)), tiled3);
+        }
+    }
+
+    #[test]
+    fn degenerate_periods() {
+        let worley = Worley::new(42);
+        assert!(worley.sample2_tiled(0.3, 1.7, (0, 0)).is_finite());
+        assert!(worley.sample3_tiled(0.3, 1.7, -2.2, (u32::MAX, 0, 1)).is_finite());
+    }
+}
@@ -0,0 +1,304 @@
+use crate::vec::{Vec3, Vec4};
+use crate::mat::Mat4;
+
+
+
+
+// ----------------------------------------------------------------------------
+// clip space
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthRange {
+    // vulkan, directx, metal
+    ZeroToOne,
+    // opengl
+    NegOneToOne,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClipSpace {
+    pub y_down: bool,
+    pub depth: DepthRange,
+    // near plane maps to the far end of the depth range
+    pub reverse_z: bool,
+}
+
+impl ClipSpace {
+    pub const VULKAN: ClipSpace = ClipSpace::new(true, DepthRange::ZeroToOne, false);
+    pub const VULKAN_REVERSE_Z: ClipSpace = ClipSpace::new(true, DepthRange::ZeroToOne, true);
+    pub const DIRECTX: ClipSpace = ClipSpace::new(false, DepthRange::ZeroToOne, false);
+    pub const OPENGL: ClipSpace = ClipSpace::new(false, DepthRange::NegOneToOne, false);
+}
+
+impl ClipSpace {
+    pub const fn new(y_down: bool, depth: DepthRange, reverse_z: bool) -> Self {
+        Self { y_down, depth, reverse_z }
+    }
+
+    pub const fn with_reverse_z(mut self, reverse_z: bool) -> Self {
+        self.reverse_z = reverse_z;
+        self
+    }
+
+    // expects a projection that maps y-up and depth into [0, 1]
+    fn apply(&self, mut proj: Mat4) -> Mat4 {
+        for col in 0..4 {
+            // z' = w - z
+            if self.reverse_z {
+                proj[col].z = proj[col].w - proj[col].z;
+            }
+
+            // z' = 2z - w
+            if self.depth == DepthRange::NegOneToOne {
+                proj[col].z = 2.0 * proj[col].z - proj[col].w;
+            }
+
+            if self.y_down {
+                proj[col].y = -proj[col].y;
+            }
+        }
+
+        proj
+    }
+}
+
+impl Default for ClipSpace {
+    fn default() -> Self {
+        Self::VULKAN
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// projections
+// ----------------------------------------------------------------------------
+
+// all angles are in radians
+impl Mat4 {
+    pub fn perspective_rh(vertical_fov: f32, aspect_ratio: f32, z_near: f32, z_far: f32, clip: ClipSpace) -> Self {
+        let focal_length = 1.0 / (vertical_fov * 0.5).tan();
+        let range = z_far / (z_near - z_far);
+
+        clip.apply(Self::from_cols(
+            Vec4::new(focal_length / aspect_ratio, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, focal_length, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, range, -1.0),
+            Vec4::new(0.0, 0.0, range * z_near, 0.0),
+        ))
+    }
+
+    pub fn perspective_lh(vertical_fov: f32, aspect_ratio: f32, z_near: f32, z_far: f32, clip: ClipSpace) -> Self {
+        let focal_length = 1.0 / (vertical_fov * 0.5).tan();
+        let range = z_far / (z_far - z_near);
+
+        clip.apply(Self::from_cols(
+            Vec4::new(focal_length / aspect_ratio, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, focal_length, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, range, 1.0),
+            Vec4::new(0.0, 0.0, -range * z_near, 0.0),
+        ))
+    }
+
+    pub fn perspective_infinite_rh(vertical_fov: f32, aspect_ratio: f32, z_near: f32, clip: ClipSpace) -> Self {
+        let focal_length = 1.0 / (vertical_fov * 0.5).tan();
+
+        clip.apply(Self::from_cols(
+            Vec4::new(focal_length / aspect_ratio, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, focal_length, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, -1.0, -1.0),
+            Vec4::new(0.0, 0.0, -z_near, 0.0),
+        ))
+    }
+
+    pub fn perspective_infinite_lh(vertical_fov: f32, aspect_ratio: f32, z_near: f32, clip: ClipSpace) -> Self {
+        let focal_length = 1.0 / (vertical_fov * 0.5).tan();
+
+        clip.apply(Self::from_cols(
+            Vec4::new(focal_length / aspect_ratio, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, focal_length, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, 1.0, 1.0),
+            Vec4::new(0.0, 0.0, -z_near, 0.0),
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn orthographic_rh(left: f32, right: f32, bottom: f32, top: f32, z_near: f32, z_far: f32, clip: ClipSpace) -> Self {
+        let rcp_width = 1.0 / (right - left);
+        let rcp_height = 1.0 / (top - bottom);
+        let range = 1.0 / (z_near - z_far);
+
+        clip.apply(Self::from_cols(
+            Vec4::new(2.0 * rcp_width, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, 2.0 * rcp_height, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, range, 0.0),
+            Vec4::new(
+                -(right + left) * rcp_width,
+                -(top + bottom) * rcp_height,
+                range * z_near,
+                1.0
+            ),
+        ))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn orthographic_lh(left: f32, right: f32, bottom: f32, top: f32, z_near: f32, z_far: f32, clip: ClipSpace) -> Self {
+        let rcp_width = 1.0 / (right - left);
+        let rcp_height = 1.0 / (top - bottom);
+        let range = 1.0 / (z_far - z_near);
+
+        clip.apply(Self::from_cols(
+            Vec4::new(2.0 * rcp_width, 0.0, 0.0, 0.0),
+            Vec4::new(0.0, 2.0 * rcp_height, 0.0, 0.0),
+            Vec4::new(0.0, 0.0, range, 0.0),
+            Vec4::new(
+                -(right + left) * rcp_width,
+                -(top + bottom) * rcp_height,
+                -range * z_near,
+                1.0
+            ),
+        ))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// views
+// ----------------------------------------------------------------------------
+
+impl Mat4 {
+    // camera looks along -z
+    pub fn look_to_rh(eye: &Vec3, direction: &Vec3, up: &Vec3) -> Self {
+        let f = direction.unit();
+        let s = f.cross(up).unit();
+        let u = s.cross(&f);
+
+        Self::from_cols(
+            Vec4::new(s.x, u.x, -f.x, 0.0),
+            Vec4::new(s.y, u.y, -f.y, 0.0),
+            Vec4::new(s.z, u.z, -f.z, 0.0),
+            Vec4::new(-s.dot(*eye), -u.dot(*eye), f.dot(*eye), 1.0),
+        )
+    }
+
+    // camera looks along +z
+    pub fn look_to_lh(eye: &Vec3, direction: &Vec3, up: &Vec3) -> Self {
+        let f = direction.unit();
+        let s = up.cross(&f).unit();
+        let u = f.cross(&s);
+
+        Self::from_cols(
+            Vec4::new(s.x, u.x, f.x, 0.0),
+            Vec4::new(s.y, u.y, f.y, 0.0),
+            Vec4::new(s.z, u.z, f.z, 0.0),
+            Vec4::new(-s.dot(*eye), -u.dot(*eye), -f.dot(*eye), 1.0),
+        )
+    }
+
+    pub fn look_at_target_rh(eye: &Vec3, target: &Vec3, up: &Vec3) -> Self {
+        Self::look_to_rh(eye, &(*target - *eye), up)
+    }
+
+    pub fn look_at_target_lh(eye: &Vec3, target: &Vec3, up: &Vec3) -> Self {
+        Self::look_to_lh(eye, &(*target - *eye), up)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOV: f32 = 1.2;
+    const ASPECT: f32 = 16.0 / 9.0;
+    const NEAR: f32 = 0.1;
+    const FAR: f32 = 100.0;
+
+    fn assert_mat_eq(lhs: Mat4, rhs: glam::Mat4) {
+        let lhs = glam::Mat4::from(lhs).to_cols_array();
+        let rhs = rhs.to_cols_array();
+        for (a, b) in lhs.iter().zip(rhs.iter()) {
+            assert!((a - b).abs() <= 1e-5 * a.abs().max(1.0), "{:?} != {:?}", lhs, rhs);
+        }
+    }
+
+    fn flip_y(mut mat: glam::Mat4) -> glam::Mat4 {
+        mat.x_axis.y = -mat.x_axis.y;
+        mat.y_axis.y = -mat.y_axis.y;
+        mat.z_axis.y = -mat.z_axis.y;
+        mat.w_axis.y = -mat.w_axis.y;
+        mat
+    }
+
+    #[test]
+    fn perspective() {
+        assert_mat_eq(Mat4::perspective_rh(FOV, ASPECT, NEAR, FAR, ClipSpace::DIRECTX), glam::Mat4::perspective_rh(FOV, ASPECT, NEAR, FAR));
+        assert_mat_eq(Mat4::perspective_lh(FOV, ASPECT, NEAR, FAR, ClipSpace::DIRECTX), glam::Mat4::perspective_lh(FOV, ASPECT, NEAR, FAR));
+        assert_mat_eq(Mat4::perspective_rh(FOV, ASPECT, NEAR, FAR, ClipSpace::OPENGL), glam::Mat4::perspective_rh_gl(FOV, ASPECT, NEAR, FAR));
+        assert_mat_eq(Mat4::perspective_rh(FOV, ASPECT, NEAR, FAR, ClipSpace::VULKAN), flip_y(glam::Mat4::perspective_rh(FOV, ASPECT, NEAR, FAR)));
+        assert_mat_eq(Mat4::perspective_lh(FOV, ASPECT, NEAR, FAR, ClipSpace::VULKAN), flip_y(glam::Mat4::perspective_lh(FOV, ASPECT, NEAR, FAR)));
+    }
+
+    #[test]
+    fn perspective_infinite() {
+        assert_mat_eq(Mat4::perspective_infinite_rh(FOV, ASPECT, NEAR, ClipSpace::DIRECTX), glam::Mat4::perspective_infinite_rh(FOV, ASPECT, NEAR));
+        assert_mat_eq(Mat4::perspective_infinite_lh(FOV, ASPECT, NEAR, ClipSpace::DIRECTX), glam::Mat4::perspective_infinite_lh(FOV, ASPECT, NEAR));
+
+        let reverse_z = ClipSpace::DIRECTX.with_reverse_z(true);
+        assert_mat_eq(Mat4::perspective_infinite_rh(FOV, ASPECT, NEAR, reverse_z), glam::Mat4::perspective_infinite_reverse_rh(FOV, ASPECT, NEAR));
+        assert_mat_eq(Mat4::perspective_infinite_lh(FOV, ASPECT, NEAR, reverse_z), glam::Mat4::perspective_infinite_reverse_lh(FOV, ASPECT, NEAR));
+    }
+
+    #[test]
+    fn reverse_z_depth() {
+        let proj = Mat4::perspective_rh(FOV, ASPECT, NEAR, FAR, ClipSpace::VULKAN_REVERSE_Z);
+        let depth = |z: f32| {
+            let clip = proj * Vec4::new(0.0, 0.0, z, 1.0);
+            clip.z / clip.w
+        };
+
+        assert!((depth(-NEAR) - 1.0).abs() < 1e-5);
+        assert!(depth(-FAR).abs() < 1e-5);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legacy_perspective() {
+        let legacy = Mat4::from_perspective_rh(60.0, ASPECT, NEAR, FAR);
+        let proj = Mat4::perspective_rh(60f32.to_radians(), ASPECT, NEAR, FAR, ClipSpace::VULKAN_REVERSE_Z);
+        assert_mat_eq(legacy, proj.into());
+    }
+
+    #[test]
+    fn orthographic() {
+        let (l, r, b, t) = (-3.0, 5.0, -2.0, 4.0);
+        assert_mat_eq(Mat4::orthographic_rh(l, r, b, t, NEAR, FAR, ClipSpace::DIRECTX), glam::Mat4::orthographic_rh(l, r, b, t, NEAR, FAR));
+        assert_mat_eq(Mat4::orthographic_lh(l, r, b, t, NEAR, FAR, ClipSpace::DIRECTX), glam::Mat4::orthographic_lh(l, r, b, t, NEAR, FAR));
+        assert_mat_eq(Mat4::orthographic_rh(l, r, b, t, NEAR, FAR, ClipSpace::OPENGL), glam::Mat4::orthographic_rh_gl(l, r, b, t, NEAR, FAR));
+    }
+
+    #[test]
+    fn views() {
+        let eye = Vec3::new(1.0, 2.0, 3.0);
+        let target = Vec3::new(-2.0, 0.5, 7.0);
+        let dir = Vec3::new(0.3, -0.2, 2.0);
+        let up = Vec3::new(0.0, 1.0, 0.0);
+
+        assert_mat_eq(Mat4::look_to_rh(&eye, &dir, &up), glam::Mat4::look_to_rh(eye.into(), glam::Vec3::from(dir).normalize(), up.into()));
+        assert_mat_eq(Mat4::look_to_lh(&eye, &dir, &up), glam::Mat4::look_to_lh(eye.into(), glam::Vec3::from(dir).normalize(), up.into()));
+        assert_mat_eq(Mat4::look_at_target_rh(&eye, &target, &up), glam::Mat4::look_at_rh(eye.into(), target.into(), up.into()));
+        assert_mat_eq(Mat4::look_at_target_lh(&eye, &target, &up), glam::Mat4::look_at_lh(eye.into(), target.into(), up.into()));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn legacy_look_at() {
+        let eye = Vec3::new(1.0, 2.0, 3.0);
+        let dir = Vec3::new(0.3, -0.2, 2.0).unit();
+        let up = Vec3::new(0.0, 1.0, 0.0);
+        assert_mat_eq(Mat4::look_at_rh(&eye, &dir, &up), Mat4::look_to_rh(&eye, &dir, &up).into());
+    }
+}
@@ -0,0 +1,467 @@
+use std::ops::{Mul, MulAssign, Neg};
+
+use crate::vec::{Vec3, Vec4};
+use crate::mat::Mat4;
+
+
+
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+// constants
+// ---------
+impl Quat {
+    pub const IDENTITY: Quat = Quat::from_xyzw(0.0, 0.0, 0.0, 1.0);
+
+    // dot products above this threshold are treated as 'the same rotation' by slerp
+    const SLERP_THRESHOLD: f32 = 0.9995;
+}
+
+// creators
+// --------
+impl Quat {
+    pub const fn from_xyzw(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub const fn from_array(val: [f32; 4]) -> Self {
+        Self::from_xyzw(val[0], val[1], val[2], val[3])
+    }
+
+    pub const fn from_vec4(val: Vec4) -> Self {
+        Self::from_xyzw(val.x, val.y, val.z, val.w)
+    }
+
+    // axis is expected to be normalized
+    pub fn from_axis_angle(axis: &Vec3, angle: f32) -> Self {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        Self::from_xyzw(axis.x * sin, axis.y * sin, axis.z * sin, cos)
+    }
+
+    pub fn from_rotation_x(angle: f32) -> Self {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        Self::from_xyzw(sin, 0.0, 0.0, cos)
+    }
+
+    pub fn from_rotation_y(angle: f32) -> Self {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        Self::from_xyzw(0.0, sin, 0.0, cos)
+    }
+
+    pub fn from_rotation_z(angle: f32) -> Self {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        Self::from_xyzw(0.0, 0.0, sin, cos)
+    }
+
+    // intrinsic rotation: first around x, then around the new y, then around the new z
+    pub fn from_euler_xyz(x: f32, y: f32, z: f32) -> Self {
+        Self::from_rotation_x(x) * Self::from_rotation_y(y) * Self::from_rotation_z(z)
+    }
+
+    // yaw around y, pitch around x, roll around z
+    pub fn from_yaw_pitch_roll(yaw: f32, pitch: f32, roll: f32) -> Self {
+        Self::from_rotation_y(yaw) * Self::from_rotation_x(pitch) * Self::from_rotation_z(roll)
+    }
+
+    // creates the shortest rotation that turns 'from' into 'to' - both are expected to be normalized
+    pub fn from_rotation_arc(from: &Vec3, to: &Vec3) -> Self {
+        let dot = from.dot(*to);
+
+        if dot >= 1.0 - f32::EPSILON {
+            return Self::IDENTITY;
+        }
+
+        if dot <= -1.0 + f32::EPSILON {
+            // vectors point in opposite directions -> rotate 180 deg around any orthogonal axis
+            let axis = if from.x.abs() < 0.9 { Vec3::new(1.0, 0.0, 0.0) } else { Vec3::new(0.0, 1.0, 0.0) };
+            let axis = from.cross(&axis).unit();
+            return Self::from_axis_angle(&axis, std::f32::consts::PI);
+        }
+
+        let axis = from.cross(to);
+        Self::from_xyzw(axis.x, axis.y, axis.z, 1.0 + dot).normalize()
+    }
+
+    // only the upper 3x3 of the matrix is used, it is expected to be a pure rotation
+    pub fn from_mat4(mat: &Mat4) -> Self {
+        let (m00, m01, m02) = (mat.x.x, mat.x.y, mat.x.z);
+        let (m10, m11, m12) = (mat.y.x, mat.y.y, mat.y.z);
+        let (m20, m21, m22) = (mat.z.x, mat.z.y, mat.z.z);
+
+        let trace = m00 + m11 + m22;
+
+        let result = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self::from_xyzw(
+                (m12 - m21) / s,
+                (m20 - m02) / s,
+                (m01 - m10) / s,
+                0.25 * s,
+            )
+        } else if m00 > m11 && m00 > m22 {
+            let s = (1.0 + m00 - m11 - m22).sqrt() * 2.0;
+            Self::from_xyzw(
+                0.25 * s,
+                (m10 + m01) / s,
+                (m20 + m02) / s,
+                (m12 - m21) / s,
+            )
+        } else if m11 > m22 {
+            let s = (1.0 + m11 - m00 - m22).sqrt() * 2.0;
+            Self::from_xyzw(
+                (m10 + m01) / s,
+                0.25 * s,
+                (m21 + m12) / s,
+                (m20 - m02) / s,
+            )
+        } else {
+            let s = (1.0 + m22 - m00 - m11).sqrt() * 2.0;
+            Self::from_xyzw(
+                (m20 + m02) / s,
+                (m21 + m12) / s,
+                0.25 * s,
+                (m01 - m10) / s,
+            )
+        };
+
+        result.normalize()
+    }
+}
+
+// conversions
+// -----------
+impl Quat {
+    pub const fn to_array(&self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    pub const fn to_vec4(&self) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, self.w)
+    }
+
+    pub const fn xyz(&self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    // returns a normalized axis and the angle in radians
+    pub fn to_axis_angle(&self) -> (Vec3, f32) {
+        let q = self.normalize();
+        let q = if q.w < 0.0 { q.neg() } else { q };
+
+        let angle = 2.0 * q.w.clamp(-1.0, 1.0).acos();
+        let sin = (1.0 - q.w * q.w).max(0.0).sqrt();
+
+        if sin < 1e-6 {
+            (Vec3::new(1.0, 0.0, 0.0), angle)
+        } else {
+            (q.xyz() / sin, angle)
+        }
+    }
+
+    pub fn to_mat4(&self) -> Mat4 {
+        let (x, y, z, w) = (self.x, self.y, self.z, self.w);
+        let (x2, y2, z2) = (x + x, y + y, z + z);
+        let (xx, xy, xz) = (x * x2, x * y2, x * z2);
+        let (yy, yz, zz) = (y * y2, y * z2, z * z2);
+        let (wx, wy, wz) = (w * x2, w * y2, w * z2);
+
+        Mat4::from_cols(
+            Vec4::new(1.0 - (yy + zz), xy + wz, xz - wy, 0.0),
+            Vec4::new(xy - wz, 1.0 - (xx + zz), yz + wx, 0.0),
+            Vec4::new(xz + wy, yz - wx, 1.0 - (xx + yy), 0.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+        )
+    }
+}
+
+impl From<Quat> for Mat4 {
+    fn from(val: Quat) -> Self {
+        val.to_mat4()
+    }
+}
+
+impl From<&Mat4> for Quat {
+    fn from(val: &Mat4) -> Self {
+        Self::from_mat4(val)
+    }
+}
+
+impl From<Quat> for [f32; 4] {
+    fn from(val: Quat) -> Self {
+        val.to_array()
+    }
+}
+
+impl From<[f32; 4]> for Quat {
+    fn from(val: [f32; 4]) -> Self {
+        Self::from_array(val)
+    }
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+// additional operations
+// ---------------------
+impl Quat {
+    pub fn dot(&self, rhs: &Self) -> f32 {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+    }
+
+    pub fn mag_squared(&self) -> f32 {
+        self.dot(self)
+    }
+
+    pub fn mag(&self) -> f32 {
+        self.mag_squared().sqrt()
+    }
+
+    pub fn is_normalized(&self) -> bool {
+        (self.mag_squared() - 1.0).abs() <= 1e-4
+    }
+
+    pub fn normalize(&self) -> Self {
+        let rcp_mag = self.mag().recip();
+        Self::from_xyzw(self.x * rcp_mag, self.y * rcp_mag, self.z * rcp_mag, self.w * rcp_mag)
+    }
+
+    pub fn normalize_assign(&mut self) {
+        *self = self.normalize();
+    }
+
+    pub const fn conjugate(&self) -> Self {
+        Self::from_xyzw(-self.x, -self.y, -self.z, self.w)
+    }
+
+    pub fn inverse(&self) -> Self {
+        let rcp_mag_sq = self.mag_squared().recip();
+        let conj = self.conjugate();
+        Self::from_xyzw(conj.x * rcp_mag_sq, conj.y * rcp_mag_sq, conj.z * rcp_mag_sq, conj.w * rcp_mag_sq)
+    }
+
+    pub fn mul_quat(&self, rhs: &Self) -> Self {
+        let (x0, y0, z0, w0) = (self.x, self.y, self.z, self.w);
+        let (x1, y1, z1, w1) = (rhs.x, rhs.y, rhs.z, rhs.w);
+
+        Self::from_xyzw(
+            w0 * x1 + x0 * w1 + y0 * z1 - z0 * y1,
+            w0 * y1 - x0 * z1 + y0 * w1 + z0 * x1,
+            w0 * z1 + x0 * y1 - y0 * x1 + z0 * w1,
+            w0 * w1 - x0 * x1 - y0 * y1 - z0 * z1,
+        )
+    }
+
+    // rotates the vector by this quaternion - the quaternion is expected to be normalized
+    pub fn mul_vec3(&self, rhs: &Vec3) -> Vec3 {
+        let q = self.xyz();
+        let t = q.cross(rhs) * 2.0;
+        *rhs + (t * self.w) + q.cross(&t)
+    }
+
+    // angle in radians between two rotations
+    pub fn angle_between(&self, rhs: &Self) -> f32 {
+        let dot = self.dot(rhs).abs().min(1.0);
+        2.0 * dot.acos()
+    }
+}
+
+// interpolation
+// -------------
+impl Quat {
+    // normalized linear interpolation - cheaper than slerp, but does not have a constant angular velocity
+    pub fn nlerp(&self, end: &Self, t: f32) -> Self {
+        let end = if self.dot(end) < 0.0 { end.neg() } else { *end };
+
+        Self::from_xyzw(
+            self.x + (end.x - self.x) * t,
+            self.y + (end.y - self.y) * t,
+            self.z + (end.z - self.z) * t,
+            self.w + (end.w - self.w) * t,
+        ).normalize()
+    }
+
+    // spherical linear interpolation - always takes the shortest path
+    pub fn slerp(&self, end: &Self, t: f32) -> Self {
+        let mut dot = self.dot(end);
+        let mut end = *end;
+
+        if dot < 0.0 {
+            end = end.neg();
+            dot = -dot;
+        }
+
+        if dot > Self::SLERP_THRESHOLD {
+            return self.nlerp(&end, t);
+        }
+
+        let theta = dot.acos();
+        let rcp_sin_theta = theta.sin().recip();
+        let scale_start = ((1.0 - t) * theta).sin() * rcp_sin_theta;
+        let scale_end = (t * theta).sin() * rcp_sin_theta;
+
+        Self::from_xyzw(
+            self.x * scale_start + end.x * scale_end,
+            self.y * scale_start + end.y * scale_end,
+            self.z * scale_start + end.z * scale_end,
+            self.w * scale_start + end.w * scale_end,
+        )
+    }
+}
+
+// operators
+// ---------
+impl Mul<Quat> for Quat {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_quat(&rhs)
+    }
+}
+
+impl MulAssign<Quat> for Quat {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul_quat(&rhs);
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+
+    fn mul(self, rhs: Vec3) -> Self::Output {
+        self.mul_vec3(&rhs)
+    }
+}
+
+impl Neg for Quat {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_xyzw(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // q and -q are the same rotation
+    fn assert_quat_eq(lhs: Quat, rhs: glam::Quat) {
+        assert!(glam::Quat::from(lhs).dot(rhs).abs() > 1.0 - 1e-5, "{:?} != {:?}", lhs, rhs);
+    }
+
+    fn assert_vec_eq(lhs: Vec3, rhs: glam::Vec3) {
+        assert!(glam::Vec3::from(lhs).abs_diff_eq(rhs, 1e-5), "{:?} != {:?}", lhs, rhs);
+    }
+
+    fn rotations() -> Vec<(Vec3, f32)> {
+        vec![
+            (Vec3::new(1.0, 0.0, 0.0), 0.3),
+            (Vec3::new(0.0, 1.0, 0.0), -1.2),
+            (Vec3::new(1.0, 2.0, 3.0).unit(), 2.5),
+            (Vec3::new(-0.5, 0.2, 0.9).unit(), 3.1),
+            (Vec3::new(0.3, -0.8, 0.1).unit(), 0.0),
+        ]
+    }
+
+    #[test]
+    fn axis_angle() {
+        for (axis, angle) in rotations() {
+            let quat = Quat::from_axis_angle(&axis, angle);
+            assert_quat_eq(quat, glam::Quat::from_axis_angle(axis.into(), angle));
+
+            if angle != 0.0 {
+                let (result_axis, result_angle) = quat.to_axis_angle();
+                let (g_axis, g_angle) = glam::Quat::from_axis_angle(axis.into(), angle).to_axis_angle();
+                assert!((result_angle - g_angle).abs() < 1e-4);
+                assert_vec_eq(result_axis, g_axis);
+            }
+        }
+
+        assert_quat_eq(Quat::from_rotation_x(0.7), glam::Quat::from_rotation_x(0.7));
+        assert_quat_eq(Quat::from_rotation_y(0.7), glam::Quat::from_rotation_y(0.7));
+        assert_quat_eq(Quat::from_rotation_z(0.7), glam::Quat::from_rotation_z(0.7));
+    }
+
+    #[test]
+    fn euler() {
+        let (x, y, z) = (0.4, -1.1, 2.3);
+        assert_quat_eq(Quat::from_euler_xyz(x, y, z), glam::Quat::from_euler(glam::EulerRot::XYZ, x, y, z));
+        assert_quat_eq(Quat::from_yaw_pitch_roll(x, y, z), glam::Quat::from_euler(glam::EulerRot::YXZ, x, y, z));
+    }
+
+    #[test]
+    fn rotation_arc() {
+        let pairs = [
+            (Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)),
+            (Vec3::new(1.0, 2.0, 3.0).unit(), Vec3::new(-2.0, 0.5, 1.0).unit()),
+            (Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, 1.0)),
+        ];
+
+        for (from, to) in pairs {
+            assert_quat_eq(Quat::from_rotation_arc(&from, &to), glam::Quat::from_rotation_arc(from.into(), to.into()));
+        }
+
+        // any axis works for opposite vectors, only the result is checked
+        let from = Vec3::new(0.0, 1.0, 0.0);
+        assert_vec_eq(Quat::from_rotation_arc(&from, &-from) * from, glam::Vec3::new(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn product_and_vector_rotation() {
+        let vec = Vec3::new(0.5, -2.0, 3.0);
+
+        for (lhs, rhs) in rotations().iter().zip(rotations().iter().rev()) {
+            let (a, b) = (Quat::from_axis_angle(&lhs.0, lhs.1), Quat::from_axis_angle(&rhs.0, rhs.1));
+            let (ga, gb) = (glam::Quat::from(a), glam::Quat::from(b));
+
+            assert_quat_eq(a * b, ga * gb);
+            assert_quat_eq(a.inverse(), ga.inverse());
+            assert_vec_eq(a * vec, ga * glam::Vec3::from(vec));
+            assert_vec_eq((a * b) * vec, ga * (gb * glam::Vec3::from(vec)));
+            assert!((a.angle_between(&b) - ga.angle_between(gb)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        let start = Quat::from_axis_angle(&Vec3::new(1.0, 2.0, 3.0).unit(), 0.5);
+        let end = Quat::from_axis_angle(&Vec3::new(-0.5, 0.2, 0.9).unit(), 2.8);
+        let (g_start, g_end) = (glam::Quat::from(start), glam::Quat::from(end));
+
+        for t in [0.0, 0.25, 0.5, 0.9, 1.0] {
+            assert_quat_eq(start.slerp(&end, t), g_start.slerp(g_end, t));
+            // glam's lerp also takes the shortest path and normalizes the result
+            assert_quat_eq(start.nlerp(&end, t), g_start.lerp(g_end, t));
+            assert_quat_eq(start.slerp(&-end, t), g_start.slerp(-g_end, t));
+        }
+
+        // close rotations fall back to nlerp
+        let close = Quat::from_axis_angle(&Vec3::new(1.0, 2.0, 3.0).unit(), 0.51);
+        assert_quat_eq(start.slerp(&close, 0.5), g_start.slerp(close.into(), 0.5));
+    }
+
+    #[test]
+    fn mat4_round_trip() {
+        for (axis, angle) in rotations() {
+            let quat = Quat::from_axis_angle(&axis, angle);
+            let mat = quat.to_mat4();
+            let g_mat = glam::Mat4::from_quat(quat.into());
+
+            assert!(glam::Mat4::from(mat).abs_diff_eq(g_mat, 1e-5), "{:?} != {:?}", mat, g_mat);
+            assert_quat_eq(Quat::from_mat4(&mat), glam::Quat::from_mat4(&g_mat));
+            assert_quat_eq(Quat::from_mat4(&mat), quat.into());
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use crate::vec::{Vec2, Vec3};
+use crate::geometry::Aabb3;
+
+
+
+
+// ----------------------------------------------------------------------------
+// pcg32
+// ----------------------------------------------------------------------------
+
+// https://www.pcg-random.org/ - pcg-xsh-rr with 64 bit state and 32 bit output
+// the output sequence only depends on seed and stream, so it is identical on every machine
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Pcg32 {
+    state: u64,
+    inc: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6364136223846793005;
+    const DEFAULT_STREAM: u64 = 0xda3e39cb94b95bdb;
+}
+
+// creators
+// --------
+impl Pcg32 {
+    // generators with the same seed but different streams produce independent sequences
+    pub fn new(seed: u64, stream: u64) -> Self {
+        let mut result = Self {
+            state: 0,
+            inc: (stream << 1) | 1,
+        };
+
+        result.step();
+        result.state = result.state.wrapping_add(seed);
+        result.step();
+        result
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
+        Self::new(seed, Self::DEFAULT_STREAM)
+    }
+
+    // creates a new generator on a different stream - the sequence of 'self' advances by two values
+    pub fn split(&mut self) -> Self {
+        let seed = self.next_u64();
+        let stream = self.next_u64();
+        Self::new(seed, stream)
+    }
+}
+
+// raw values
+// ----------
+impl Pcg32 {
+    fn step(&mut self) {
+        self.state = self.state
+            .wrapping_mul(Self::MULTIPLIER)
+            .wrapping_add(self.inc);
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+
+        let xor_shifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xor_shifted.rotate_right(rot)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        let low = self.next_u32() as u64;
+        (high << 32) | low
+    }
+
+    // uniform in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        // 24 bits fit exactly into the f32 mantissa
+        (self.next_u32() >> 8) as f32 * (1.0 / (1_u32 << 24) as f32)
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u32() & 1 == 1
+    }
+}
+
+// ranges
+// ------
+impl Pcg32 {
+    // uniform in [0, bound) without modulo bias
+    pub fn below(&mut self, bound: u32) -> u32 {
+        assert!(bound > 0, "bound has to be greater than zero");
+
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let val = self.next_u32();
+            if val >= threshold {
+                return val % bound;
+            }
+        }
+    }
+
+    // uniform in [min, max), an empty range returns 'min' without advancing the sequence
+    pub fn range_u32(&mut self, min: u32, max: u32) -> u32 {
+        assert!(min <= max, "invalid range: min ({}) is greater than max ({})", min, max);
+        if min == max { return min; }
+
+        min + self.below(max - min)
+    }
+
+    // uniform in [min, max), an empty range returns 'min' without advancing the sequence
+    pub fn range_i32(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min <= max, "invalid range: min ({}) is greater than max ({})", min, max);
+        if min == max { return min; }
+
+        let span = max.wrapping_sub(min) as u32;
+        min.wrapping_add(self.below(span) as i32)
+    }
+
+    // uniform in [min, max)
+    pub fn range_f32(&mut self, min: f32, max: f32) -> f32 {
+        min + (max - min) * self.next_f32()
+    }
+
+    // true with the given probability
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+}
+
+// collections
+// -----------
+impl Pcg32 {
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        items.get(self.below(items.len() as u32) as usize)
+    }
+
+    // negative weights count as zero, returns 'None' if all weights are zero
+    pub fn weighted_index(&mut self, weights: &[f32]) -> Option<usize> {
+        let total: f32 = weights.iter().map(|w| w.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = self.next_f32() * total;
+        let mut last_valid = None;
+
+        for (idx, weight) in weights.iter().enumerate() {
+            let weight = weight.max(0.0);
+            if weight <= 0.0 { continue; }
+
+            if target < weight {
+                return Some(idx);
+            }
+
+            target -= weight;
+            last_valid = Some(idx);
+        }
+
+        // rounding errors
+        last_valid
+    }
+
+    pub fn choose_weighted<'a, T>(&mut self, items: &'a [T], weights: &[f32]) -> Option<&'a T> {
+        debug_assert_eq!(items.len(), weights.len());
+        self.weighted_index(weights).and_then(|idx| items.get(idx))
+    }
+
+    // fisher-yates
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            let other = self.below(idx as u32 + 1) as usize;
+            items.swap(idx, other);
+        }
+    }
+}
+
+// geometry
+// --------
+impl Pcg32 {
+    // uniformly distributed on the area of the circle
+    pub fn point_in_circle(&mut self, radius: f32) -> Vec2 {
+        let angle = self.next_f32() * std::f32::consts::TAU;
+        let dist = self.next_f32().sqrt() * radius;
+        Vec2::new(angle.cos() * dist, angle.sin() * dist)
+    }
+
+    pub fn point_on_circle(&mut self, radius: f32) -> Vec2 {
+        let angle = self.next_f32() * std::f32::consts::TAU;
+        Vec2::new(angle.cos() * radius, angle.sin() * radius)
+    }
+
+    pub fn point_on_sphere(&mut self, radius: f32) -> Vec3 {
+        let z = self.range_f32(-1.0, 1.0);
+        let angle = self.next_f32() * std::f32::consts::TAU;
+        let r = (1.0 - z * z).max(0.0).sqrt();
+        Vec3::new(r * angle.cos(), r * angle.sin(), z) * radius
+    }
+
+    // uniformly distributed in the volume of the sphere
+    pub fn point_in_sphere(&mut self, radius: f32) -> Vec3 {
+        let dist = self.next_f32().cbrt();
+        self.point_on_sphere(radius * dist)
+    }
+
+    pub fn point_in_aabb(&mut self, aabb: &Aabb3) -> Vec3 {
+        Vec3::new(
+            self.range_f32(aabb.min.x, aabb.max.x),
+            self.range_f32(aabb.min.y, aabb.max.y),
+            self.range_f32(aabb.min.z, aabb.max.z),
+        )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_implementation() {
+        // output of the pcg32-demo of the reference c implementation with 'pcg32_srandom_r(&rng, 42, 54)'
+        let mut rng = Pcg32::new(42, 54);
+        let expected = [0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e];
+        assert_eq!(expected.map(|_| rng.next_u32()), expected);
+    }
+
+    // changing any of these values breaks saved seeds and lockstep replays
+    #[test]
+    fn default_stream_sequence() {
+        let mut rng = Pcg32::from_seed(12345);
+        let expected = [2251339066, 380381740, 3135122815, 3539316216, 3092115165, 2553719454];
+        assert_eq!(expected.map(|_| rng.next_u32()), expected);
+
+        let mut rng = Pcg32::from_seed(12345);
+        assert_eq!(rng.next_f32(), 0.5241807);
+        assert_eq!(rng.below(10), 0);
+        assert_eq!(rng.range_i32(-5, 5), 0);
+    }
+
+    #[test]
+    fn split() {
+        let mut rng = Pcg32::from_seed(7);
+        let mut split = rng.split();
+        assert_eq!(split.next_u32(), 215069948);
+        assert_eq!(rng.next_u32(), 3514199328);
+    }
+
+    #[test]
+    fn clones_repeat_the_sequence() {
+        let mut rng = Pcg32::from_seed(99);
+        rng.next_u64();
+
+        let mut clone = rng.clone();
+        for _ in 0..100 {
+            assert_eq!(rng.next_u32(), clone.next_u32());
+        }
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Pcg32::from_seed(5);
+        for _ in 0..1000 {
+            assert!((10..20).contains(&rng.range_u32(10, 20)));
+            assert!((-7..3).contains(&rng.range_i32(-7, 3)));
+            assert!((-1.0..1.0).contains(&rng.range_f32(-1.0, 1.0)));
+        }
+
+        assert!((i32::MIN..i32::MAX).contains(&rng.range_i32(i32::MIN, i32::MAX)));
+        assert_eq!(rng.range_u32(u32::MAX - 1, u32::MAX), u32::MAX - 1);
+    }
+
+    #[test]
+    fn empty_ranges() {
+        let mut rng = Pcg32::from_seed(5);
+        let mut clone = rng.clone();
+
+        assert_eq!(rng.range_u32(4, 4), 4);
+        assert_eq!(rng.range_u32(u32::MAX, u32::MAX), u32::MAX);
+        assert_eq!(rng.range_i32(-3, -3), -3);
+        assert_eq!(rng.range_i32(i32::MIN, i32::MIN), i32::MIN);
+
+        // the sequence doesn't advance
+        assert_eq!(rng.next_u32(), clone.next_u32());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid range")]
+    fn inverted_u32_range() {
+        Pcg32::from_seed(5).range_u32(5, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid range")]
+    fn inverted_i32_range() {
+        Pcg32::from_seed(5).range_i32(3, -3);
+    }
+}
@@ -0,0 +1,251 @@
+// sse2 implementations of the hot Mat4 operations
+// sse2 is part of the x86_64 baseline, so no runtime feature detection is required
+// the matrix product additionally has an avx path that handles two columns at once, it is picked at runtime
+// the scalar implementations in mat.rs stay the reference: all paths have to produce the same results (within epsilon)
+
+use std::arch::x86_64::*;
+
+use crate::vec::Vec4;
+use crate::mat::Mat4;
+
+
+
+
+// lane order: result = (a[i0], a[i1], b[i2], b[i3])
+macro_rules! shuffle {
+    ($a:expr, $b:expr, [$i0:literal, $i1:literal, $i2:literal, $i3:literal]) => {
+        _mm_shuffle_ps::<{ $i0 | ($i1 << 2) | ($i2 << 4) | ($i3 << 6) }>($a, $b)
+    };
+}
+
+#[inline(always)]
+unsafe fn load(val: &Vec4) -> __m128 {
+    _mm_loadu_ps(val as *const Vec4 as *const f32)
+}
+
+#[inline(always)]
+unsafe fn store(val: __m128) -> Vec4 {
+    let mut result = Vec4::zero();
+    _mm_storeu_ps(&mut result as *mut Vec4 as *mut f32, val);
+    result
+}
+
+#[inline(always)]
+unsafe fn mul_vec_raw(cols: &[__m128; 4], rhs: __m128) -> __m128 {
+    let x = _mm_mul_ps(cols[0], shuffle!(rhs, rhs, [0, 0, 0, 0]));
+    let y = _mm_mul_ps(cols[1], shuffle!(rhs, rhs, [1, 1, 1, 1]));
+    let z = _mm_mul_ps(cols[2], shuffle!(rhs, rhs, [2, 2, 2, 2]));
+    let w = _mm_mul_ps(cols[3], shuffle!(rhs, rhs, [3, 3, 3, 3]));
+
+    _mm_add_ps(_mm_add_ps(x, y), _mm_add_ps(z, w))
+}
+
+#[inline(always)]
+unsafe fn load_cols(mat: &Mat4) -> [__m128; 4] {
+    [load(&mat.x), load(&mat.y), load(&mat.z), load(&mat.w)]
+}
+
+
+
+pub(crate) fn mat4_mul_vec(mat: &Mat4, rhs: &Vec4) -> Vec4 {
+    unsafe {
+        let cols = load_cols(mat);
+        store(mul_vec_raw(&cols, load(rhs)))
+    }
+}
+
+pub(crate) fn mat4_mul_mat(lhs: &Mat4, rhs: &Mat4) -> Mat4 {
+    if has_avx() {
+        // safety: avx support was checked above
+        return unsafe { mat4_mul_mat_avx(lhs, rhs) };
+    }
+
+    mat4_mul_mat_sse2(lhs, rhs)
+}
+
+fn mat4_mul_mat_sse2(lhs: &Mat4, rhs: &Mat4) -> Mat4 {
+    unsafe {
+        let cols = load_cols(lhs);
+
+        Mat4::from_cols(
+            store(mul_vec_raw(&cols, load(&rhs.x))),
+            store(mul_vec_raw(&cols, load(&rhs.y))),
+            store(mul_vec_raw(&cols, load(&rhs.z))),
+            store(mul_vec_raw(&cols, load(&rhs.w))),
+        )
+    }
+}
+
+// the result of the detection is cached by std, only the first call is expensive
+#[inline(always)]
+fn has_avx() -> bool {
+    cfg!(target_feature = "avx") || is_x86_feature_detected!("avx")
+}
+
+// two columns of 'rhs' per iteration: the low lane holds the first, the high lane the second column
+#[target_feature(enable = "avx")]
+unsafe fn mat4_mul_mat_avx(lhs: &Mat4, rhs: &Mat4) -> Mat4 {
+    let cols = [
+        _mm256_broadcast_ps(&load(&lhs.x)),
+        _mm256_broadcast_ps(&load(&lhs.y)),
+        _mm256_broadcast_ps(&load(&lhs.z)),
+        _mm256_broadcast_ps(&load(&lhs.w)),
+    ];
+
+    let mut result = Mat4::IDENTITY;
+    let src = rhs as *const Mat4 as *const f32;
+    let dst = &mut result as *mut Mat4 as *mut f32;
+
+    // the columns of a 'Mat4' are stored contiguously, see '#[repr(C)]'
+    for offset in [0, 8] {
+        let pair = _mm256_loadu_ps(src.add(offset));
+
+        let x = _mm256_mul_ps(cols[0], _mm256_permute_ps::<0b00_00_00_00>(pair));
+        let y = _mm256_mul_ps(cols[1], _mm256_permute_ps::<0b01_01_01_01>(pair));
+        let z = _mm256_mul_ps(cols[2], _mm256_permute_ps::<0b10_10_10_10>(pair));
+        let w = _mm256_mul_ps(cols[3], _mm256_permute_ps::<0b11_11_11_11>(pair));
+
+        _mm256_storeu_ps(dst.add(offset), _mm256_add_ps(_mm256_add_ps(x, y), _mm256_add_ps(z, w)));
+    }
+
+    result
+}
+
+// same cofactor expansion as Mat4::inverse_scalar
+pub(crate) fn mat4_inverse(mat: &Mat4) -> Mat4 {
+    unsafe {
+        let [x, y, z, w] = load_cols(mat);
+
+        // fac = (m2[r0] * m3[r1], m2[r0] * m3[r1], m1[r0] * m3[r1], m1[r0] * m2[r1]) - (...)
+        macro_rules! fac {
+            ($r0:literal, $r1:literal) => {{
+                let swp_a = shuffle!(w, z, [$r1, $r1, $r1, $r1]);
+                let swp_b = shuffle!(w, z, [$r0, $r0, $r0, $r0]);
+
+                let swp_00 = shuffle!(z, y, [$r0, $r0, $r0, $r0]);
+                let swp_01 = shuffle!(swp_a, swp_a, [0, 0, 0, 2]);
+                let swp_02 = shuffle!(swp_b, swp_b, [0, 0, 0, 2]);
+                let swp_03 = shuffle!(z, y, [$r1, $r1, $r1, $r1]);
+
+                _mm_sub_ps(_mm_mul_ps(swp_00, swp_01), _mm_mul_ps(swp_02, swp_03))
+            }};
+        }
+
+        // vec = (m1[row], m0[row], m0[row], m0[row])
+        macro_rules! vec {
+            ($row:literal) => {{
+                let tmp = shuffle!(y, x, [$row, $row, $row, $row]);
+                shuffle!(tmp, tmp, [0, 2, 2, 2])
+            }};
+        }
+
+        let fac0 = fac!(2, 3);
+        let fac1 = fac!(1, 3);
+        let fac2 = fac!(1, 2);
+        let fac3 = fac!(0, 3);
+        let fac4 = fac!(0, 2);
+        let fac5 = fac!(0, 1);
+
+        let vec0 = vec!(0);
+        let vec1 = vec!(1);
+        let vec2 = vec!(2);
+        let vec3 = vec!(3);
+
+        let inv0 = _mm_add_ps(_mm_sub_ps(_mm_mul_ps(vec1, fac0), _mm_mul_ps(vec2, fac1)), _mm_mul_ps(vec3, fac2));
+        let inv1 = _mm_add_ps(_mm_sub_ps(_mm_mul_ps(vec0, fac0), _mm_mul_ps(vec2, fac3)), _mm_mul_ps(vec3, fac4));
+        let inv2 = _mm_add_ps(_mm_sub_ps(_mm_mul_ps(vec0, fac1), _mm_mul_ps(vec1, fac3)), _mm_mul_ps(vec3, fac5));
+        let inv3 = _mm_add_ps(_mm_sub_ps(_mm_mul_ps(vec0, fac2), _mm_mul_ps(vec1, fac4)), _mm_mul_ps(vec2, fac5));
+
+        let sign_a = _mm_set_ps(-1.0, 1.0, -1.0, 1.0);
+        let sign_b = _mm_set_ps(1.0, -1.0, 1.0, -1.0);
+
+        let inv0 = _mm_mul_ps(inv0, sign_a);
+        let inv1 = _mm_mul_ps(inv1, sign_b);
+        let inv2 = _mm_mul_ps(inv2, sign_a);
+        let inv3 = _mm_mul_ps(inv3, sign_b);
+
+        // first row of the inverse
+        let row0 = shuffle!(
+            shuffle!(inv0, inv1, [0, 0, 0, 0]),
+            shuffle!(inv2, inv3, [0, 0, 0, 0]),
+            [0, 2, 0, 2]
+        );
+
+        // horizontal add -> every lane holds the determinant
+        let dot0 = _mm_mul_ps(x, row0);
+        let dot1 = _mm_add_ps(dot0, shuffle!(dot0, dot0, [1, 0, 3, 2]));
+        let det  = _mm_add_ps(dot1, shuffle!(dot1, dot1, [2, 3, 0, 1]));
+        let rcp_det = _mm_div_ps(_mm_set1_ps(1.0), det);
+
+        Mat4::from_cols(
+            store(_mm_mul_ps(inv0, rcp_det)),
+            store(_mm_mul_ps(inv1, rcp_det)),
+            store(_mm_mul_ps(inv2, rcp_det)),
+            store(_mm_mul_ps(inv3, rcp_det)),
+        )
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn assert_vec_eq(lhs: &Vec4, rhs: &Vec4) {
+        for (a, b) in [(lhs.x, rhs.x), (lhs.y, rhs.y), (lhs.z, rhs.z), (lhs.w, rhs.w)] {
+            assert!((a - b).abs() <= EPSILON * a.abs().max(1.0), "{:?} != {:?}", lhs, rhs);
+        }
+    }
+
+    fn assert_mat_eq(lhs: &Mat4, rhs: &Mat4) {
+        for (l, r) in [(lhs.x, rhs.x), (lhs.y, rhs.y), (lhs.z, rhs.z), (lhs.w, rhs.w)] {
+            assert_vec_eq(&l, &r);
+        }
+    }
+
+    // deterministic, well conditioned matrices
+    fn matrices() -> Vec<Mat4> {
+        (0..32).map(|i| {
+            let f = |j: usize| (((i * 16 + j) * 7919 % 113) as f32 / 113.0) - 0.5;
+            Mat4::from_cols(
+                Vec4::new(2.0 + f(0), f(1), f(2), f(3)),
+                Vec4::new(f(4), 2.0 + f(5), f(6), f(7)),
+                Vec4::new(f(8), f(9), 2.0 + f(10), f(11)),
+                Vec4::new(f(12), f(13), f(14), 2.0 + f(15)),
+            )
+        }).collect()
+    }
+
+    #[test]
+    fn mul_vec_matches_scalar() {
+        for m in matrices() {
+            let v = Vec4::new(m.x.y, -m.y.z, m.z.w * 3.0, 1.0);
+            assert_vec_eq(&m.mul_vec(&v), &m.mul_vec_scalar(&v));
+        }
+    }
+
+    #[test]
+    fn mul_mat_matches_scalar() {
+        let matrices = matrices();
+        for (lhs, rhs) in matrices.iter().zip(matrices.iter().rev()) {
+            let scalar = lhs.mul_mat_scalar(rhs);
+            assert_mat_eq(&lhs.mul_mat(rhs), &scalar);
+            assert_mat_eq(&mat4_mul_mat_sse2(lhs, rhs), &scalar);
+
+            if has_avx() {
+                assert_mat_eq(&unsafe { mat4_mul_mat_avx(lhs, rhs) }, &scalar);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_matches_scalar() {
+        for m in matrices() {
+            assert_mat_eq(&m.inverse(), &m.inverse_scalar());
+            assert_mat_eq(&m.mul_mat(&m.inverse()), &Mat4::IDENTITY);
+        }
+    }
+}
@@ -1,12 +1,35 @@
 use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, Neg, Rem, RemAssign, Index, IndexMut};
 use std::convert::{AsRef, AsMut};
 
+use crate::fixed::{Fixed32, Fixed64};
+
+
+
+pub trait Scalar: Copy {
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! impl_scalar_primitive {
+    ($($scalar:ident),+) => {
+        $(
+            impl Scalar for $scalar {
+                const ZERO: Self = 0 as $scalar;
+                const ONE: Self = 1 as $scalar;
+            }
+        )+
+    };
+}
+
+impl_scalar_primitive!(f32, i32, u32);
+
 
 
 #[macro_export]
 macro_rules! impl_vector_common {
     ($vec:ident : $scalar:ident => $vec_size:literal : { $($field_idx:literal : $field:ident),+ }) => {
-        #[derive(Clone, Copy)]
+        #[repr(C)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
         pub struct $vec {
             $(pub $field: $scalar),+
         }
@@ -15,8 +38,8 @@ macro_rules! impl_vector_common {
         // constants
         // ---------
         impl $vec {
-            pub const SCALAR_ZERO: $scalar = 0 as $scalar;
-            pub const SCALAR_ONE: $scalar = 1 as $scalar;
+            pub const SCALAR_ZERO: $scalar = <$scalar as $crate::Scalar>::ZERO;
+            pub const SCALAR_ONE: $scalar = <$scalar as $crate::Scalar>::ONE;
         }
 
         // creators
@@ -343,6 +366,25 @@ macro_rules! impl_vector_common {
                 $(result += self.$field * rhs.$field;)+
                 result
             }
+
+            // component-wise minimum
+            pub fn min(&self, rhs: Self) -> Self {
+                Self {
+                    $($field: self.$field.min(rhs.$field)),+
+                }
+            }
+
+            // component-wise maximum
+            pub fn max(&self, rhs: Self) -> Self {
+                Self {
+                    $($field: self.$field.max(rhs.$field)),+
+                }
+            }
+
+            // component-wise clamp
+            pub fn clamp(&self, min: Self, max: Self) -> Self {
+                self.max(min).min(max)
+            }
         }
     };
 }
@@ -374,6 +416,20 @@ macro_rules! impl_vector_float {
                 $scalar::sqrt(tmp)
             }
 
+            pub fn mag_squared(&self) -> $scalar {
+                self.dot(*self)
+            }
+
+            pub fn distance(&self, rhs: Self) -> $scalar {
+                (*self - rhs).mag()
+            }
+
+            pub fn abs(&self) -> Self {
+                Self {
+                    $($field: self.$field.abs()),+
+                }
+            }
+
             pub fn unit(self) -> $vec {
                 let mag = self.mag();
                 self.div(mag)
@@ -390,6 +446,52 @@ macro_rules! impl_vector_float {
 
 
 
+macro_rules! impl_vector_fixed {
+    ($vec:ident : $scalar:ident => $vec_size:literal : { $($field_idx:literal : $field:ident),+ }) => {
+        // additional operations
+        // ---------------------
+        impl $vec {
+            pub fn mag_squared(&self) -> $scalar {
+                self.dot(*self)
+            }
+
+            pub fn mag(&self) -> $scalar {
+                self.mag_squared().sqrt()
+            }
+
+            pub fn distance(&self, rhs: Self) -> $scalar {
+                (*self - rhs).mag()
+            }
+
+            // zero-length vectors stay zero
+            pub fn unit(self) -> $vec {
+                let mag = self.mag();
+                if mag == $scalar::ZERO { return self; }
+                self.div(mag)
+            }
+
+            pub fn abs(&self) -> Self {
+                Self {
+                    $($field: self.$field.abs()),+
+                }
+            }
+
+            pub fn from_f32(val: [f32; $vec_size]) -> Self {
+                Self {
+                    $($field: $scalar::from_f32(val[$field_idx])),+
+                }
+            }
+
+            pub fn to_f32(&self) -> [f32; $vec_size] {
+                [$(self.$field.to_f32()),+]
+            }
+        }
+    };
+}
+
+
+
+
 impl_vector_common!(Vec2: f32 => 2: { 0:x, 1:y });
 impl_vector_signed!(Vec2: f32 => 2: { 0:x, 1:y });
 impl_vector_float! (Vec2: f32 => 2: { 0:x, 1:y });
@@ -402,11 +504,37 @@ impl_vector_common!(Vec4: f32 => 4: { 0:x, 1:y, 2:z, 3:w });
 impl_vector_signed!(Vec4: f32 => 4: { 0:x, 1:y, 2:z, 3:w });
 impl_vector_float! (Vec4: f32 => 4: { 0:x, 1:y, 2:z, 3:w });
 
+impl_vector_common!(IVec2: i32 => 2: { 0:x, 1:y });
+impl_vector_signed!(IVec2: i32 => 2: { 0:x, 1:y });
+
+impl_vector_common!(IVec3: i32 => 3: { 0:x, 1:y, 2:z });
+impl_vector_signed!(IVec3: i32 => 3: { 0:x, 1:y, 2:z });
+
 impl_vector_common!(IVec4: i32 => 4: { 0:x, 1:y, 2:z, 3:w });
 impl_vector_signed!(IVec4: i32 => 4: { 0:x, 1:y, 2:z, 3:w });
 
+impl_vector_common!(UVec2: u32 => 2: { 0:x, 1:y });
+
+impl_vector_common!(UVec3: u32 => 3: { 0:x, 1:y, 2:z });
+
 impl_vector_common!(UVec4: u32 => 4: { 0:x, 1:y, 2:z, 3:w });
 
+impl_vector_common!(Fx32Vec2: Fixed32 => 2: { 0:x, 1:y });
+impl_vector_signed!(Fx32Vec2: Fixed32 => 2: { 0:x, 1:y });
+impl_vector_fixed! (Fx32Vec2: Fixed32 => 2: { 0:x, 1:y });
+
+impl_vector_common!(Fx32Vec3: Fixed32 => 3: { 0:x, 1:y, 2:z });
+impl_vector_signed!(Fx32Vec3: Fixed32 => 3: { 0:x, 1:y, 2:z });
+impl_vector_fixed! (Fx32Vec3: Fixed32 => 3: { 0:x, 1:y, 2:z });
+
+impl_vector_common!(Fx64Vec2: Fixed64 => 2: { 0:x, 1:y });
+impl_vector_signed!(Fx64Vec2: Fixed64 => 2: { 0:x, 1:y });
+impl_vector_fixed! (Fx64Vec2: Fixed64 => 2: { 0:x, 1:y });
+
+impl_vector_common!(Fx64Vec3: Fixed64 => 3: { 0:x, 1:y, 2:z });
+impl_vector_signed!(Fx64Vec3: Fixed64 => 3: { 0:x, 1:y, 2:z });
+impl_vector_fixed! (Fx64Vec3: Fixed64 => 3: { 0:x, 1:y, 2:z });
+
 
 
 
@@ -422,12 +550,95 @@ impl From<Vec4> for (f32, f32, f32, f32) {
     }
 }
 
-impl Vec3 {
-    pub fn cross(&self, rhs: &Self) -> Self {
-        Self::new(
-            (self.y * rhs.z) - (self.z - rhs.y),
-            (self.z * rhs.x) - (self.x - rhs.z),
-            (self.x * rhs.y) - (self.y - rhs.x)
-        )
-    }
+macro_rules! impl_vector_cross {
+    ($($vec:ident),+) => {
+        $(
+            impl $vec {
+                pub fn cross(&self, rhs: &Self) -> Self {
+                    Self::new(
+                        (self.y * rhs.z) - (self.z * rhs.y),
+                        (self.z * rhs.x) - (self.x * rhs.z),
+                        (self.x * rhs.y) - (self.y * rhs.x)
+                    )
+                }
+            }
+        )+
+    };
 }
+
+impl_vector_cross!(Vec3, Fx32Vec3, Fx64Vec3);
+
+
+
+
+// numeric casts between vectors of the same size, with the semantics of 'as'
+// float -> int truncates towards zero and saturates, int <-> uint reinterprets the bits
+macro_rules! impl_vector_cast {
+    ($($vec:ident => $fn_name:ident : $target:ident : $target_scalar:ident : { $($field:ident),+ }),+ $(,)?) => {
+        $(
+            impl $vec {
+                pub fn $fn_name(&self) -> $target {
+                    $target::new($(self.$field as $target_scalar),+)
+                }
+            }
+        )+
+    };
+}
+
+impl_vector_cast!(
+    Vec2  => as_ivec2: IVec2: i32: { x, y },
+    Vec2  => as_uvec2: UVec2: u32: { x, y },
+    IVec2 => as_vec2:  Vec2:  f32: { x, y },
+    IVec2 => as_uvec2: UVec2: u32: { x, y },
+    UVec2 => as_vec2:  Vec2:  f32: { x, y },
+    UVec2 => as_ivec2: IVec2: i32: { x, y },
+
+    Vec3  => as_ivec3: IVec3: i32: { x, y, z },
+    Vec3  => as_uvec3: UVec3: u32: { x, y, z },
+    IVec3 => as_vec3:  Vec3:  f32: { x, y, z },
+    IVec3 => as_uvec3: UVec3: u32: { x, y, z },
+    UVec3 => as_vec3:  Vec3:  f32: { x, y, z },
+    UVec3 => as_ivec3: IVec3: i32: { x, y, z },
+
+    Vec4  => as_ivec4: IVec4: i32: { x, y, z, w },
+    Vec4  => as_uvec4: UVec4: u32: { x, y, z, w },
+    IVec4 => as_vec4:  Vec4:  f32: { x, y, z, w },
+    IVec4 => as_uvec4: UVec4: u32: { x, y, z, w },
+    UVec4 => as_vec4:  Vec4:  f32: { x, y, z, w },
+    UVec4 => as_ivec4: IVec4: i32: { x, y, z, w },
+);
+
+// floor / ceil / round of float vectors into integer vectors
+macro_rules! impl_vector_round {
+    ($($vec:ident => $ivec:ident : { $($field:ident),+ }),+) => {
+        $(
+            impl $vec {
+                pub fn floor(&self) -> Self {
+                    Self::new($(self.$field.floor()),+)
+                }
+
+                pub fn ceil(&self) -> Self {
+                    Self::new($(self.$field.ceil()),+)
+                }
+
+                pub fn round(&self) -> Self {
+                    Self::new($(self.$field.round()),+)
+                }
+
+                pub fn floor_to_int(&self) -> $ivec {
+                    $ivec::new($(self.$field.floor() as i32),+)
+                }
+
+                pub fn ceil_to_int(&self) -> $ivec {
+                    $ivec::new($(self.$field.ceil() as i32),+)
+                }
+            }
+        )+
+    };
+}
+
+impl_vector_round!(
+    Vec2 => IVec2: { x, y },
+    Vec3 => IVec3: { x, y, z },
+    Vec4 => IVec4: { x, y, z, w }
+);
@@ -19,15 +19,15 @@ impl HellCamera {
 
         let height = 2.0;
         let width = height * aspect_ratio;
-        let mut proj = glam::Mat4::orthographic_lh(
+        let proj = hell_math::Mat4::orthographic_lh(
             -width / 2.0,
              width / 2.0,
             -height / 2.0,
              height / 2.0,
             0.0,
-            100.0
-        );
-        proj.y_axis.y *= -1.0;
+            100.0,
+            hell_math::ClipSpace::VULKAN
+        ).into();
 
         let view_proj = view * proj;
 
@@ -5,6 +5,7 @@ use hell_core::error::HellResult;
 
 use crate::camera::HellCamera;
 use crate::render_types::RenderPackage;
+use crate::scene::SceneFile;
 use crate::resources::{TextureManager, MaterialManager, ShaderManager, ResourceHandle};
 use crate::vulkan::primitives::VulkanSwapchain;
 use crate::vulkan::{VulkanBackend, VulkanContext};
@@ -109,4 +110,16 @@ impl HellRenderer {
     pub fn acquire_material(&mut self, path: impl Into<String>) -> HellResult<ResourceHandle> {
         self.mat_man.acquire_from_file(&self.backend, &mut self.tex_man, path.into())
     }
+
+    pub fn load_scene(&mut self, path: &str) -> HellResult<RenderPackage> {
+        let mut package = RenderPackage::default();
+        self.load_scene_into(path, &mut package)?;
+        Ok(package)
+    }
+
+    // appends the objects of the scene to an existing package
+    pub fn load_scene_into(&mut self, path: &str, package: &mut RenderPackage) -> HellResult<()> {
+        let scene = SceneFile::load_file(path)?;
+        scene.add_to_render_package(self, package)
+    }
 }
@@ -0,0 +1,88 @@
+use std::{path::Path, fs};
+
+use hell_common::transform::Transform;
+use hell_core::error::HellResult;
+
+use crate::HellRenderer;
+use crate::render_types::{RenderData, RenderPackage};
+
+
+
+// ----------------------------------------------------------------------------
+// scene file
+// ----------------------------------------------------------------------------
+
+// scene:
+//   name: level_01
+//   world:
+//     - mesh: 0
+//       material: assets/materials/player.yaml
+//       transform:
+//         translation: [0.0, 1.0, 0.0]
+//         rotation: [0.0, 0.0, 0.0, 1.0]
+//         scale: [1.0, 1.0, 1.0]
+//   ui: []
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SceneFile {
+    pub scene: SceneInfo,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SceneInfo {
+    pub name: String,
+    #[serde(default)]
+    pub world: Vec<SceneObject>,
+    #[serde(default)]
+    pub ui: Vec<SceneObject>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SceneObject {
+    pub mesh: usize,
+    // path of the material file, see 'MaterialFile'
+    pub material: String,
+    // all fields are optional, missing ones are taken from the identity transform
+    #[serde(default)]
+    pub transform: Transform,
+}
+
+impl SceneFile {
+    pub fn from_yaml(raw: &str) -> HellResult<Self> {
+        let file: SceneFile = serde_yaml::from_str(raw)?;
+        Ok(file)
+    }
+
+    pub fn load_file(path: impl AsRef<Path>) -> HellResult<Self> {
+        let raw = fs::read_to_string(path)?;
+        Self::from_yaml(&raw)
+    }
+
+    pub fn to_yaml(&self) -> HellResult<String> {
+        let raw = serde_yaml::to_string(self)?;
+        Ok(raw)
+    }
+
+    pub fn save_file(&self, path: impl AsRef<Path>) -> HellResult<()> {
+        fs::write(path, self.to_yaml()?)?;
+        Ok(())
+    }
+}
+
+impl SceneFile {
+    // appends all objects of the scene - materials are acquired through the renderer, so they are only loaded once
+    pub fn add_to_render_package(&self, renderer: &mut HellRenderer, package: &mut RenderPackage) -> HellResult<()> {
+        Self::add_objects(&self.scene.world, renderer, &mut package.world)?;
+        Self::add_objects(&self.scene.ui, renderer, &mut package.ui)?;
+        Ok(())
+    }
+
+    fn add_objects(objects: &[SceneObject], renderer: &mut HellRenderer, render_data: &mut RenderData) -> HellResult<()> {
+        for obj in objects {
+            let material = renderer.acquire_material(obj.material.as_str())?;
+            render_data.add_data(obj.mesh, material, obj.transform.clone());
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,19 @@
 // https://raw.githubusercontent.com/unknownue/vulkan-tutorial-rust/master/src/utility/platforms.rs
 
 use ash::extensions;
+use hell_common::window::HellSurfaceInfo;
+
+pub fn required_extension_names(surface_info: &HellSurfaceInfo) -> Vec<*const i8> {
+    let surface_extension = match surface_info {
+        HellSurfaceInfo::Xlib { .. }    => extensions::khr::XlibSurface::name(),
+        HellSurfaceInfo::Xcb { .. }     => extensions::khr::XcbSurface::name(),
+        HellSurfaceInfo::Wayland { .. } => extensions::khr::WaylandSurface::name(),
+        HellSurfaceInfo::Headless       => extensions::ext::HeadlessSurface::name(),
+    };
 
-pub fn required_extension_names() -> Vec<*const i8> {
     vec![
         extensions::khr::Surface::name().as_ptr(),
-        extensions::khr::XlibSurface::name().as_ptr(),
+        surface_extension.as_ptr(),
         extensions::ext::DebugUtils::name().as_ptr(),   // required for validation layers
     ]
 }
@@ -2,6 +2,7 @@ use std::ffi;
 use std::os::raw;
 
 use ash::vk;
+use hell_common::window::HellSurfaceInfo;
 use hell_core::error::{HellResult, ErrToHellErr};
 use crate::{vulkan::{validation_layers, platforms, debugging}, config};
 
@@ -20,7 +21,8 @@ impl VulkanInstance {
 }
 
 impl VulkanInstance {
-    pub fn new (app_name: &str) -> HellResult<Self> {
+    // the surface info decides which platform surface extension is enabled
+    pub fn new (app_name: &str, surface_info: &HellSurfaceInfo) -> HellResult<Self> {
         let entry = unsafe { ash::Entry::load().to_render_hell_err()? };
 
         if config::ENABLE_VALIDATION_LAYERS && !validation_layers::check_validation_layer_support(&entry, config::VALIDATION_LAYER_NAMES)? {
@@ -38,7 +40,7 @@ impl VulkanInstance {
             .api_version(Self::API_VERSION)
             .build();
 
-        let extension_names = platforms::required_extension_names();
+        let extension_names = platforms::required_extension_names(surface_info);
 
         let mut instance_info = vk::InstanceCreateInfo::builder()
             .application_info(&app_info)
@@ -32,22 +32,38 @@ impl Drop for VulkanSurface {
 }
 
 pub fn create_surface(entry: &ash::Entry, instance: &ash::Instance, surface_info: &HellSurfaceInfo) -> Result<vk::SurfaceKHR, vk::Result> {
-    use std::ptr;
+    use ash::extensions::{khr, ext};
 
-    let x11_display = surface_info.get_display();
-    let x11_window = surface_info.get_window();
+    match *surface_info {
+        HellSurfaceInfo::Xlib { display, window } => {
+            let create_info = vk::XlibSurfaceCreateInfoKHR::builder()
+                .dpy(display as *mut vk::Display)
+                .window(window as vk::Window);
 
-    let x11_create_info = vk::XlibSurfaceCreateInfoKHR {
-        s_type: vk::StructureType::XLIB_SURFACE_CREATE_INFO_KHR,
-        p_next: ptr::null(),
-        flags: Default::default(),
-        window: x11_window as vk::Window,
-        dpy: x11_display as *mut vk::Display,
-    };
+            let loader = khr::XlibSurface::new(entry, instance);
+            unsafe { loader.create_xlib_surface(&create_info, None) }
+        }
+        HellSurfaceInfo::Xcb { connection, window } => {
+            let create_info = vk::XcbSurfaceCreateInfoKHR::builder()
+                .connection(connection)
+                .window(window);
 
-    let xlib_surface_loader = ash::extensions::khr::XlibSurface::new(entry, instance);
+            let loader = khr::XcbSurface::new(entry, instance);
+            unsafe { loader.create_xcb_surface(&create_info, None) }
+        }
+        HellSurfaceInfo::Wayland { display, surface } => {
+            let create_info = vk::WaylandSurfaceCreateInfoKHR::builder()
+                .display(display)
+                .surface(surface);
 
-    unsafe {
-        xlib_surface_loader.create_xlib_surface(&x11_create_info, None)
+            let loader = khr::WaylandSurface::new(entry, instance);
+            unsafe { loader.create_wayland_surface(&create_info, None) }
+        }
+        HellSurfaceInfo::Headless => {
+            let create_info = vk::HeadlessSurfaceCreateInfoEXT::builder();
+
+            let loader = ext::HeadlessSurface::new(entry, instance);
+            unsafe { loader.create_headless_surface(&create_info, None) }
+        }
     }
 }
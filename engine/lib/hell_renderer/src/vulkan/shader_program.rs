@@ -5,6 +5,7 @@ use std::{array, collections::HashMap, mem::{self, size_of}};
 
 use ash::vk::{self, WriteDescriptorSet};
 use hell_core::{collections::dyn_array::DynArray, error::{HellResult, OptToHellErr, HellErrorHelper}};
+use hell_math::GpuData;
 use crate::{vulkan::{VulkanContextRef, primitives::{VulkanDescriptorSetGroup, VulkanSwapchain,  VulkanRenderPass, VulkanImage, VulkanBuffer, VulkanMemoryMap, VulkanCommands, VulkanSampler, VulkanTexture, VulkanCommandBuffer}, pipeline::{VulkanShader, VulkanPipeline}, VulkanFrame}, resources::{ResourceHandle, TextureManager}, render_types::{PerFrame, ValueRange, MemRange, NumberFormat}, config};
 
 
@@ -643,7 +644,7 @@ impl ShaderProgram {
 
     // ------------------------------------------------------------------------
 
-    pub fn set_uniform<T>(&mut self, handle: UniformHandle, value: &[T]) -> HellResult<()> {
+    pub fn set_uniform<T: GpuData>(&mut self, handle: UniformHandle, value: &[T]) -> HellResult<()> {
         let uniform = &self.uniforms[handle.scope as usize][handle.idx];
         let offset = self.bound_offset + uniform.range.offset;
 
@@ -29,7 +29,7 @@ impl std::fmt::Debug for VulkanContext {
 
 impl VulkanContext {
     pub fn new(surface_info: &HellSurfaceInfo) -> HellResult<Self> {
-        let instance = VulkanInstance::new(config::APP_NAME)?;
+        let instance = VulkanInstance::new(config::APP_NAME, surface_info)?;
         let debug_data = VulkanDebugData::new(&instance.entry, &instance.instance);
         let surface = VulkanSurface::new(&instance.entry, &instance.instance, surface_info)?;
         let phys_device = VulkanPhysDevice::pick_phys_device(&instance.instance, &surface)?;
@@ -1,4 +1,5 @@
-use hell_input::{KeyCode, KeyState};
+use hell_input::{KeyCode, KeyState, MouseButton, ModifiersState, EditKey};
+use hell_math::Vec2;
 
 pub(crate) fn keycode_to_hell(k: &winit::event::VirtualKeyCode) -> KeyCode {
     match k{
@@ -63,15 +64,15 @@ pub(crate) fn keycode_to_hell(k: &winit::event::VirtualKeyCode) -> KeyCode {
         winit::event::VirtualKeyCode::F22 => KeyCode::F22,
         winit::event::VirtualKeyCode::F23 => KeyCode::F23,
         winit::event::VirtualKeyCode::F24 => KeyCode::F24,
-        winit::event::VirtualKeyCode::Snapshot => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Scroll => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Pause => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Insert => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Home => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Delete => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::End => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::PageDown => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::PageUp => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::Snapshot => KeyCode::PrintScreen,
+        winit::event::VirtualKeyCode::Scroll => KeyCode::ScrollLock,
+        winit::event::VirtualKeyCode::Pause => KeyCode::Pause,
+        winit::event::VirtualKeyCode::Insert => KeyCode::Insert,
+        winit::event::VirtualKeyCode::Home => KeyCode::Home,
+        winit::event::VirtualKeyCode::Delete => KeyCode::Delete,
+        winit::event::VirtualKeyCode::End => KeyCode::End,
+        winit::event::VirtualKeyCode::PageDown => KeyCode::PageDown,
+        winit::event::VirtualKeyCode::PageUp => KeyCode::PageUp,
         winit::event::VirtualKeyCode::Left => KeyCode::ArrowLeft,
         winit::event::VirtualKeyCode::Up => KeyCode::ArrowUp,
         winit::event::VirtualKeyCode::Right => KeyCode::ArrowRight,
@@ -79,9 +80,9 @@ pub(crate) fn keycode_to_hell(k: &winit::event::VirtualKeyCode) -> KeyCode {
         winit::event::VirtualKeyCode::Back => KeyCode::Backspace,
         winit::event::VirtualKeyCode::Return => KeyCode::Return,
         winit::event::VirtualKeyCode::Space => KeyCode::Space,
-        winit::event::VirtualKeyCode::Compose => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Caret => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Numlock => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::Compose => KeyCode::Compose,
+        winit::event::VirtualKeyCode::Caret => KeyCode::Caret,
+        winit::event::VirtualKeyCode::Numlock => KeyCode::NumLock,
         winit::event::VirtualKeyCode::Numpad0 => KeyCode::Numpad0,
         winit::event::VirtualKeyCode::Numpad1 => KeyCode::Numpad1,
         winit::event::VirtualKeyCode::Numpad2 => KeyCode::Numpad2,
@@ -92,79 +93,232 @@ pub(crate) fn keycode_to_hell(k: &winit::event::VirtualKeyCode) -> KeyCode {
         winit::event::VirtualKeyCode::Numpad7 => KeyCode::Numpad7,
         winit::event::VirtualKeyCode::Numpad8 => KeyCode::Numpad8,
         winit::event::VirtualKeyCode::Numpad9 => KeyCode::Numpad9,
-        winit::event::VirtualKeyCode::NumpadAdd => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NumpadDivide => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NumpadDecimal => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NumpadComma => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NumpadEnter => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NumpadEquals => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NumpadMultiply => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NumpadSubtract => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::AbntC1 => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::AbntC2 => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Apostrophe => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Apps => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Asterisk => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::At => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::NumpadAdd => KeyCode::NumpadAdd,
+        winit::event::VirtualKeyCode::NumpadDivide => KeyCode::NumpadDivide,
+        winit::event::VirtualKeyCode::NumpadDecimal => KeyCode::NumpadDecimal,
+        winit::event::VirtualKeyCode::NumpadComma => KeyCode::NumpadComma,
+        winit::event::VirtualKeyCode::NumpadEnter => KeyCode::NumpadEnter,
+        winit::event::VirtualKeyCode::NumpadEquals => KeyCode::NumpadEquals,
+        winit::event::VirtualKeyCode::NumpadMultiply => KeyCode::NumpadMultiply,
+        winit::event::VirtualKeyCode::NumpadSubtract => KeyCode::NumpadSubtract,
+        winit::event::VirtualKeyCode::AbntC1 => KeyCode::AbntC1,
+        winit::event::VirtualKeyCode::AbntC2 => KeyCode::AbntC2,
+        winit::event::VirtualKeyCode::Apostrophe => KeyCode::Apostrophe,
+        winit::event::VirtualKeyCode::Apps => KeyCode::ContextMenu,
+        winit::event::VirtualKeyCode::Asterisk => KeyCode::Asterisk,
+        winit::event::VirtualKeyCode::At => KeyCode::At,
         winit::event::VirtualKeyCode::Ax => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Backslash => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Calculator => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Capital => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Colon => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Comma => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Convert => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Equals => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Grave => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Kana => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Kanji => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::Backslash => KeyCode::Backslash,
+        winit::event::VirtualKeyCode::Calculator => KeyCode::LaunchCalculator,
+        winit::event::VirtualKeyCode::Capital => KeyCode::CapsLock,
+        winit::event::VirtualKeyCode::Colon => KeyCode::Colon,
+        winit::event::VirtualKeyCode::Comma => KeyCode::Comma,
+        winit::event::VirtualKeyCode::Convert => KeyCode::Convert,
+        winit::event::VirtualKeyCode::Equals => KeyCode::Equals,
+        winit::event::VirtualKeyCode::Grave => KeyCode::Grave,
+        winit::event::VirtualKeyCode::Kana => KeyCode::KanaMode,
+        winit::event::VirtualKeyCode::Kanji => KeyCode::KanjiMode,
         winit::event::VirtualKeyCode::LAlt => KeyCode::AltLeft,
-        winit::event::VirtualKeyCode::LBracket => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::LBracket => KeyCode::BracketLeft,
         winit::event::VirtualKeyCode::LControl => KeyCode::ControlLeft,
         winit::event::VirtualKeyCode::LShift => KeyCode::ShiftLeft,
         winit::event::VirtualKeyCode::LWin => KeyCode::MetaLeft,
-        winit::event::VirtualKeyCode::Mail => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::MediaSelect => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::MediaStop => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Minus => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Mute => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::MyComputer => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NavigateForward => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NavigateBackward => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NextTrack => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::NoConvert => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::OEM102 => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Period => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::PlayPause => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Plus => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Power => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::PrevTrack => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::Mail => KeyCode::LaunchMail,
+        winit::event::VirtualKeyCode::MediaSelect => KeyCode::MediaSelect,
+        winit::event::VirtualKeyCode::MediaStop => KeyCode::MediaStop,
+        winit::event::VirtualKeyCode::Minus => KeyCode::Minus,
+        winit::event::VirtualKeyCode::Mute => KeyCode::VolumeMute,
+        winit::event::VirtualKeyCode::MyComputer => KeyCode::LaunchMyComputer,
+        winit::event::VirtualKeyCode::NavigateForward => KeyCode::BrowserForward,
+        winit::event::VirtualKeyCode::NavigateBackward => KeyCode::BrowserBack,
+        winit::event::VirtualKeyCode::NextTrack => KeyCode::MediaNextTrack,
+        winit::event::VirtualKeyCode::NoConvert => KeyCode::NonConvert,
+        winit::event::VirtualKeyCode::OEM102 => KeyCode::IntlBackslash,
+        winit::event::VirtualKeyCode::Period => KeyCode::Period,
+        winit::event::VirtualKeyCode::PlayPause => KeyCode::MediaPlayPause,
+        winit::event::VirtualKeyCode::Plus => KeyCode::Plus,
+        winit::event::VirtualKeyCode::Power => KeyCode::Power,
+        winit::event::VirtualKeyCode::PrevTrack => KeyCode::MediaPrevTrack,
         winit::event::VirtualKeyCode::RAlt => KeyCode::AltRight,
-        winit::event::VirtualKeyCode::RBracket => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::RBracket => KeyCode::BracketRight,
         winit::event::VirtualKeyCode::RControl => KeyCode::ControlRight,
         winit::event::VirtualKeyCode::RShift => KeyCode::ShiftRight,
         winit::event::VirtualKeyCode::RWin => KeyCode::MetaRight,
-        winit::event::VirtualKeyCode::Semicolon => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Slash => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Sleep => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Stop => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Sysrq => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Tab => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Underline => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::Semicolon => KeyCode::Semicolon,
+        winit::event::VirtualKeyCode::Slash => KeyCode::Slash,
+        winit::event::VirtualKeyCode::Sleep => KeyCode::Sleep,
+        winit::event::VirtualKeyCode::Stop => KeyCode::BrowserStop,
+        winit::event::VirtualKeyCode::Sysrq => KeyCode::PrintScreen,
+        winit::event::VirtualKeyCode::Tab => KeyCode::Tab,
+        winit::event::VirtualKeyCode::Underline => KeyCode::Underline,
         winit::event::VirtualKeyCode::Unlabeled => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::VolumeDown => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::VolumeUp => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Wake => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::WebBack => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::WebFavorites => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::WebForward => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::WebHome => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::WebRefresh => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::WebSearch => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::WebStop => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Yen => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Copy => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Paste => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Cut => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::VolumeDown => KeyCode::VolumeDown,
+        winit::event::VirtualKeyCode::VolumeUp => KeyCode::VolumeUp,
+        winit::event::VirtualKeyCode::Wake => KeyCode::Wake,
+        winit::event::VirtualKeyCode::WebBack => KeyCode::BrowserBack,
+        winit::event::VirtualKeyCode::WebFavorites => KeyCode::BrowserFavorites,
+        winit::event::VirtualKeyCode::WebForward => KeyCode::BrowserForward,
+        winit::event::VirtualKeyCode::WebHome => KeyCode::BrowserHome,
+        winit::event::VirtualKeyCode::WebRefresh => KeyCode::BrowserRefresh,
+        winit::event::VirtualKeyCode::WebSearch => KeyCode::BrowserSearch,
+        winit::event::VirtualKeyCode::WebStop => KeyCode::BrowserStop,
+        winit::event::VirtualKeyCode::Yen => KeyCode::Yen,
+        winit::event::VirtualKeyCode::Copy => KeyCode::Copy,
+        winit::event::VirtualKeyCode::Paste => KeyCode::Paste,
+        winit::event::VirtualKeyCode::Cut => KeyCode::Cut,
+    }
+}
+
+// linux evdev codes (x11 and wayland), mapped to the key at the same position on a us keyboard
+pub(crate) fn scancode_to_hell(scancode: u32) -> KeyCode {
+    match scancode {
+        1 => KeyCode::Escape,
+        2 => KeyCode::Alpha1,
+        3 => KeyCode::Alpha2,
+        4 => KeyCode::Alpha3,
+        5 => KeyCode::Alpha4,
+        6 => KeyCode::Alpha5,
+        7 => KeyCode::Alpha6,
+        8 => KeyCode::Alpha7,
+        9 => KeyCode::Alpha8,
+        10 => KeyCode::Alpha9,
+        11 => KeyCode::Alpha0,
+        12 => KeyCode::Minus,
+        13 => KeyCode::Equals,
+        14 => KeyCode::Backspace,
+        15 => KeyCode::Tab,
+        16 => KeyCode::Q,
+        17 => KeyCode::W,
+        18 => KeyCode::E,
+        19 => KeyCode::R,
+        20 => KeyCode::T,
+        21 => KeyCode::Y,
+        22 => KeyCode::U,
+        23 => KeyCode::I,
+        24 => KeyCode::O,
+        25 => KeyCode::P,
+        26 => KeyCode::BracketLeft,
+        27 => KeyCode::BracketRight,
+        28 => KeyCode::Return,
+        29 => KeyCode::ControlLeft,
+        30 => KeyCode::A,
+        31 => KeyCode::S,
+        32 => KeyCode::D,
+        33 => KeyCode::F,
+        34 => KeyCode::G,
+        35 => KeyCode::H,
+        36 => KeyCode::J,
+        37 => KeyCode::K,
+        38 => KeyCode::L,
+        39 => KeyCode::Semicolon,
+        40 => KeyCode::Apostrophe,
+        41 => KeyCode::Grave,
+        42 => KeyCode::ShiftLeft,
+        43 => KeyCode::Backslash,
+        44 => KeyCode::Z,
+        45 => KeyCode::X,
+        46 => KeyCode::C,
+        47 => KeyCode::V,
+        48 => KeyCode::B,
+        49 => KeyCode::N,
+        50 => KeyCode::M,
+        51 => KeyCode::Comma,
+        52 => KeyCode::Period,
+        53 => KeyCode::Slash,
+        54 => KeyCode::ShiftRight,
+        55 => KeyCode::NumpadMultiply,
+        56 => KeyCode::AltLeft,
+        57 => KeyCode::Space,
+        58 => KeyCode::CapsLock,
+        59 => KeyCode::F1,
+        60 => KeyCode::F2,
+        61 => KeyCode::F3,
+        62 => KeyCode::F4,
+        63 => KeyCode::F5,
+        64 => KeyCode::F6,
+        65 => KeyCode::F7,
+        66 => KeyCode::F8,
+        67 => KeyCode::F9,
+        68 => KeyCode::F10,
+        69 => KeyCode::NumLock,
+        70 => KeyCode::ScrollLock,
+        71 => KeyCode::Numpad7,
+        72 => KeyCode::Numpad8,
+        73 => KeyCode::Numpad9,
+        74 => KeyCode::NumpadSubtract,
+        75 => KeyCode::Numpad4,
+        76 => KeyCode::Numpad5,
+        77 => KeyCode::Numpad6,
+        78 => KeyCode::NumpadAdd,
+        79 => KeyCode::Numpad1,
+        80 => KeyCode::Numpad2,
+        81 => KeyCode::Numpad3,
+        82 => KeyCode::Numpad0,
+        83 => KeyCode::NumpadDecimal,
+        86 => KeyCode::IntlBackslash,
+        87 => KeyCode::F11,
+        88 => KeyCode::F12,
+        92 => KeyCode::Convert,
+        94 => KeyCode::NonConvert,
+        96 => KeyCode::NumpadEnter,
+        97 => KeyCode::ControlRight,
+        98 => KeyCode::NumpadDivide,
+        99 => KeyCode::PrintScreen,
+        100 => KeyCode::AltRight,
+        102 => KeyCode::Home,
+        103 => KeyCode::ArrowUp,
+        104 => KeyCode::PageUp,
+        105 => KeyCode::ArrowLeft,
+        106 => KeyCode::ArrowRight,
+        107 => KeyCode::End,
+        108 => KeyCode::ArrowDown,
+        109 => KeyCode::PageDown,
+        110 => KeyCode::Insert,
+        111 => KeyCode::Delete,
+        113 => KeyCode::VolumeMute,
+        114 => KeyCode::VolumeDown,
+        115 => KeyCode::VolumeUp,
+        116 => KeyCode::Power,
+        117 => KeyCode::NumpadEquals,
+        119 => KeyCode::Pause,
+        121 => KeyCode::NumpadComma,
+        124 => KeyCode::Yen,
+        125 => KeyCode::MetaLeft,
+        126 => KeyCode::MetaRight,
+        127 => KeyCode::ContextMenu,
+        128 => KeyCode::BrowserStop,
+        133 => KeyCode::Copy,
+        135 => KeyCode::Paste,
+        137 => KeyCode::Cut,
+        140 => KeyCode::LaunchCalculator,
+        142 => KeyCode::Sleep,
+        143 => KeyCode::Wake,
+        155 => KeyCode::LaunchMail,
+        156 => KeyCode::BrowserFavorites,
+        157 => KeyCode::LaunchMyComputer,
+        158 => KeyCode::BrowserBack,
+        159 => KeyCode::BrowserForward,
+        163 => KeyCode::MediaNextTrack,
+        164 => KeyCode::MediaPlayPause,
+        165 => KeyCode::MediaPrevTrack,
+        166 => KeyCode::MediaStop,
+        172 => KeyCode::BrowserHome,
+        173 => KeyCode::BrowserRefresh,
+        183 => KeyCode::F13,
+        184 => KeyCode::F14,
+        185 => KeyCode::F15,
+        186 => KeyCode::F16,
+        187 => KeyCode::F17,
+        188 => KeyCode::F18,
+        189 => KeyCode::F19,
+        190 => KeyCode::F20,
+        191 => KeyCode::F21,
+        192 => KeyCode::F22,
+        193 => KeyCode::F23,
+        194 => KeyCode::F24,
+        217 => KeyCode::BrowserSearch,
+        226 => KeyCode::MediaSelect,
+        _ => KeyCode::Invalid,
     }
 }
 
@@ -175,6 +329,59 @@ pub fn element_state_to_hell(state: winit::event::ElementState) -> KeyState {
     }
 }
 
+// x11 reports the side buttons as 8 and 9, other buttons are ignored
+pub(crate) fn mouse_button_to_hell(button: winit::event::MouseButton) -> Option<MouseButton> {
+    match button {
+        winit::event::MouseButton::Left     => Some(MouseButton::Left),
+        winit::event::MouseButton::Right    => Some(MouseButton::Right),
+        winit::event::MouseButton::Middle   => Some(MouseButton::Middle),
+        winit::event::MouseButton::Other(8) => Some(MouseButton::Back),
+        winit::event::MouseButton::Other(9) => Some(MouseButton::Forward),
+        winit::event::MouseButton::Other(_) => None,
+    }
+}
+
+// touchpads report pixels instead of lines
+const SCROLL_PIXELS_PER_LINE: f32 = 20.0;
+
+pub(crate) fn scroll_delta_to_hell(delta: winit::event::MouseScrollDelta) -> Vec2 {
+    match delta {
+        winit::event::MouseScrollDelta::LineDelta(x, y) => Vec2::new(x, y),
+        winit::event::MouseScrollDelta::PixelDelta(pos) => Vec2::new(pos.x as f32, pos.y as f32) / SCROLL_PIXELS_PER_LINE,
+    }
+}
+
+pub(crate) fn position_to_hell(position: winit::dpi::PhysicalPosition<f64>) -> Vec2 {
+    Vec2::new(position.x as f32, position.y as f32)
+}
+
+pub(crate) fn edit_key_to_hell(k: &winit::event::VirtualKeyCode) -> Option<EditKey> {
+    match k {
+        winit::event::VirtualKeyCode::Back => Some(EditKey::Backspace),
+        winit::event::VirtualKeyCode::Delete => Some(EditKey::Delete),
+        winit::event::VirtualKeyCode::Home => Some(EditKey::Home),
+        winit::event::VirtualKeyCode::End => Some(EditKey::End),
+        winit::event::VirtualKeyCode::Tab => Some(EditKey::Tab),
+        winit::event::VirtualKeyCode::Return => Some(EditKey::Enter),
+        winit::event::VirtualKeyCode::NumpadEnter => Some(EditKey::Enter),
+        winit::event::VirtualKeyCode::Left => Some(EditKey::Left),
+        winit::event::VirtualKeyCode::Right => Some(EditKey::Right),
+        winit::event::VirtualKeyCode::Up => Some(EditKey::Up),
+        winit::event::VirtualKeyCode::Down => Some(EditKey::Down),
+        _ => None,
+    }
+}
+
+// winit does not distinguish between the left and the right side
+pub(crate) fn modifiers_to_hell(modifiers: winit::event::ModifiersState) -> ModifiersState {
+    let mut result = ModifiersState::empty();
+    result.set(ModifiersState::SHIFT, modifiers.shift());
+    result.set(ModifiersState::CTRL, modifiers.ctrl());
+    result.set(ModifiersState::ALT, modifiers.alt());
+    result.set(ModifiersState::SUPER, modifiers.logo());
+    result
+}
+
 
 // pub(crate) fn update_key_state_winit(&mut self, e: winit::event::KeyboardInput) -> HellResult<()> {
 //     if let Some(code) = e.virtual_keycode {
@@ -1,21 +1,30 @@
 use hell_app::HellApp;
-use hell_common::window::{HellWindow, HellSurfaceInfo, HellWindowExtent};
+use hell_common::window::{HellWindow, HellSurfaceInfo, HellWindowExtent, HellWindowMode, HellCursorMode};
 
-use hell_core::error::{HellResult, OptToHellErr};
+use hell_core::error::{HellResult, HellError, HellErrorKind, OptToHellErr};
+use hell_input::KeyCode;
+use hell_math::Vec2;
 use winit::dpi::LogicalSize;
 use winit::error::OsError;
-use winit::event::{VirtualKeyCode, ElementState, KeyboardInput, WindowEvent, Event};
+use winit::event::{VirtualKeyCode, ElementState, KeyboardInput, WindowEvent, Event, DeviceEvent, Ime};
 use winit::event_loop::{EventLoop, ControlFlow};
+use winit::window::{CursorGrabMode, Fullscreen};
 
-use crate::input::{keycode_to_hell, element_state_to_hell};
+use crate::input::{keycode_to_hell, element_state_to_hell, mouse_button_to_hell, scroll_delta_to_hell, position_to_hell, modifiers_to_hell, edit_key_to_hell, scancode_to_hell};
 use crate::utils::fps_limiter::FPSLimiter;
 
 
 
 
 pub struct Window {
-    event_loop: winit::event_loop::EventLoop<()>,
+    // taken by 'main_loop'
+    event_loop: Option<winit::event_loop::EventLoop<()>>,
     window: winit::window::Window,
+
+    title: String,
+    window_mode: HellWindowMode,
+    cursor_mode: HellCursorMode,
+    close_requested: bool,
 }
 
 impl Window {
@@ -27,10 +36,17 @@ impl Window {
             .with_title(title)
             .with_inner_size(size)
             .build(&event_loop)?;
+        // without it no ime events are reported
+        window.set_ime_allowed(true);
 
         Ok(Self {
-            event_loop,
+            event_loop: Some(event_loop),
             window,
+
+            title: title.to_string(),
+            window_mode: HellWindowMode::Windowed,
+            cursor_mode: HellCursorMode::Visible,
+            close_requested: false,
         })
     }
 }
@@ -39,19 +55,87 @@ impl HellWindow for Window {
     fn create_surface_info(&self) -> HellResult<HellSurfaceInfo> {
         use winit::platform::unix::WindowExtUnix;
 
-        let x11_display = self.window.xlib_display().to_generic_hell_err()?;
+        // wayland handles are only available if winit picked the wayland backend
+        if let (Some(display), Some(surface)) = (self.window.wayland_display(), self.window.wayland_surface()) {
+            return Ok(HellSurfaceInfo::new_wayland(display, surface));
+        }
+
+        let x11_display = self.window.xlib_display().to_window_hell_err()?;
         let x11_window = self.window.xlib_window().to_window_hell_err()?;
 
-        Ok(HellSurfaceInfo::new(x11_display, x11_window))
+        Ok(HellSurfaceInfo::new_xlib(x11_display, x11_window))
     }
 
     fn get_window_extent(&self) -> HellWindowExtent {
-        let inner_size = self.window.inner_size();
+        Self::get_winit_window_extent(&self.window)
+    }
 
-        HellWindowExtent {
-            width: inner_size.width,
-            height: inner_size.height,
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn set_title(&mut self, title: &str) {
+        self.window.set_title(title);
+        self.title = title.to_string();
+    }
+
+    fn window_mode(&self) -> HellWindowMode {
+        self.window_mode
+    }
+
+    fn set_window_mode(&mut self, mode: HellWindowMode) {
+        match mode {
+            HellWindowMode::Windowed => {
+                self.window.set_fullscreen(None);
+                self.window.set_decorations(true);
+            }
+            HellWindowMode::Borderless => {
+                self.window.set_fullscreen(None);
+                self.window.set_decorations(false);
+            }
+            HellWindowMode::Fullscreen => {
+                // 'None' picks the monitor the window is currently on
+                self.window.set_fullscreen(Some(Fullscreen::Borderless(None)));
+            }
         }
+
+        self.window_mode = mode;
+    }
+
+    fn cursor_mode(&self) -> HellCursorMode {
+        self.cursor_mode
+    }
+
+    fn set_cursor_mode(&mut self, mode: HellCursorMode) -> HellResult<()> {
+        match mode {
+            HellCursorMode::Visible | HellCursorMode::Hidden => {
+                self.window.set_cursor_grab(CursorGrabMode::None)
+                    .map_err(|e| HellError::from_msg(HellErrorKind::WindowError, format!("failed to release the cursor: {}", e)))?;
+            }
+            HellCursorMode::Grabbed => {
+                // x11 only supports confining, macos only supports locking
+                self.window.set_cursor_grab(CursorGrabMode::Confined)
+                    .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Locked))
+                    .map_err(|e| HellError::from_msg(HellErrorKind::WindowError, format!("failed to grab the cursor: {}", e)))?;
+            }
+        }
+
+        self.window.set_cursor_visible(mode == HellCursorMode::Visible);
+        self.cursor_mode = mode;
+
+        Ok(())
+    }
+
+    fn scale_factor(&self) -> f64 {
+        self.window.scale_factor()
+    }
+
+    fn request_close(&mut self) {
+        self.close_requested = true;
+    }
+
+    fn is_close_requested(&self) -> bool {
+        self.close_requested
     }
 }
 
@@ -68,28 +152,34 @@ impl Window {
 
 impl Window {
     // TODO: error handling
-    pub fn main_loop(self, mut app: HellApp) {
+    pub fn main_loop(mut self, mut app: HellApp) {
         let mut fps = FPSLimiter::new();
         let mut handle_resize = false;
+        let event_loop = self.event_loop.take().expect("the event loop has already been started");
 
 
-        self.event_loop.run(move |event, _, control_flow| {
+        event_loop.run(move |event, _, control_flow| {
             // continously run the event loop, event if the OS hasn't dispatched any events
             control_flow.set_poll();
             // wait until events are available
             // control_flow.set_wait();
 
-            app.advance_frame().unwrap();
-
             match event {
                 Event::WindowEvent { event, .. } => {
-                    Self::handle_window_event(&event, control_flow, &mut app).expect("failed to handle window event");
+                    Self::handle_window_event(&event, control_flow, &self.window, &mut app).expect("failed to handle window event");
                 },
+                Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } => {
+                    app.input.add_mouse_motion(Vec2::new(delta.0 as f32, delta.1 as f32));
+                }
                 Event::MainEventsCleared => {
                     self.window.request_redraw();
                 }
                 Event::RedrawRequested(_) => {
-                    Window::handle_redraw_request(&mut handle_resize, &self.window, &mut app, &mut fps).expect("failed to handle redraw request");
+                    let is_updated = Window::handle_redraw_request(&mut handle_resize, &mut self, &mut app, &mut fps).expect("failed to handle redraw request");
+                    // per-frame input state (released keys, mouse deltas) has to survive until the game was updated
+                    if is_updated {
+                        app.advance_frame().expect("failed to advance the frame");
+                    }
                 }
                 Event::LoopDestroyed => {
                     app.wait_idle().expect("failed to wait for the app to become idle");
@@ -98,11 +188,15 @@ impl Window {
 
             }
 
+            if self.close_requested {
+                *control_flow = ControlFlow::Exit;
+            }
+
             // "drop(app);" on last iteration
         });
     }
 
-    fn handle_window_event(event: &winit::event::WindowEvent, control_flow: &mut winit::event_loop::ControlFlow, app: &mut HellApp) -> HellResult<()> {
+    fn handle_window_event(event: &winit::event::WindowEvent, control_flow: &mut winit::event_loop::ControlFlow, window: &winit::window::Window, app: &mut HellApp) -> HellResult<()> {
         match event {
             WindowEvent::CloseRequested => { *control_flow = ControlFlow::Exit },
 
@@ -111,10 +205,58 @@ impl Window {
                 *control_flow = ControlFlow::Exit;
             }
 
-            WindowEvent::KeyboardInput { input: KeyboardInput { virtual_keycode: Some(keycode), state, .. }, .. } => {
-                let keycode = keycode_to_hell(keycode);
+            // live keyboard input would break the determinism of a replay
+            WindowEvent::KeyboardInput { .. } | WindowEvent::ModifiersChanged(_) if app.is_replaying() => (),
+
+            WindowEvent::KeyboardInput { input: KeyboardInput { scancode, virtual_keycode, state, .. }, .. } => {
                 let state = element_state_to_hell(*state);
-                app.input.update_key_state(keycode, state)?;
+                let physical = scancode_to_hell(*scancode);
+                let logical = virtual_keycode.as_ref().map(keycode_to_hell).unwrap_or(KeyCode::Invalid);
+
+                if let Some(edit_key) = virtual_keycode.as_ref().and_then(edit_key_to_hell) {
+                    app.input.update_edit_key(edit_key, state);
+                }
+
+                if logical != KeyCode::Invalid {
+                    app.input.update_key_state(logical, state)?;
+                }
+
+                if physical != KeyCode::Invalid {
+                    app.input.update_physical_key_state(physical, state)?;
+                    if logical != KeyCode::Invalid { app.input.update_key_layout(physical, logical); }
+                }
+            }
+
+            WindowEvent::ReceivedCharacter(c) => app.input.push_text_char(*c),
+
+            WindowEvent::Ime(ime) => match ime {
+                Ime::Enabled => app.input.update_ime_enabled(true),
+                Ime::Preedit(text, cursor) => app.input.update_ime_preedit(text.clone(), *cursor),
+                Ime::Commit(text) => app.input.commit_ime_text(text.clone()),
+                Ime::Disabled => app.input.update_ime_enabled(false),
+            },
+
+            WindowEvent::ModifiersChanged(modifiers) => {
+                app.input.update_modifiers_state(modifiers_to_hell(*modifiers));
+            }
+
+            WindowEvent::MouseInput { button, state, .. } => {
+                if let Some(button) = mouse_button_to_hell(*button) {
+                    app.input.update_mouse_button_state(button, element_state_to_hell(*state));
+                }
+            }
+
+            WindowEvent::CursorMoved { position, .. } => {
+                let extent = Self::get_winit_window_extent(window);
+                let window_size = Vec2::new(extent.width as f32, extent.height as f32);
+                app.input.update_cursor_position(position_to_hell(*position), window_size);
+            }
+
+            WindowEvent::CursorEntered { .. } => app.input.update_cursor_inside_window(true),
+            WindowEvent::CursorLeft { .. }    => app.input.update_cursor_inside_window(false),
+
+            WindowEvent::MouseWheel { delta, .. } => {
+                app.input.add_mouse_scroll(scroll_delta_to_hell(*delta));
             }
 
             _ => (),
@@ -123,10 +265,13 @@ impl Window {
         Ok(())
     }
 
-    fn handle_redraw_request(handle_resize: &mut bool, window: &winit::window::Window, app: &mut HellApp, fps: &mut FPSLimiter) -> HellResult<()> {
+    // returns whether the game was updated, frames that only handle a resize don't consume the input
+    fn handle_redraw_request(handle_resize: &mut bool, window: &mut Window, app: &mut HellApp, fps: &mut FPSLimiter) -> HellResult<bool> {
         // TODO: check resize logic
+        let is_updated = !*handle_resize;
+
         if *handle_resize {
-            let window_extent = Window::get_winit_window_extent(window);
+            let window_extent = window.get_window_extent();
 
             if (window_extent.width * window_extent.height) > 0 {
                 app.handle_window_changed(window_extent)?;
@@ -137,11 +282,11 @@ impl Window {
             }
         } else {
             let delta_time = fps.delta_time();
-            *handle_resize = app.draw_frame(delta_time)?;
+            *handle_resize = app.draw_frame(delta_time, window)?;
         }
 
         fps.tick_frame();
 
-        Ok(())
+        Ok(is_updated)
     }
 }